@@ -0,0 +1,302 @@
+//! An immutable map stored in fixed-size arrays, constructed at compile time.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::iter::IntoIterator;
+use core::ops::Index;
+use core::slice;
+use phf_shared::{self, HashFn, HashKey, PhfBorrow, PhfHash};
+
+/// An immutable map of `N` entries stored in fixed-size arrays, constructed
+/// at compile time.
+///
+/// Unlike a `Map`, which points to its entries and displacements, an
+/// `ArrayMap` holds them inline. It can be a `const` rather than a `static`,
+/// and lookups don't need to load any pointers before probing. Since it owns
+/// its entries, the keys and values needn't be `'static`.
+///
+/// The displacement array has `N` elements too, of which only the first
+/// `buckets` are used. This wastes some space but keeps `N` the only
+/// parameter of the type.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_array_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct ArrayMap<K, V, const N: usize> {
+    #[doc(hidden)]
+    pub key: HashKey,
+    #[doc(hidden)]
+    pub hasher: HashFn,
+    /// The number of displacements in use, zero for linearly searched maps.
+    #[doc(hidden)]
+    pub buckets: usize,
+    #[doc(hidden)]
+    pub disps: [(u32, u32); N],
+    #[doc(hidden)]
+    pub entries: [(K, V); N],
+}
+
+impl<K, V, const N: usize> fmt::Debug for ArrayMap<K, V, N>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<'a, K, V, T: ?Sized, const N: usize> Index<&'a T> for ArrayMap<K, V, N>
+where
+    T: Eq + PhfHash,
+    K: PhfBorrow<T>,
+{
+    type Output = V;
+
+    fn index(&self, k: &'a T) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<K, V, const N: usize> ArrayMap<K, V, N> {
+    /// Returns the number of entries in the `ArrayMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns true if the `ArrayMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `ArrayMap`.
+    pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T: ?Sized>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This can be useful for interning schemes.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&K>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get_entry(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T: ?Sized>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        if self.buckets == 0 {
+            // Small maps don't use displacements and are searched linearly,
+            // which is cheaper than hashing the key.
+            return self
+                .entries
+                .iter()
+                .find(|e| e.0.borrow() == key)
+                .map(|e| (&e.0, &e.1));
+        }
+        let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+        let disps = self.disps.get(..self.buckets)?;
+        let index = phf_shared::try_get_index(&hashes, disps, N)?;
+        let (k, v) = self.entries.get(index as usize)?;
+        if k.borrow() == key {
+            Some((k, v))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in an arbitrary but fixed order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a ArrayMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the key/value pairs in an `ArrayMap`.
+pub struct Entries<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next_back().map(|e| (&e.0, &e.1))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
+
+/// An iterator over the keys in an `ArrayMap`.
+pub struct Keys<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Keys<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values in an `ArrayMap`.
+pub struct Values<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Values<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Values<'a, K, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back().map(|e| e.1)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
@@ -0,0 +1,118 @@
+//! An immutable map of string keys constructed at compile time, which ignores
+//! ASCII case on lookup.
+use core::fmt;
+use core::iter::IntoIterator;
+use core::ops::Index;
+
+use phf_shared::AsciiFolded;
+
+use crate::map::{Entries, Keys, Values};
+use crate::Map;
+
+/// An immutable map of string keys constructed at compile time, which
+/// ignores ASCII case on lookup.
+///
+/// Looking up `"Content-Type"` finds the entry declared as `"content-type"`,
+/// and `get_entry` and `get_key` return the key as it was declared. Unlike
+/// keying a `Map` by `UniCase`, the keys stay plain `&'static str`s.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_ascii_case_insensitive_map!` macro and code generation. They are
+/// subject to change at any time and should never be accessed directly.
+pub struct AsciiCaseInsensitiveMap<'a, V> {
+    #[doc(hidden)]
+    pub map: Map<'a, &'a str, V>,
+}
+
+impl<V> fmt::Debug for AsciiCaseInsensitiveMap<'_, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(fmt)
+    }
+}
+
+impl<V> Index<&str> for AsciiCaseInsensitiveMap<'_, V> {
+    type Output = V;
+
+    fn index(&self, k: &str) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<'a, V> AsciiCaseInsensitiveMap<'a, V> {
+    /// Returns the number of entries in the `AsciiCaseInsensitiveMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `AsciiCaseInsensitiveMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `AsciiCaseInsensitiveMap`, ignoring
+    /// ASCII case.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to, ignoring ASCII
+    /// case.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key, as it was declared.
+    ///
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key(&self, key: &str) -> Option<&'a &'a str> {
+        self.find(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the declared key and the value.
+    pub fn get_entry(&self, key: &str) -> Option<(&&'a str, &V)> {
+        self.find(key)
+    }
+
+    fn find(&self, key: &str) -> Option<(&'a &'a str, &'a V)> {
+        self.map
+            .find(&AsciiFolded(key), |k| k.eq_ignore_ascii_case(key))
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, &'a str, V> {
+        self.map.entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, &'a str, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in an arbitrary but fixed order.
+    pub fn values(&self) -> Values<'_, &'a str, V> {
+        self.map.values()
+    }
+}
+
+impl<'a, 'b, V> IntoIterator for &'b AsciiCaseInsensitiveMap<'a, V> {
+    type Item = (&'b &'a str, &'b V);
+    type IntoIter = Entries<'b, &'a str, V>;
+
+    fn into_iter(self) -> Entries<'b, &'a str, V> {
+        self.entries()
+    }
+}
@@ -0,0 +1,217 @@
+//! An immutable set of small integer-like values constructed at compile time.
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::iter::IntoIterator;
+use core::marker::PhantomData;
+
+use phf_shared::PhfBitIndex;
+
+/// An immutable set of small integer-like values constructed at compile time.
+///
+/// Rather than hashing, a `BitSet` stores one bit per value between its
+/// smallest and largest element, so membership is a single bit test. A
+/// `BitSet<u8>` takes at most 32 bytes.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_bit_set!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct BitSet<'a, T> {
+    #[doc(hidden)]
+    pub offset: u64,
+    #[doc(hidden)]
+    pub len: usize,
+    #[doc(hidden)]
+    pub words: &'a [u64],
+    #[doc(hidden)]
+    pub marker: PhantomData<T>,
+}
+
+/// Ties the element type of a macro generated `BitSet` to its keys.
+#[doc(hidden)]
+pub const fn marker<T>(_: &[T]) -> PhantomData<T> {
+    PhantomData
+}
+
+impl<T> fmt::Debug for BitSet<'_, T>
+where
+    T: fmt::Debug + PhfBitIndex,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self).finish()
+    }
+}
+
+impl<T> BitSet<'_, T> {
+    /// Returns the number of elements in the `BitSet`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the `BitSet` contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> BitSet<'_, T>
+where
+    T: PhfBitIndex,
+{
+    /// Returns true if `value` is in the `BitSet`.
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        let bit = value.bit_index().wrapping_sub(self.offset);
+        let word = usize::try_from(bit / 64)
+            .ok()
+            .and_then(|i| self.words.get(i));
+        match word {
+            Some(word) => word >> (bit % 64) & 1 != 0,
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the values in the set.
+    ///
+    /// Values are returned in ascending order of their bit index, which is
+    /// their numeric order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            offset: self.offset,
+            words: self.words,
+            front: 0,
+            back: self.words.len() as u64 * 64,
+            remaining: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if `other` shares no elements with `self`.
+    #[inline]
+    pub fn is_disjoint(&self, other: &BitSet<'_, T>) -> bool {
+        !self.iter().any(|value| other.contains(&value))
+    }
+
+    /// Returns true if `other` contains all values in `self`.
+    #[inline]
+    pub fn is_subset(&self, other: &BitSet<'_, T>) -> bool {
+        self.iter().all(|value| other.contains(&value))
+    }
+
+    /// Returns true if `self` contains all values in `other`.
+    #[inline]
+    pub fn is_superset(&self, other: &BitSet<'_, T>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BitSet<'_, T>
+where
+    T: PhfBitIndex,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the values in a `BitSet`.
+pub struct Iter<'a, T> {
+    offset: u64,
+    words: &'a [u64],
+    // the bits in `front..back` haven't been visited yet
+    front: u64,
+    back: u64,
+    remaining: usize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            offset: self.offset,
+            words: self.words,
+            front: self.front,
+            back: self.back,
+            remaining: self.remaining,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Iter<'a, T>
+where
+    T: fmt::Debug + PhfBitIndex,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PhfBitIndex,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.front < self.back {
+            let word = self.words[(self.front / 64) as usize] >> (self.front % 64);
+            if word == 0 {
+                // skip to the start of the next word
+                self.front = (self.front / 64 + 1) * 64;
+                continue;
+            }
+            let bit = self.front + u64::from(word.trailing_zeros());
+            if bit >= self.back {
+                break;
+            }
+            self.front = bit + 1;
+            self.remaining -= 1;
+            return Some(T::from_bit_index(self.offset + bit));
+        }
+        self.front = self.back;
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: PhfBitIndex,
+{
+    fn next_back(&mut self) -> Option<T> {
+        while self.front < self.back {
+            let last = self.back - 1;
+            let word = self.words[(last / 64) as usize] << (63 - last % 64);
+            if word == 0 {
+                // skip to the end of the previous word
+                self.back = last - last % 64;
+                continue;
+            }
+            let bit = last - u64::from(word.leading_zeros());
+            if bit < self.front {
+                break;
+            }
+            self.back = bit;
+            self.remaining -= 1;
+            return Some(T::from_bit_index(self.offset + bit));
+        }
+        self.back = self.front;
+        None
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: PhfBitIndex {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> where T: PhfBitIndex {}
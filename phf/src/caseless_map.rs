@@ -0,0 +1,120 @@
+//! An immutable map of string keys constructed at compile time, which ignores
+//! Unicode case on lookup.
+use core::fmt;
+use core::iter::IntoIterator;
+use core::ops::Index;
+
+use phf_shared::UnicodeFolded;
+use unicase_::UniCase;
+
+use crate::map::{Entries, Keys, Values};
+use crate::Map;
+
+/// An immutable map of string keys constructed at compile time, which
+/// ignores Unicode case on lookup.
+///
+/// Keys and probes are compared by their full Unicode case folding, so
+/// looking up `"STRASSE"` finds the entry declared as `"Straße"`. Like an
+/// [`AsciiCaseInsensitiveMap`](crate::AsciiCaseInsensitiveMap), `get_entry`
+/// and `get_key` return the key as it was declared. Requires the `caseless`
+/// feature.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_caseless_map!` macro and code generation. They are subject to change
+/// at any time and should never be accessed directly.
+pub struct CaselessMap<'a, V> {
+    #[doc(hidden)]
+    pub map: Map<'a, &'a str, V>,
+}
+
+impl<V> fmt::Debug for CaselessMap<'_, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(fmt)
+    }
+}
+
+impl<V> Index<&str> for CaselessMap<'_, V> {
+    type Output = V;
+
+    fn index(&self, k: &str) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<'a, V> CaselessMap<'a, V> {
+    /// Returns the number of entries in the `CaselessMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `CaselessMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `CaselessMap`, ignoring case.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to, ignoring case.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key, as it was declared.
+    ///
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key(&self, key: &str) -> Option<&'a &'a str> {
+        self.find(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the declared key and the value.
+    pub fn get_entry(&self, key: &str) -> Option<(&&'a str, &V)> {
+        self.find(key)
+    }
+
+    fn find(&self, key: &str) -> Option<(&'a &'a str, &'a V)> {
+        self.map.find(&UnicodeFolded(key), |k| {
+            UniCase::unicode(*k) == UniCase::unicode(key)
+        })
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, &'a str, V> {
+        self.map.entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, &'a str, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in an arbitrary but fixed order.
+    pub fn values(&self) -> Values<'_, &'a str, V> {
+        self.map.values()
+    }
+}
+
+impl<'a, 'b, V> IntoIterator for &'b CaselessMap<'a, V> {
+    type Item = (&'b &'a str, &'b V);
+    type IntoIter = Entries<'b, &'a str, V>;
+
+    fn into_iter(self) -> Entries<'b, &'a str, V> {
+        self.entries()
+    }
+}
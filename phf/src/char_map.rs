@@ -0,0 +1,255 @@
+//! An immutable map of `char` keys stored in a two-level table, constructed
+//! at compile time.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::iter::IntoIterator;
+use core::ops::Index;
+use core::slice;
+use phf_shared::CHAR_PAGE_BITS;
+
+/// An immutable map of `char` keys stored in a two-level table, constructed
+/// at compile time.
+///
+/// The chars are split into pages of 256, like the tables used for Unicode
+/// property lookups. Lookups index the page of the key and then its slot
+/// within that page, without hashing or comparing keys. Pages without keys
+/// share their storage, so this is more compact than a `Map` when the keys
+/// cluster in a few pages, e.g. a few scripts.
+///
+/// Entries are iterated in the order they were defined in.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct CharMap<'a, V> {
+    /// The block of each page, up to the last page with a key.
+    #[doc(hidden)]
+    pub pages: &'a [u16],
+    /// One more than the index of the entry of each char, or zero.
+    #[doc(hidden)]
+    pub blocks: &'a [u16],
+    #[doc(hidden)]
+    pub entries: &'a [(char, V)],
+}
+
+impl<V> fmt::Debug for CharMap<'_, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<'a, V> Index<&'a char> for CharMap<'_, V> {
+    type Output = V;
+
+    fn index(&self, k: &'a char) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<V> CharMap<'_, V> {
+    /// Returns the number of entries in the `CharMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the `CharMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `CharMap`.
+    pub fn contains_key(&self, key: &char) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get(&self, key: &char) -> Option<&V> {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry(&self, key: &char) -> Option<(&char, &V)> {
+        let c = *key as usize;
+        let block = *self.pages.get(c >> CHAR_PAGE_BITS)? as usize;
+        let slot = *self
+            .blocks
+            .get((block << CHAR_PAGE_BITS) + (c & ((1 << CHAR_PAGE_BITS) - 1)))?;
+        let (key, value) = self.entries.get(usize::from(slot).checked_sub(1)?)?;
+        Some((key, value))
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in the same order in which they are defined.
+    pub fn entries(&self) -> Entries<'_, V> {
+        Entries {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in the same order in which they are defined.
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in the same order in which they are defined.
+    pub fn values(&self) -> Values<'_, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a CharMap<'_, V> {
+    type Item = (&'a char, &'a V);
+    type IntoIter = Entries<'a, V>;
+
+    fn into_iter(self) -> Entries<'a, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the key/value pairs in a `CharMap`.
+pub struct Entries<'a, V> {
+    iter: slice::Iter<'a, (char, V)>,
+}
+
+impl<'a, V> Clone for Entries<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Entries<'a, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Entries<'a, V> {
+    type Item = (&'a char, &'a V);
+
+    fn next(&mut self) -> Option<(&'a char, &'a V)> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Entries<'a, V> {
+    fn next_back(&mut self) -> Option<(&'a char, &'a V)> {
+        self.iter.next_back().map(|e| (&e.0, &e.1))
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Entries<'a, V> {}
+
+impl<'a, V> FusedIterator for Entries<'a, V> {}
+
+/// An iterator over the keys in a `CharMap`.
+pub struct Keys<'a, V> {
+    iter: Entries<'a, V>,
+}
+
+impl<'a, V> Clone for Keys<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Keys<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Keys<'a, V> {
+    type Item = &'a char;
+
+    fn next(&mut self) -> Option<&'a char> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Keys<'a, V> {
+    fn next_back(&mut self) -> Option<&'a char> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Keys<'a, V> {}
+
+impl<'a, V> FusedIterator for Keys<'a, V> {}
+
+/// An iterator over the values in a `CharMap`.
+pub struct Values<'a, V> {
+    iter: Entries<'a, V>,
+}
+
+impl<'a, V> Clone for Values<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Values<'a, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Values<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Values<'a, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back().map(|e| e.1)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Values<'a, V> {}
+
+impl<'a, V> FusedIterator for Values<'a, V> {}
@@ -0,0 +1,143 @@
+//! `clap` value parsers backed by phf tables.
+//!
+//! Requires the `clap` feature.
+//!
+//! ```
+//! # extern crate clap_ as clap;
+//! use clap::{Arg, Command};
+//! use phf::{phf_map, phf_set};
+//!
+//! #[derive(Clone, Copy, Debug, PartialEq)]
+//! enum Level {
+//!     Low,
+//!     High,
+//! }
+//!
+//! static COLORS: phf::Set<&'static str> = phf_set!("auto", "always", "never");
+//! static LEVELS: phf::Map<&'static str, Level> = phf_map! {
+//!     "low" => Level::Low,
+//!     "high" => Level::High,
+//! };
+//!
+//! fn main() {
+//!     let command = Command::new("app")
+//!         .arg(Arg::new("color").long("color").value_parser(COLORS.value_parser()))
+//!         .arg(
+//!             Arg::new("level")
+//!                 .long("level")
+//!                 .ignore_case(true)
+//!                 .value_parser(LEVELS.value_parser()),
+//!         );
+//!
+//!     let matches = command
+//!         .clone()
+//!         .try_get_matches_from(["app", "--color", "never", "--level", "HIGH"])
+//!         .unwrap();
+//!     assert_eq!(Some(&"never"), matches.get_one::<&'static str>("color"));
+//!     assert_eq!(Some(&Level::High), matches.get_one::<Level>("level"));
+//!
+//!     assert!(command.try_get_matches_from(["app", "--color", "blue"]).is_err());
+//! }
+//! ```
+use clap_::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap_::{Arg, Command, Error};
+use std::boxed::Box;
+use std::ffi::OsStr;
+
+use crate::{Map, Set};
+
+/// A `clap` value parser which accepts the strings in a `Set`, returned by
+/// `Set::value_parser`.
+///
+/// The strings are also listed as the possible values in the help text.
+#[derive(Clone, Copy)]
+pub struct SetValueParser {
+    set: &'static Set<'static, &'static str>,
+}
+
+/// A `clap` value parser which accepts the keys of a `Map` and produces the
+/// value they map to, returned by `Map::value_parser`.
+///
+/// The keys are also listed as the possible values in the help text.
+pub struct MapValueParser<V: 'static> {
+    map: &'static Map<'static, &'static str, V>,
+}
+
+impl<V> Clone for MapValueParser<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for MapValueParser<V> {}
+
+impl Set<'static, &'static str> {
+    /// Returns a `clap` value parser which accepts the strings in the set.
+    ///
+    /// Requires the `clap` feature.
+    pub fn value_parser(&'static self) -> SetValueParser {
+        SetValueParser { set: self }
+    }
+}
+
+impl<V> Map<'static, &'static str, V> {
+    /// Returns a `clap` value parser which accepts the keys of the map and
+    /// produces the value they map to.
+    ///
+    /// Requires the `clap` feature.
+    pub fn value_parser(&'static self) -> MapValueParser<V> {
+        MapValueParser { map: self }
+    }
+}
+
+/// Parses `value` as one of `keys`, returning the matching entry.
+///
+/// Validation is left to `PossibleValuesParser`, so that errors are reported
+/// like any other argument with possible values.
+fn parse_entry<V>(
+    map: &'static Map<'static, &'static str, V>,
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &OsStr,
+) -> Result<(&'static &'static str, &'static V), Error> {
+    let value = PossibleValuesParser::new(map.keys().copied()).parse_ref(cmd, arg, value)?;
+    // an argument set to ignore case accepts keys with different case
+    let entry = map.get_entry(value.as_str()).or_else(|| {
+        map.entries()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&value))
+    });
+    Ok(entry.expect("value accepted by possible values parser"))
+}
+
+impl TypedValueParser for SetValueParser {
+    type Value = &'static str;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<&'static str, Error> {
+        parse_entry(&self.set.map, cmd, arg, value).map(|e| *e.0)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.set.iter().map(|s| PossibleValue::new(*s))))
+    }
+}
+
+impl<V> TypedValueParser for MapValueParser<V>
+where
+    V: Clone + Send + Sync,
+{
+    type Value = V;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<V, Error> {
+        parse_entry(self.map, cmd, arg, value).map(|e| e.1.clone())
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.map.keys().map(|k| PossibleValue::new(*k))))
+    }
+}
@@ -0,0 +1,156 @@
+//! An immutable map constructed at compile time, with a value for every key
+//! it doesn't contain.
+use core::fmt;
+use core::iter::IntoIterator;
+use core::ops::Index;
+
+use phf_shared::{PhfBorrow, PhfHash};
+
+use crate::map::{Entries, Keys, Values};
+use crate::Map;
+
+/// An immutable map constructed at compile time, with a default value for
+/// every key it doesn't contain.
+///
+/// `phf_map!` generates one for a map with a `_ => value` entry. The default
+/// is stored once, and [`get_or_default`](DefaultMap::get_or_default) and
+/// indexing never fail.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct DefaultMap<'a, K, V> {
+    #[doc(hidden)]
+    pub map: Map<'a, K, V>,
+    #[doc(hidden)]
+    pub default: V,
+}
+
+impl<K, V> fmt::Debug for DefaultMap<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DefaultMap")
+            .field("map", &self.map)
+            .field("default", &self.default)
+            .finish()
+    }
+}
+
+impl<K, V, T: ?Sized> Index<&T> for DefaultMap<'_, K, V>
+where
+    T: Eq + PhfHash,
+    K: PhfBorrow<T>,
+{
+    type Output = V;
+
+    fn index(&self, k: &T) -> &V {
+        self.get_or_default(k)
+    }
+}
+
+impl<'a, K, V> DefaultMap<'a, K, V> {
+    /// Returns the number of entries in the `DefaultMap`, not counting the
+    /// default.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `DefaultMap` has no entries besides the default.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of keys which aren't in the map.
+    #[inline]
+    pub const fn default_value(&self) -> &V {
+        &self.default
+    }
+
+    /// Determines if `key` has an entry of its own in the `DefaultMap`.
+    pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns a reference to the value that `key` maps to, or `None` if it
+    /// would get the default.
+    pub fn get<T: ?Sized>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a reference to the value that `key` maps to, or to the default
+    /// if `key` isn't in the map.
+    pub fn get_or_default<T: ?Sized>(&self, key: &T) -> &V
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get(key).unwrap_or(&self.default)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This can be useful for interning schemes.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T: ?Sized>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get_entry(key)
+    }
+
+    /// Returns an iterator over the key/value pairs in the map, without the
+    /// default.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        self.map.entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in the map, without the default.
+    ///
+    /// Values are returned in an arbitrary but fixed order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a DefaultMap<'_, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
@@ -0,0 +1,100 @@
+//! An immutable map from keys to functions of one signature, constructed at
+//! compile time.
+use core::fmt;
+
+use phf_shared::{PhfBorrow, PhfHash};
+
+use crate::map::{Entries, Keys};
+use crate::Map;
+
+/// An immutable map from keys to functions of one signature, constructed at
+/// compile time, such as the handlers of a command dispatcher.
+///
+/// Each handler takes `Args` and returns `Ret`. Handlers of several
+/// arguments take them as a tuple. The handlers of a `static` map can only
+/// borrow `'static` data from their arguments, since `Args` can't name a
+/// lifetime of each call.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_dispatch_map!` macro and code generation. They are subject to change
+/// at any time and should never be accessed directly.
+pub struct DispatchMap<'a, K, Args, Ret> {
+    #[doc(hidden)]
+    pub map: Map<'a, K, fn(Args) -> Ret>,
+}
+
+impl<K, Args, Ret> fmt::Debug for DispatchMap<'_, K, Args, Ret>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self.keys()).finish()
+    }
+}
+
+impl<K, Args, Ret> DispatchMap<'_, K, Args, Ret> {
+    /// Returns the number of handlers in the `DispatchMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `DispatchMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` has a handler in the `DispatchMap`.
+    pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the handler of `key`.
+    pub fn get<T: ?Sized>(&self, key: &T) -> Option<fn(Args) -> Ret>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get(key).copied()
+    }
+
+    /// Calls the handler of `key` with `args`, returning its result, or
+    /// `None` if `key` has no handler.
+    pub fn call<T: ?Sized>(&self, key: &T, args: Args) -> Option<Ret>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get(key).map(|handler| handler(args))
+    }
+
+    /// Returns an iterator over the keys and handlers in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, fn(Args) -> Ret> {
+        self.map.entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, K, fn(Args) -> Ret> {
+        self.map.keys()
+    }
+}
+
+impl<'a, K, Args, Ret> IntoIterator for &'a DispatchMap<'_, K, Args, Ret> {
+    type Item = (&'a K, &'a fn(Args) -> Ret);
+    type IntoIter = Entries<'a, K, fn(Args) -> Ret>;
+
+    fn into_iter(self) -> Entries<'a, K, fn(Args) -> Ret> {
+        self.entries()
+    }
+}
@@ -0,0 +1,16 @@
+//! Support for enums generated by `phf_enum!`.
+use core::fmt;
+
+/// The error returned when parsing a string which isn't the string of any
+/// variant of an enum generated by `phf_enum!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseEnumError;
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("unknown variant")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseEnumError {}
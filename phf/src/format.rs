@@ -0,0 +1,89 @@
+//! `defmt::Format` implementations, which format like the `Debug` ones.
+//!
+//! Requires the `defmt` feature.
+use defmt::{write, Format, Formatter};
+
+use crate::{map, ordered_map, ordered_set, set, Map, OrderedMap, OrderedSet, Set};
+
+/// Writes `items` separated by commas, leaving the brackets to the caller.
+fn format_items<T: Format>(fmt: Formatter<'_>, items: impl Iterator<Item = T>) {
+    for (i, item) in items.enumerate() {
+        if i != 0 {
+            write!(fmt, ", ");
+        }
+        write!(fmt, "{}", item);
+    }
+}
+
+/// A map entry formatted as `k: v`.
+struct Pair<'a, K, V>(&'a K, &'a V);
+
+impl<'a, K: Format, V: Format> Format for Pair<'a, K, V> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "{}: {}", self.0, self.1);
+    }
+}
+
+impl<K: Format, V: Format> Format for Map<'_, K, V> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "{{");
+        format_items(fmt, self.entries().map(|(k, v)| Pair(k, v)));
+        write!(fmt, "}}");
+    }
+}
+
+impl<T: Format> Format for Set<'_, T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "{{");
+        format_items(fmt, self.iter());
+        write!(fmt, "}}");
+    }
+}
+
+impl<K: Format, V: Format> Format for OrderedMap<'_, K, V> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "{{");
+        format_items(fmt, self.entries().map(|(k, v)| Pair(k, v)));
+        write!(fmt, "}}");
+    }
+}
+
+impl<T: Format> Format for OrderedSet<'_, T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "{{");
+        format_items(fmt, self.iter());
+        write!(fmt, "}}");
+    }
+}
+
+impl<'a, K: Format, V: Format> Format for map::Entries<'a, K, V> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "[");
+        format_items(fmt, self.clone());
+        write!(fmt, "]");
+    }
+}
+
+impl<'a, T: Format> Format for set::Iter<'a, T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "[");
+        format_items(fmt, self.clone());
+        write!(fmt, "]");
+    }
+}
+
+impl<'a, K: Format, V: Format> Format for ordered_map::Entries<'a, K, V> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "[");
+        format_items(fmt, self.clone());
+        write!(fmt, "]");
+    }
+}
+
+impl<'a, T: Format> Format for ordered_set::Iter<'a, T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        write!(fmt, "[");
+        format_items(fmt, self.clone());
+        write!(fmt, "]");
+    }
+}
@@ -0,0 +1,121 @@
+//! Conversions to and comparisons with `indexmap`'s `IndexMap` and
+//! `IndexSet`, which keep the order of an `OrderedMap` or `OrderedSet`.
+//!
+//! Requires the `indexmap` feature.
+//!
+//! ```
+//! # extern crate indexmap_ as indexmap;
+//! use indexmap::{IndexMap, IndexSet};
+//! use phf::{phf_ordered_map, phf_ordered_set};
+//!
+//! static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+//!     "foo" => 10,
+//!     "bar" => 11,
+//!     "baz" => 12,
+//! );
+//! static SET: phf::OrderedSet<&'static str> = phf_ordered_set!("hello", "world");
+//!
+//! fn main() {
+//!     let mut index_map = IndexMap::<_, _>::from(&MAP);
+//!     assert_eq!(vec!["foo", "bar", "baz"], index_map.keys().copied().collect::<Vec<_>>());
+//!     assert!(MAP == index_map);
+//!     assert!(index_map == MAP);
+//!
+//!     // order is ignored, but values are not
+//!     index_map.swap_remove("foo");
+//!     index_map.insert("foo", 10);
+//!     assert!(MAP == index_map);
+//!     index_map.insert("qux", 13);
+//!     assert!(MAP != index_map);
+//!     index_map.swap_remove("qux");
+//!     index_map.insert("foo", 0);
+//!     assert!(index_map != MAP);
+//!
+//!     let mut index_set = IndexSet::<_>::from(&SET);
+//!     assert_eq!(vec!["hello", "world"], index_set.iter().copied().collect::<Vec<_>>());
+//!     assert!(SET == index_set);
+//!     assert!(index_set == SET);
+//!
+//!     index_set.reverse();
+//!     assert!(SET == index_set);
+//!     index_set.insert("foo");
+//!     assert!(SET != index_set);
+//! }
+//! ```
+use core::hash::{BuildHasher, Hash};
+use indexmap_::{IndexMap, IndexSet};
+
+use crate::{OrderedMap, OrderedSet};
+
+impl<'a, K, V, S> From<&'a OrderedMap<'_, K, V>> for IndexMap<K, V, S>
+where
+    K: Clone + Hash + Eq,
+    V: Clone,
+    S: BuildHasher + Default,
+{
+    /// Clones the entries of `map` into a new `IndexMap`, in the same order.
+    fn from(map: &'a OrderedMap<'_, K, V>) -> IndexMap<K, V, S> {
+        let mut index_map = IndexMap::with_capacity_and_hasher(map.len(), S::default());
+        index_map.extend(map.entries().map(|(k, v)| (k.clone(), v.clone())));
+        index_map
+    }
+}
+
+impl<'a, T, S> From<&'a OrderedSet<'_, T>> for IndexSet<T, S>
+where
+    T: Clone + Hash + Eq,
+    S: BuildHasher + Default,
+{
+    /// Clones the values of `set` into a new `IndexSet`, in the same order.
+    fn from(set: &'a OrderedSet<'_, T>) -> IndexSet<T, S> {
+        let mut index_set = IndexSet::with_capacity_and_hasher(set.len(), S::default());
+        index_set.extend(set.iter().cloned());
+        index_set
+    }
+}
+
+/// Like `IndexMap`'s own equality, order is ignored.
+impl<K, V, S> PartialEq<IndexMap<K, V, S>> for OrderedMap<'_, K, V>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &IndexMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.entries().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+/// Like `IndexMap`'s own equality, order is ignored.
+impl<K, V, S> PartialEq<OrderedMap<'_, K, V>> for IndexMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &OrderedMap<'_, K, V>) -> bool {
+        other == self
+    }
+}
+
+/// Like `IndexSet`'s own equality, order is ignored.
+impl<T, S> PartialEq<IndexSet<T, S>> for OrderedSet<'_, T>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &IndexSet<T, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
+/// Like `IndexSet`'s own equality, order is ignored.
+impl<T, S> PartialEq<OrderedSet<'_, T>> for IndexSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &OrderedSet<'_, T>) -> bool {
+        other == self
+    }
+}
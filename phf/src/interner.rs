@@ -0,0 +1,88 @@
+//! A static string interner constructed at compile time.
+use core::fmt;
+use core::iter::IntoIterator;
+
+use crate::{ordered_set, OrderedSet};
+
+/// A static string interner constructed at compile time.
+///
+/// Each string is assigned a dense [`Symbol`], its position in the list the
+/// interner was defined with. Interning is a hash lookup, and resolving a
+/// symbol is an array index.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_interner!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct Interner {
+    #[doc(hidden)]
+    pub set: OrderedSet<'static, &'static str>,
+}
+
+/// An interned string of an [`Interner`].
+///
+/// Symbols of the same interner compare in the order their strings were
+/// defined in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the position of the symbol's string in the list its interner
+    /// was defined with.
+    #[inline]
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.set.fmt(fmt)
+    }
+}
+
+impl Interner {
+    /// Returns the number of strings in the `Interner`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns true if the `Interner` contains no strings.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the symbol of `s`, or `None` if `s` isn't in the interner.
+    pub fn intern(&self, s: &str) -> Option<Symbol> {
+        self.set.get_index(s).map(|i| Symbol(i as u32))
+    }
+
+    /// Returns the string of `symbol`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `symbol` is from a different interner with more strings.
+    pub fn resolve(&self, symbol: Symbol) -> &'static str {
+        self.set.index(symbol.0 as usize).expect("invalid symbol")
+    }
+
+    /// Returns an iterator over the strings in the interner.
+    ///
+    /// Strings are returned in the same order in which they were defined,
+    /// which is the order of their symbols.
+    pub fn iter(&self) -> ordered_set::Iter<'_, &'static str> {
+        self.set.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Interner {
+    type Item = &'a &'static str;
+    type IntoIter = ordered_set::Iter<'a, &'static str>;
+
+    fn into_iter(self) -> ordered_set::Iter<'a, &'static str> {
+        self.iter()
+    }
+}
@@ -0,0 +1,155 @@
+//! An immutable map from ranges of keys to values constructed at compile
+//! time.
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::iter::IntoIterator;
+use core::slice;
+
+/// An immutable map from non-overlapping ranges of keys to values
+/// constructed at compile time.
+///
+/// Lookups binary search the sorted ranges, so a key anywhere within a
+/// range finds its value. This suits tables such as Unicode properties,
+/// where a handful of ranges cover many keys.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_interval_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct IntervalMap<'a, K, V> {
+    /// The inclusive bounds of each range, in ascending order.
+    #[doc(hidden)]
+    pub bounds: &'a [(K, K)],
+    #[doc(hidden)]
+    pub values: &'a [V],
+}
+
+impl<K, V> fmt::Debug for IntervalMap<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.entries()).finish()
+    }
+}
+
+impl<K, V> IntervalMap<'_, K, V> {
+    /// Returns the number of ranges in the `IntervalMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Returns true if the `IntervalMap` has no ranges.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the ranges in the map, as
+    /// `(start, end, value)` with inclusive bounds.
+    ///
+    /// Ranges are returned in ascending order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            bounds: self.bounds.iter(),
+            values: self.values.iter(),
+        }
+    }
+}
+
+impl<K, V> IntervalMap<'_, K, V>
+where
+    K: Ord,
+{
+    /// Determines if `key` is within any range of the `IntervalMap`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value of the range containing `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_entry(key).map(|e| e.2)
+    }
+
+    /// Like `get`, but returns the inclusive bounds of the range as well as
+    /// the value.
+    pub fn get_entry(&self, key: &K) -> Option<(&K, &K, &V)> {
+        let index = self
+            .bounds
+            .binary_search_by(|(start, end)| {
+                if end < key {
+                    Ordering::Less
+                } else if start > key {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()?;
+        let (start, end) = self.bounds.get(index)?;
+        Some((start, end, self.values.get(index)?))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a IntervalMap<'_, K, V> {
+    type Item = (&'a K, &'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the ranges in an `IntervalMap`.
+pub struct Entries<'a, K, V> {
+    bounds: slice::Iter<'a, (K, K)>,
+    values: slice::Iter<'a, V>,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bounds: self.bounds.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a K, &'a V)> {
+        let (start, end) = self.bounds.next()?;
+        Some((start, end, self.values.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bounds.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a K, &'a V)> {
+        let (start, end) = self.bounds.next_back()?;
+        Some((start, end, self.values.next_back()?))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
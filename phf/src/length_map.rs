@@ -0,0 +1,264 @@
+//! An immutable map of string keys constructed at compile time, bucketed by
+//! key length.
+use core::fmt;
+use core::iter::{Flatten, FusedIterator, IntoIterator};
+use core::ops::Index;
+use core::slice;
+
+use crate::Map;
+
+/// An immutable map of string keys constructed at compile time.
+///
+/// Lookups first dispatch on the length of the key and then search a small
+/// per-length `Map`, which is usually no bigger than a handful of entries
+/// for keyword tables and so is searched linearly. Only keys of the same
+/// length are ever compared.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct LengthMap<'a, V> {
+    #[doc(hidden)]
+    pub min_len: usize,
+    #[doc(hidden)]
+    pub len: usize,
+    #[doc(hidden)]
+    pub maps: &'a [Map<'a, &'a str, V>],
+}
+
+impl<V> fmt::Debug for LengthMap<'_, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<V> Index<&str> for LengthMap<'_, V> {
+    type Output = V;
+
+    fn index(&self, k: &str) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<'a, V> LengthMap<'a, V> {
+    /// Returns the number of entries in the `LengthMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the `LengthMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `LengthMap`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key(&self, key: &str) -> Option<&'a &'a str> {
+        self.map(key)?.get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry(&self, key: &str) -> Option<(&&'a str, &V)> {
+        self.map(key)?.get_entry(key)
+    }
+
+    /// Returns the map of the keys as long as `key`.
+    fn map(&self, key: &str) -> Option<&'a Map<'a, &'a str, V>> {
+        self.maps.get(key.len().wrapping_sub(self.min_len))
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in order of ascending key length, and in an
+    /// arbitrary but fixed order otherwise.
+    pub fn entries(&self) -> Entries<'_, V> {
+        Entries {
+            iter: self.maps.iter().flatten(),
+            remaining: self.len,
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in the same order as `entries`.
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in the same order as `entries`.
+    pub fn values(&self) -> Values<'_, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a LengthMap<'_, V> {
+    type Item = (&'a &'a str, &'a V);
+    type IntoIter = Entries<'a, V>;
+
+    fn into_iter(self) -> Entries<'a, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the key/value pairs in a `LengthMap`.
+pub struct Entries<'a, V> {
+    iter: Flatten<slice::Iter<'a, Map<'a, &'a str, V>>>,
+    remaining: usize,
+}
+
+impl<'a, V> Clone for Entries<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Entries<'a, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Entries<'a, V> {
+    type Item = (&'a &'a str, &'a V);
+
+    fn next(&mut self) -> Option<(&'a &'a str, &'a V)> {
+        let entry = self.iter.next()?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Entries<'a, V> {
+    fn next_back(&mut self) -> Option<(&'a &'a str, &'a V)> {
+        let entry = self.iter.next_back()?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Entries<'a, V> {}
+
+impl<'a, V> FusedIterator for Entries<'a, V> {}
+
+/// An iterator over the keys in a `LengthMap`.
+pub struct Keys<'a, V> {
+    iter: Entries<'a, V>,
+}
+
+impl<'a, V> Clone for Keys<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Keys<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Keys<'a, V> {
+    type Item = &'a &'a str;
+
+    fn next(&mut self) -> Option<&'a &'a str> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Keys<'a, V> {
+    fn next_back(&mut self) -> Option<&'a &'a str> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Keys<'a, V> {}
+
+impl<'a, V> FusedIterator for Keys<'a, V> {}
+
+/// An iterator over the values in a `LengthMap`.
+pub struct Values<'a, V> {
+    iter: Entries<'a, V>,
+}
+
+impl<'a, V> Clone for Values<'a, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, V> fmt::Debug for Values<'a, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, V> Iterator for Values<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V> DoubleEndedIterator for Values<'a, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back().map(|e| e.1)
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Values<'a, V> {}
+
+impl<'a, V> FusedIterator for Values<'a, V> {}
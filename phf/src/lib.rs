@@ -8,7 +8,7 @@
 //! If the `macros` Cargo feature is enabled, the `phf_map`, `phf_set`,
 //! `phf_ordered_map`, and `phf_ordered_set` macros can be used to construct
 //! the PHF type. This method can be used with a stable compiler
-//! (minimum supported rust version is 1.46.
+//! (minimum supported rust version is 1.51.
 //! feature).
 //!
 //! ```toml
@@ -38,6 +38,49 @@
 //! Alternatively, you can use the `phf_codegen` crate to generate PHF datatypes
 //! in a build script.
 //!
+//! # Features
+//!
+//! The crate is always `no_std` and only depends on `core` by default.
+//!
+//! * `alloc` implements [`PhfHash`] and `PhfBorrow` for `String`, `Vec<u8>`
+//!   and `Rc<T>`, so maps may use them as keys. It also adds
+//!   [`Map::iter_sorted`] and [`Set::iter_sorted`].
+//! * `std` implies `alloc`, and is enabled by default. It implements
+//!   [`PhfHash`] and `PhfBorrow` for `Arc<T>`, and adds set
+//!   operations with `HashSet`, such as
+//!   [`Set::symmetric_difference_hash_set`].
+//! * `macros` provides the `phf_map!` family of macros.
+//! * `stats` adds methods such as [`Map::max_displacement`] which expose
+//!   details of the generated tables, to help diagnose slow lookups.
+//! * `clap` implies `std`, and provides `clap` value parsers which accept the
+//!   strings of a set or the keys of a map, see the [`clap`](crate::clap)
+//!   module. It requires a compiler supported by `clap` 4.
+//! * `suggest` implies `alloc`, and adds lookups such as
+//!   [`Map::get_or_suggest`] which return the closest key on a miss, see the
+//!   [`suggest`](crate::suggest) module.
+//! * `defmt` implements `defmt::Format` for `Map`, `Set`, `OrderedMap`,
+//!   `OrderedSet` and their entry iterators, to log them on embedded targets.
+//! * `indexmap` implies `std`, and converts an [`OrderedMap`] or
+//!   [`OrderedSet`] into an `IndexMap` or `IndexSet` with the same order, to
+//!   seed a mutable copy. They can also be compared, ignoring order like
+//!   `indexmap` does. It requires a compiler supported by `indexmap` 2.
+//! * `simd` runs the rounds of SipHash with NEON or simd128 instructions on
+//!   `aarch64` and on `wasm32` built with `-C target-feature=+simd128`.
+//!   Lookups hash exactly as before, so tables need not be regenerated.
+//!   Other targets, including x86_64, where vector code was slower, keep the
+//!   scalar SipHash. The NEON and simd128 backends need Rust 1.59 and 1.54
+//!   respectively.
+//! * `unicase` and `uncased` implement [`PhfHash`] for the case-insensitive
+//!   string types of those crates.
+//! * `caseless` adds [`CaselessMap`], which ignores Unicode case with the full
+//!   case folding of the `unicase` crate, where an [`AsciiCaseInsensitiveMap`]
+//!   isn't enough, and `phf_caseless_map!` along with `macros`. It builds
+//!   `phf_macros` even without `macros`.
+//! * `unicode-normalization` implies `alloc`, and adds lookups such as
+//!   [`Map::get_nfc`] which normalize the probe, for tables generated with
+//!   `@normalize = nfc;`, which also requires it, as does `@strict;`. Like `caseless`, it builds
+//!   `phf_macros` even without `macros`.
+//!
 //! ## Note
 //!
 //! Currently, the macro syntax has some limitations and may not
@@ -47,10 +90,14 @@
 //! [#196]: https://github.com/rust-phf/rust-phf/issues/196
 #![doc(html_root_url = "https://docs.rs/phf/0.10")]
 #![warn(missing_docs)]
-#![cfg_attr(not(feature = "std"), no_std)]
+// Lookup methods keep `?Sized` next to the parameter and the rest in `where`.
+#![allow(clippy::multiple_bound_locations)]
+#![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
 #[cfg(feature = "std")]
-extern crate std as core;
+extern crate std;
 
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`Map`].
@@ -71,20 +118,248 @@ extern crate std as core;
 ///     assert_eq!(MY_MAP["hello"], 1);
 /// }
 /// ```
+///
+/// The macros expand to constant expressions, so they can initialize `const`
+/// items, including associated consts of traits and impls, as well as
+/// `static`s. Like any `const`, such a map is copied into every place that
+/// uses it, while its arrays are shared.
+///
+/// # Keys
+///
+/// Keys must be literals: strings, byte strings, chars, bools, integers with
+/// a type suffix, arrays of `u8`, or `UniCase` of a string with the `unicase`
+/// feature. Byte chars such as `b'n'` are `u8` keys, and may be mixed with
+/// suffixed integers:
+///
+/// ```
+/// use phf::{phf_map, Map};
+///
+/// static ESCAPES: Map<u8, u8> = phf_map! {
+///     b'n' => b'\n',
+///     b't' => b'\t',
+///     b'0' => 0u8,
+///     0x5cu8 => b'\\',
+/// };
+///
+/// fn main () {
+///     assert_eq!(Some(&b'\t'), ESCAPES.get(&b't'));
+///     assert_eq!(Some(&b'\\'), ESCAPES.get(&b'\\'));
+/// }
+/// ```
+///
+/// # Default entry
+///
+/// A `_ => value` entry generates a [`DefaultMap`] instead of a [`Map`],
+/// which returns `value` for every key without an entry of its own:
+///
+/// ```
+/// use phf::{phf_map, DefaultMap};
+///
+/// fn not_found() -> u16 {
+///     404
+/// }
+///
+/// fn index() -> u16 {
+///     200
+/// }
+///
+/// static ROUTES: DefaultMap<&'static str, fn() -> u16> = phf_map! {
+///     "/" => index,
+///     "/index.html" => index,
+///     _ => not_found,
+/// };
+///
+/// fn main () {
+///     assert_eq!(200, ROUTES.get_or_default("/")());
+///     assert_eq!(404, ROUTES.get_or_default("/admin")());
+///     assert_eq!(None, ROUTES.get("/admin").map(|route| route()));
+/// }
+/// ```
+///
+/// It can't be combined with `@strategy` or `@shards`, and the other
+/// `phf_*!` macros don't accept it.
+///
+/// # Options
+///
+/// The entries may be preceded by options of the form `@name = value;`, or
+/// `@name;` for flags:
+///
+/// * `@layout = split;` stores keys and values in separate arrays, so that
+///   probing the map doesn't touch value data. This helps when values are
+///   large. The default is `@layout = pairs;`.
+/// * `@align = 64;` pads each `(key, value)` pair to a multiple of 16, 32, 64
+///   or 128 bytes, and aligns the entries to the same boundary. If the padded
+///   size divides the cache line size, for instance 24 byte pairs padded to
+///   32, a probe never straddles two cache lines. This trades space for
+///   lookup latency on large, hot tables, and can't be combined with
+///   `@layout = split;`.
+/// * `@hasher = short_key;` hashes keys of at most 16 bytes with a cheap
+///   inline mix instead of SipHash, see [`HashFn::ShortKey`]. `@hasher = sip;`
+///   and `@hasher = sip64;` select SipHash with 128-bit and 64-bit output. By
+///   default, tables of at most 65536 entries use `sip64` and larger ones
+///   `sip`.
+/// * `@key = 0x...;` derives the 128-bit SipHash key from the given integer
+///   instead of a fixed, publicly known seed. Use it to keep the key of a
+///   security-sensitive map unpredictable, or to get a different table for
+///   the same entries.
+/// * `@strategy = length_bucketed;` generates a [`LengthMap`] instead of a
+///   [`Map`], which dispatches on the length of the key before searching a
+///   small per-length table. Keys must be string literals. This works well for
+///   keyword tables. `@strategy = two_level;` generates a [`CharMap`] of `char`
+///   keys, which looks keys up in a two-level table of 256-char pages instead
+///   of hashing them, and accepts no other option but `@duplicates`. The
+///   default is `@strategy = chd;`.
+/// * `@shards = N;` generates a [`ShardedMap`] of `N` independent tables
+///   instead of a [`Map`]. This is mostly useful with `phf_codegen`, which can
+///   write each shard separately.
+/// * `@fingerprints;` stores a 16-bit fingerprint of each key, which lookups
+///   check before comparing keys. This makes misses cheaper on maps with long
+///   keys, at the cost of two bytes per entry.
+/// * `@filter;` generates a small Bloom filter of the keys, which lookups
+///   check before probing the map. This rejects most missing keys after a
+///   single load and is worth it when most lookups miss. It costs at least
+///   two bytes per entry. Maps of at most five entries are searched linearly
+///   and never get a filter.
+/// * `@definition_order;` makes [`Map::entries`] and the other iterators
+///   return the entries in the order they are written in, like an
+///   [`OrderedMap`]. This costs an extra index per entry. It only applies to
+///   a [`Map`], and can't be combined with `@strategy` or `@shards`.
+/// * `@normalize = nfc;` replaces each key with its Unicode NFC normal form,
+///   and rejects keys which are the same once normalized. Keys must be string
+///   literals. Probe with [`Map::get_nfc`] to normalize the strings looked up
+///   as well. This requires the `unicode-normalization` feature.
+/// * `@strict;` rejects string keys which only differ by ASCII case or Unicode
+///   normalization, such as `"Foo"` and `"foo"`, which are usually mistakes
+///   in the input. This requires the `unicode-normalization` feature.
+/// * `@duplicates = last;` lets a later entry override an earlier one with
+///   the same key, which keeps the position of the first. This suits entries
+///   layered on top of each other on purpose, such as defaults followed by
+///   overrides. `@duplicates = first;` keeps the first entry instead, with a
+///   warning about each one ignored. The default is `@duplicates = error;`.
+/// * `@crate = path;` makes the expansion refer to this crate by `path`
+///   instead of `phf`, for crates which rename their dependency on it, or
+///   macros of other crates which wrap these ones with
+///   `@crate = $crate::path::to::phf;`. Every `phf_*!` macro accepts it.
+///
+/// ```
+/// use phf::{phf_map, Map};
+///
+/// static MY_MAP: Map<&'static str, [u8; 64]> = phf_map! {
+///     @layout = split;
+///     "hello" => [1; 64],
+///     "world" => [2; 64],
+/// };
+/// ```
+///
+/// # Caching
+///
+/// Generating a large map takes a while, and runs again on every clean
+/// build. If the `PHF_MACROS_CACHE_DIR` environment variable is set when
+/// compiling, the hash of each table is saved in that directory, e.g.
+/// `target/phf`, and reused by later builds with the same entries. This
+/// applies to the hash tables of all the `phf_*!` macros, but not to the
+/// split of `@shards`, which takes no search.
+///
+/// A saved hash is only checked with `phf_generator::verify_hash`, that is,
+/// that it gives every entry a slot of its own. A cache file which fails
+/// the check, or can't be read, is generated again and overwritten. One
+/// which was corrupted but still passes is used as it is, so the map works
+/// but may be laid out differently than in a build without the cache.
+/// Deleting the directory clears the cache.
+///
+/// Cargo doesn't know that the expansion reads `PHF_MACROS_CACHE_DIR`, so
+/// setting, changing or unsetting it doesn't rebuild crates which were
+/// already compiled. Only the next expansion of each macro uses the new
+/// value, e.g. after `cargo clean` or an edit of the crate. A crate which
+/// should be rebuilt when it changes can declare it from its build script:
+///
+/// ```no_run
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-env-changed=PHF_MACROS_CACHE_DIR");
+/// }
+/// ```
 #[::proc_macro_hack::proc_macro_hack]
 pub use phf_macros::phf_map;
 
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`OrderedMap`].
 ///
-/// Requires the `macros` feature. Same usage as [`phf_map`].
+/// Requires the `macros` feature. Same usage as [`phf_map`], but only
+/// accepts the `@hasher`, `@key`, `@normalize` and `@duplicates` options and
+/// the `@slot_order;`, `@strict;` and `@sorted;` flags. `@slot_order;` stores the entries in the order they
+/// are probed rather than definition order, which improves cache behavior on
+/// large maps at the cost of an extra index per entry. `@sorted;` rejects keys
+/// which aren't in ascending order, so that the map can be searched with
+/// [`OrderedMap::binary_search_by_key`] and [`OrderedMap::partition_point`].
 #[::proc_macro_hack::proc_macro_hack]
 pub use phf_macros::phf_ordered_map;
 
+#[cfg(feature = "macros")]
+/// Macro to create a `const` or `static` (compile-time) [`ArrayMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], but only
+/// accepts the `@hasher` and `@key` options. The number of entries must be
+/// spelled out in the type.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_array_map, ArrayMap};
+///
+/// const MY_MAP: ArrayMap<&'static str, u32, 2> = phf_array_map! {
+///     "hello" => 1,
+///     "world" => 2,
+/// };
+///
+/// fn main () {
+///     assert_eq!(MY_MAP["hello"], 1);
+/// }
+/// ```
+#[::proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_array_map;
+
+#[cfg(feature = "macros")]
+/// Macro to declare an enum whose variants convert to and from strings.
+///
+/// Requires the `macros` feature. Each unit variant is followed by
+/// `=> "string"`. Besides the enum itself, this generates
+///
+/// * `VARIANTS` and `NAMES` constants with the variants and their strings,
+/// * a `const fn as_str(&self) -> &'static str`,
+/// * an `iter()` function over the variants,
+/// * and a [`FromStr`](core::str::FromStr) impl backed by a [`Map`], which
+///   fails with [`ParseEnumError`].
+///
+/// The `@hasher` and `@key` options are accepted before the enum.
+///
+/// # Example
+///
+/// ```
+/// use phf::phf_enum;
+///
+/// phf_enum! {
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     pub enum Method {
+///         Get => "GET",
+///         Post => "POST",
+///     }
+/// }
+///
+/// fn main () {
+///     assert_eq!("POST", Method::Post.as_str());
+///     assert_eq!(Ok(Method::Get), "GET".parse());
+///     assert!("PUT".parse::<Method>().is_err());
+///     assert_eq!(vec![Method::Get, Method::Post], Method::iter().copied().collect::<Vec<_>>());
+/// }
+/// ```
+pub use phf_macros::phf_enum;
+
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`Set`].
 ///
-/// Requires the `macros` feature.
+/// Requires the `macros` feature. Accepts the same options as [`phf_map`],
+/// except for `@strategy` and `@shards`.
 ///
 /// # Example
 ///
@@ -106,10 +381,266 @@ pub use phf_macros::phf_set;
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`OrderedSet`].
 ///
-/// Requires the `macros` feature. Same usage as [`phf_set`].
+/// Requires the `macros` feature. Same usage as [`phf_set`], but only
+/// accepts the same options as [`phf_ordered_map`].
 #[proc_macro_hack::proc_macro_hack]
 pub use phf_macros::phf_ordered_set;
 
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`Interner`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_ordered_set`], but the
+/// elements must be string literals. The symbol of each string is its
+/// position in the list.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_interner, Interner};
+///
+/// static KEYWORDS: Interner = phf_interner! {
+///     "fn",
+///     "let",
+///     "match",
+/// };
+///
+/// fn main () {
+///     let symbol = KEYWORDS.intern("let").unwrap();
+///     assert_eq!(1, symbol.as_u32());
+///     assert_eq!("let", KEYWORDS.resolve(symbol));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_interner;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`BitSet`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_set`], but the
+/// elements must be literals of a type implementing [`PhfBitIndex`], and
+/// span at most 65536 consecutive values. No options are accepted.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_bit_set, BitSet};
+///
+/// static VOWELS: BitSet<u8> = phf_bit_set! {
+///     b'a', b'e', b'i', b'o', b'u',
+/// };
+///
+/// fn main () {
+///     assert!(VOWELS.contains(&b'e'));
+///     assert!(!VOWELS.contains(&b'z'));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_bit_set;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`AsciiCaseInsensitiveMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], but the keys
+/// must be string literals which are distinct ignoring ASCII case. Accepts
+/// the same options as [`phf_set`].
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_ascii_case_insensitive_map, AsciiCaseInsensitiveMap};
+///
+/// static HEADERS: AsciiCaseInsensitiveMap<u32> = phf_ascii_case_insensitive_map! {
+///     "Content-Type" => 0,
+///     "Content-Length" => 1,
+/// };
+///
+/// fn main () {
+///     assert_eq!(Some((&"Content-Type", &0)), HEADERS.get_entry("content-type"));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_ascii_case_insensitive_map;
+
+#[cfg(all(feature = "macros", feature = "caseless"))]
+/// Macro to create a `static` (compile-time) [`CaselessMap`].
+///
+/// Requires the `macros` and `caseless` features. Same usage as
+/// [`phf_ascii_case_insensitive_map`], but the keys must be distinct
+/// ignoring Unicode case.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_caseless_map, CaselessMap};
+///
+/// static STREETS: CaselessMap<u32> = phf_caseless_map! {
+///     "Hauptstraße" => 0,
+///     "Ελληνικά" => 1,
+/// };
+///
+/// fn main () {
+///     assert_eq!(Some(&0), STREETS.get("HAUPTSTRASSE"));
+///     assert_eq!(Some((&"Ελληνικά", &1)), STREETS.get_entry("ΕΛΛΗΝΙΚΆ"));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_caseless_map;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`DispatchMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], but the values
+/// are functions or non-capturing closures, which are each checked against
+/// the signature of the map. Only the `@layout`, `@align`, `@hasher` and
+/// `@key` options and the `@fingerprints;` and `@filter;` flags are accepted.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_dispatch_map, DispatchMap};
+///
+/// fn add((a, b): (i64, i64)) -> i64 {
+///     a + b
+/// }
+///
+/// static COMMANDS: DispatchMap<&'static str, (i64, i64), i64> = phf_dispatch_map! {
+///     "add" => add,
+///     "sub" => |(a, b)| a - b,
+/// };
+///
+/// fn main () {
+///     assert_eq!(Some(5), COMMANDS.call("add", (2, 3)));
+///     assert_eq!(Some(-1), COMMANDS.call("sub", (2, 3)));
+///     assert_eq!(None, COMMANDS.call("mul", (2, 3)));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_dispatch_map;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`IntervalMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], but the keys
+/// are ranges `start..=end` or `start..end` of integer or char literals,
+/// which must not overlap. No options are accepted.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_interval_map, IntervalMap};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Script {
+///     Latin,
+///     Greek,
+/// }
+///
+/// static SCRIPTS: IntervalMap<u32, Script> = phf_interval_map! {
+///     0x0000..=0x007F => Script::Latin,
+///     0x0370..=0x03FF => Script::Greek,
+/// };
+///
+/// fn main () {
+///     assert_eq!(Some(&Script::Greek), SCRIPTS.get(&('λ' as u32)));
+///     assert_eq!(None, SCRIPTS.get(&0x0100));
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_interval_map;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`TrieMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], but the keys
+/// must all be string literals or all be byte string literals. No options
+/// are accepted.
+///
+/// # Example
+///
+/// ```
+/// use phf::{phf_trie_map, TrieMap};
+///
+/// static MIME_TYPES: TrieMap<&'static [u8], &'static str> = phf_trie_map! {
+///     b"\x89PNG" => "image/png",
+///     b"GIF8" => "image/gif",
+///     b"%PDF-" => "application/pdf",
+/// };
+///
+/// fn main () {
+///     let (_, mime) = MIME_TYPES.longest_prefix(b"GIF89a...").unwrap();
+///     assert_eq!("image/gif", *mime);
+/// }
+/// ```
+#[proc_macro_hack::proc_macro_hack]
+pub use phf_macros::phf_trie_map;
+
+/// Fails compilation unless a string literal is a key of a [`Map`] or an
+/// element of a [`Set`].
+///
+/// This catches typos in well-known keys used far from the table's
+/// definition. Constants can't read statics, so the table must be a `const`;
+/// define the `static` from it to keep a single copy of the entries. The
+/// keys are searched linearly at compile time, and the table must have
+/// `&'static str` keys.
+///
+/// ```
+/// use phf::{phf_assert_key, phf_map, Map};
+///
+/// const KEYWORDS: Map<&'static str, u32> = phf_map! {
+///     "loop" => 0,
+///     "continue" => 1,
+///     "break" => 2,
+/// };
+///
+/// pub static KEYWORD_MAP: Map<&'static str, u32> = KEYWORDS;
+///
+/// phf_assert_key!(KEYWORDS, "break");
+///
+/// fn main() {
+///     assert_eq!(Some(&2), KEYWORD_MAP.get("break"));
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use phf::{phf_assert_key, phf_set, Set};
+///
+/// const KEYWORDS: Set<&'static str> = phf_set!("loop", "continue", "break");
+///
+/// phf_assert_key!(KEYWORDS, "brake");
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! phf_assert_key {
+    ($table:expr, $key:expr $(,)?) => {
+        // the array has one element if the key is missing, which fails to
+        // type check
+        const _: [(); 0] = [(); !$table.__contains_str_const($key) as usize];
+    };
+}
+
+#[doc(inline)]
+pub use self::array_map::ArrayMap;
+#[doc(inline)]
+pub use self::ascii_case_insensitive_map::AsciiCaseInsensitiveMap;
+#[doc(inline)]
+pub use self::bit_set::BitSet;
+#[cfg(feature = "caseless")]
+#[doc(inline)]
+pub use self::caseless_map::CaselessMap;
+#[doc(inline)]
+pub use self::char_map::CharMap;
+#[doc(inline)]
+pub use self::default_map::DefaultMap;
+#[doc(inline)]
+pub use self::dispatch_map::DispatchMap;
+#[doc(inline)]
+pub use self::enums::ParseEnumError;
+#[doc(inline)]
+pub use self::interner::{Interner, Symbol};
+#[doc(inline)]
+pub use self::interval_map::IntervalMap;
+#[doc(inline)]
+pub use self::length_map::LengthMap;
 #[doc(inline)]
 pub use self::map::Map;
 #[doc(inline)]
@@ -118,9 +649,35 @@ pub use self::ordered_map::OrderedMap;
 pub use self::ordered_set::OrderedSet;
 #[doc(inline)]
 pub use self::set::Set;
-pub use phf_shared::PhfHash;
+#[doc(inline)]
+pub use self::sharded_map::ShardedMap;
+#[doc(inline)]
+pub use self::trie_map::TrieMap;
+pub use phf_shared::{HashFn, HashKey, PhfBitIndex, PhfHash};
 
+pub mod array_map;
+pub mod ascii_case_insensitive_map;
+pub mod bit_set;
+#[cfg(feature = "caseless")]
+pub mod caseless_map;
+pub mod char_map;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod default_map;
+pub mod dispatch_map;
+pub mod enums;
+#[cfg(feature = "defmt")]
+mod format;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+pub mod interner;
+pub mod interval_map;
+pub mod length_map;
 pub mod map;
 pub mod ordered_map;
 pub mod ordered_set;
 pub mod set;
+pub mod sharded_map;
+#[cfg(feature = "suggest")]
+pub mod suggest;
+pub mod trie_map;
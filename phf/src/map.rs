@@ -1,28 +1,412 @@
 //! An immutable map constructed at compile time.
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::cmp::Ordering;
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
-use core::ops::Index;
+use core::ops::{Index, Range};
+use core::ptr;
 use core::slice;
-use phf_shared::{self, HashKey, PhfBorrow, PhfHash};
+use phf_shared::{self, HashFn, HashKey, PhfBorrow, PhfHash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
 
 /// An immutable map constructed at compile time.
 ///
+/// `'a` is the lifetime of the tables. It is `'static` for the tables
+/// generated by `phf_map!` and `phf_codegen`, and can be left out of the type
+/// of a `static`, as in `static MAP: Map<&str, u32>`. Maps built with
+/// `from_raw_parts` may also borrow their keys and values.
+///
 /// ## Note
 ///
 /// The fields of this struct are public so that they may be initialized by the
 /// `phf_map!` macro and code generation. They are subject to change at any
 /// time and should never be accessed directly.
-pub struct Map<K: 'static, V: 'static> {
+pub struct Map<'a, K, V> {
     #[doc(hidden)]
     pub key: HashKey,
     #[doc(hidden)]
-    pub disps: &'static [(u32, u32)],
+    pub hasher: HashFn,
+    #[doc(hidden)]
+    pub disps: Disps<'a>,
     #[doc(hidden)]
-    pub entries: &'static [(K, V)],
+    pub entries: Slots<'a, K, V>,
+    /// `None` unless the map was generated with fingerprints, a filter or
+    /// definition order, so that other maps don't pay for the empty slices.
+    #[doc(hidden)]
+    pub side: Option<&'a SideTables<'a>>,
+}
+
+/// The tables of a `Map` which only some maps opt into.
+#[doc(hidden)]
+pub struct SideTables<'a> {
+    pub fingerprints: &'a [u16],
+    pub filter: &'a [u64],
+    /// Empty if the entries are iterated in slot order. Otherwise this maps
+    /// definition order to slots.
+    pub order: &'a [usize],
+}
+
+impl<'a> SideTables<'a> {
+    const EMPTY: SideTables<'a> = SideTables {
+        fingerprints: &[],
+        filter: &[],
+        order: &[],
+    };
+}
+
+/// The displacement table of a `Map` or `OrderedMap`.
+///
+/// Every displacement is smaller than the number of entries, so the
+/// generator picks the narrowest integer type which can hold them.
+///
+/// Tables without a perfect hash function store the sorted
+/// `phf_shared::sort_key` of each entry instead, which are binary searched.
+///
+/// `Packed` points to displacements packed as varints, which large tables
+/// may opt into. It is a single pointer so that it doesn't make the other
+/// variants any larger.
+#[doc(hidden)]
+pub enum Disps<'a> {
+    U8(&'a [(u8, u8)]),
+    U16(&'a [(u16, u16)]),
+    U32(&'a [(u32, u32)]),
+    Sorted(&'a [u64]),
+    Packed(&'a PackedDisps<'a>),
+}
+
+/// Displacements packed as varints, see `phf_shared::get_packed_disps`.
+#[doc(hidden)]
+pub struct PackedDisps<'a> {
+    pub buckets: usize,
+    pub offsets: &'a [u32],
+    pub bytes: &'a [u8],
+}
+
+impl Disps<'_> {
+    #[inline]
+    pub(crate) const fn len(&self) -> usize {
+        match self {
+            Disps::U8(disps) => disps.len(),
+            Disps::U16(disps) => disps.len(),
+            Disps::U32(disps) => disps.len(),
+            Disps::Sorted(hashes) => hashes.len(),
+            Disps::Packed(packed) => packed.buckets,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn max(&self) -> u32 {
+        fn max<D: Copy + Into<u32>>(disps: &[(D, D)]) -> u32 {
+            disps
+                .iter()
+                .map(|&(d1, d2)| d1.into().max(d2.into()))
+                .max()
+                .unwrap_or(0)
+        }
+
+        match self {
+            Disps::U8(disps) => max(disps),
+            Disps::U16(disps) => max(disps),
+            Disps::U32(disps) => max(disps),
+            Disps::Sorted(_) => 0,
+            Disps::Packed(packed) => (0..packed.buckets)
+                .filter_map(|bucket| {
+                    phf_shared::get_packed_disps(packed.offsets, packed.bytes, bucket)
+                })
+                .map(|(d1, d2)| d1.max(d2))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Like `get_index`, but returns `None` rather than panicking if the
+    /// table is inconsistent.
+    #[inline]
+    pub(crate) fn try_get_index(&self, hashes: &phf_shared::Hashes, len: usize) -> Option<usize> {
+        let index = match self {
+            Disps::U8(disps) => phf_shared::try_get_index(hashes, disps, len),
+            Disps::U16(disps) => phf_shared::try_get_index(hashes, disps, len),
+            Disps::U32(disps) => phf_shared::try_get_index(hashes, disps, len),
+            Disps::Packed(packed) => packed.try_get_index(hashes, len),
+            Disps::Sorted(sorted) => {
+                return Some(
+                    sorted
+                        .binary_search(&phf_shared::sort_key(hashes))
+                        .unwrap_or(0),
+                )
+            }
+        };
+        index.map(|index| index as usize)
+    }
+
+    /// Returns the only index at which the key with `hashes` can be.
+    #[inline]
+    pub(crate) fn get_index(&self, hashes: &phf_shared::Hashes, len: usize) -> usize {
+        let index = match self {
+            Disps::U8(disps) => phf_shared::get_index(hashes, disps, len),
+            Disps::U16(disps) => phf_shared::get_index(hashes, disps, len),
+            Disps::U32(disps) => phf_shared::get_index(hashes, disps, len),
+            // inconsistent displacements miss like a missing key, since the
+            // key found is compared afterwards
+            Disps::Packed(packed) => packed.try_get_index(hashes, len).unwrap_or(0),
+            // the sort keys are distinct, so a missing key can be compared
+            // with any entry
+            Disps::Sorted(sorted) => {
+                return sorted
+                    .binary_search(&phf_shared::sort_key(hashes))
+                    .unwrap_or(0)
+            }
+        };
+        index as usize
+    }
+}
+
+impl PackedDisps<'_> {
+    #[inline]
+    fn try_get_index(&self, hashes: &phf_shared::Hashes, len: usize) -> Option<u32> {
+        phf_shared::try_get_packed_index(hashes, self.buckets, self.offsets, self.bytes, len)
+    }
+}
+
+/// The storage backing the entries of a `Map`.
+///
+/// `Split` keeps keys and values in parallel arrays so that probing a slot
+/// only touches key data. `Aligned` pads each pair to a power of two, so
+/// that a probe reads a single cache line. `Chunked` gathers pairs from
+/// several arrays, which `phf_codegen` can write to separate files.
+#[doc(hidden)]
+pub enum Slots<'a, K, V> {
+    Pairs(&'a [(K, V)]),
+    Split(&'a [K], &'a [V]),
+    Aligned(AlignedSlots<'a, K, V>),
+    Chunked(ChunkedSlots<'a, K, V>),
+}
+
+/// A value padded to the alignment of `A`, one of the `Align*` markers.
+///
+/// A zero-length array takes the alignment of its element without taking
+/// any space, and the size of a type is a multiple of its alignment.
+#[doc(hidden)]
+#[repr(C)]
+pub struct Aligned<A, T> {
+    pub align: [A; 0],
+    pub value: T,
+}
+
+#[doc(hidden)]
+#[repr(align(16))]
+pub struct Align16;
+
+#[doc(hidden)]
+#[repr(align(32))]
+pub struct Align32;
+
+#[doc(hidden)]
+#[repr(align(64))]
+pub struct Align64;
+
+#[doc(hidden)]
+#[repr(align(128))]
+pub struct Align128;
+
+/// `(key, value)` pairs padded to 16, 32, 64 or 128 bytes.
+#[doc(hidden)]
+pub enum AlignedSlots<'a, K, V> {
+    A16(&'a [Aligned<Align16, (K, V)>]),
+    A32(&'a [Aligned<Align32, (K, V)>]),
+    A64(&'a [Aligned<Align64, (K, V)>]),
+    A128(&'a [Aligned<Align128, (K, V)>]),
+}
+
+// not derived, which would require `K: Copy` and `V: Copy`
+impl<'a, K, V> Clone for AlignedSlots<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for AlignedSlots<'a, K, V> {}
+
+impl<'a, K, V> AlignedSlots<'a, K, V> {
+    #[inline]
+    const fn len(&self) -> usize {
+        match self {
+            AlignedSlots::A16(entries) => entries.len(),
+            AlignedSlots::A32(entries) => entries.len(),
+            AlignedSlots::A64(entries) => entries.len(),
+            AlignedSlots::A128(entries) => entries.len(),
+        }
+    }
+
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    const fn entry(&self, index: usize) -> &'a (K, V) {
+        match self {
+            AlignedSlots::A16(entries) => &entries[index].value,
+            AlignedSlots::A32(entries) => &entries[index].value,
+            AlignedSlots::A64(entries) => &entries[index].value,
+            AlignedSlots::A128(entries) => &entries[index].value,
+        }
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Option<&'a (K, V)> {
+        if index < self.len() {
+            Some(self.entry(index))
+        } else {
+            None
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `self.len()`.
+    #[inline]
+    unsafe fn entry_unchecked(&self, index: usize) -> &'a (K, V) {
+        match self {
+            AlignedSlots::A16(entries) => &entries.get_unchecked(index).value,
+            AlignedSlots::A32(entries) => &entries.get_unchecked(index).value,
+            AlignedSlots::A64(entries) => &entries.get_unchecked(index).value,
+            AlignedSlots::A128(entries) => &entries.get_unchecked(index).value,
+        }
+    }
+}
+
+/// `(key, value)` pairs split into arrays of `chunk_len` pairs, of which
+/// only the last may be shorter.
+#[doc(hidden)]
+pub struct ChunkedSlots<'a, K, V> {
+    pub len: usize,
+    pub chunk_len: usize,
+    pub chunks: &'a [&'a [(K, V)]],
+}
+
+// not derived, which would require `K: Copy` and `V: Copy`
+impl<'a, K, V> Clone for ChunkedSlots<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for ChunkedSlots<'a, K, V> {}
+
+impl<'a, K, V> ChunkedSlots<'a, K, V> {
+    #[inline]
+    const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    const fn entry(&self, index: usize) -> &'a (K, V) {
+        &self.chunks[index / self.chunk_len][index % self.chunk_len]
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Option<&'a (K, V)> {
+        let chunk = self.chunks.get(index.checked_div(self.chunk_len)?)?;
+        chunk.get(index.checked_rem(self.chunk_len)?)
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `self.len()`.
+    #[inline]
+    unsafe fn entry_unchecked(&self, index: usize) -> &'a (K, V) {
+        self.chunks
+            .get_unchecked(index / self.chunk_len)
+            .get_unchecked(index % self.chunk_len)
+    }
+}
+
+impl<'a, K, V> Slots<'a, K, V> {
+    #[inline]
+    pub(crate) const fn len(&self) -> usize {
+        match self {
+            Slots::Pairs(entries) => entries.len(),
+            Slots::Split(keys, _) => keys.len(),
+            Slots::Aligned(entries) => entries.len(),
+            Slots::Chunked(entries) => entries.len(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn key(&self, index: usize) -> &'a K {
+        match self {
+            Slots::Pairs(entries) => &entries[index].0,
+            Slots::Split(keys, _) => &keys[index],
+            Slots::Aligned(entries) => &entries.entry(index).0,
+            Slots::Chunked(entries) => &entries.entry(index).0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_key(&self, index: usize) -> Option<&'a K> {
+        match self {
+            Slots::Pairs(entries) => entries.get(index).map(|e| &e.0),
+            Slots::Split(keys, _) => keys.get(index),
+            Slots::Aligned(entries) => entries.get(index).map(|e| &e.0),
+            Slots::Chunked(entries) => entries.get(index).map(|e| &e.0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_entry(&self, index: usize) -> Option<(&'a K, &'a V)> {
+        match self {
+            Slots::Pairs(entries) => entries.get(index).map(|e| (&e.0, &e.1)),
+            Slots::Split(keys, values) => Some((keys.get(index)?, values.get(index)?)),
+            Slots::Aligned(entries) => entries.get(index).map(|e| (&e.0, &e.1)),
+            Slots::Chunked(entries) => entries.get(index).map(|e| (&e.0, &e.1)),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn position(&self, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        match self {
+            Slots::Pairs(entries) => entries.iter().position(|e| eq(&e.0)),
+            Slots::Split(keys, _) => keys.iter().position(eq),
+            Slots::Aligned(entries) => (0..entries.len()).position(|i| eq(&entries.entry(i).0)),
+            Slots::Chunked(entries) => entries
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.iter())
+                .position(|e| eq(&e.0)),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `self.len()`.
+    #[inline]
+    pub(crate) unsafe fn value_unchecked(&self, index: usize) -> &V {
+        match self {
+            Slots::Pairs(entries) => &entries.get_unchecked(index).1,
+            Slots::Split(_, values) => values.get_unchecked(index),
+            Slots::Aligned(entries) => &entries.entry_unchecked(index).1,
+            Slots::Chunked(entries) => &entries.entry_unchecked(index).1,
+        }
+    }
 }
 
-impl<K, V> fmt::Debug for Map<K, V>
+/// Prints the entries in iteration order, see `Map::debug_sorted` for
+/// output sorted by key.
+///
+/// This is also the case for `{:#?}`: sorting only when `K: Ord` would take
+/// specialization, and a `K: Ord` bound would rule out printing maps of
+/// other keys, so the sorted output is a separate adapter.
+impl<K, V> fmt::Debug for Map<'_, K, V>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -32,19 +416,63 @@ where
     }
 }
 
-impl<'a, K, V, T: ?Sized> Index<&'a T> for Map<K, V>
+impl<K, V, T: ?Sized> Index<&T> for Map<'_, K, V>
 where
     T: Eq + PhfHash,
     K: PhfBorrow<T>,
 {
     type Output = V;
 
-    fn index(&self, k: &'a T) -> &V {
+    fn index(&self, k: &T) -> &V {
         self.get(k).expect("invalid key")
     }
 }
 
-impl<K, V> Map<K, V> {
+impl<'a, K, V> Map<'a, K, V> {
+    /// Creates a `Map` from a table generated by external tools.
+    ///
+    /// Unlike the fields of `Map`, which change between releases, the
+    /// arguments of this function are stable. For every entry `(k, v)` of
+    /// `entries`, `k` must be stored at the index returned by
+    /// [`phf_shared::get_index`] for `phf_shared::hash_with(k, &key, hasher)`,
+    /// `disps` and `entries.len()`. If `disps` is empty, the map is searched
+    /// linearly and the entries may be in any order.
+    ///
+    /// A map which breaks these rules behaves incorrectly, but never
+    /// unsafely: lookups may miss.
+    ///
+    /// The tables may be borrowed rather than `'static`:
+    ///
+    /// ```
+    /// use phf::{HashFn, Map};
+    ///
+    /// static MAP: Map<&'static str, u32> =
+    ///     Map::from_raw_parts(0, HashFn::Sip, &[], &[("hello", 1), ("world", 2)]);
+    ///
+    /// fn main() {
+    ///     assert_eq!(Some(&2), MAP.get("world"));
+    ///
+    ///     let (hello, world) = (String::from("hello"), String::from("world"));
+    ///     let entries = [(hello.as_str(), &hello), (world.as_str(), &world)];
+    ///     let map = Map::from_raw_parts(0, HashFn::Sip, &[], &entries);
+    ///     assert_eq!(Some(&&world), map.get("world"));
+    /// }
+    /// ```
+    pub const fn from_raw_parts(
+        key: HashKey,
+        hasher: HashFn,
+        disps: &'a [(u32, u32)],
+        entries: &'a [(K, V)],
+    ) -> Map<'a, K, V> {
+        Map {
+            key,
+            hasher,
+            disps: Disps::U32(disps),
+            entries: Slots::Pairs(entries),
+            side: None,
+        }
+    }
+
     /// Returns the number of entries in the `Map`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -57,6 +485,46 @@ impl<K, V> Map<K, V> {
         self.len() == 0
     }
 
+    /// Returns the number of slots in the `Map`.
+    ///
+    /// The table is minimal, so this is always equal to `len`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the number of displacement buckets the keys are hashed into.
+    ///
+    /// This is zero for small maps, which are searched linearly, and `len`
+    /// for maps sorted by hash, which are binary searched.
+    #[inline]
+    pub const fn buckets(&self) -> usize {
+        self.disps.len()
+    }
+
+    /// Returns the average number of entries per displacement bucket.
+    ///
+    /// The generator aims for about five, so lookups only need the
+    /// displacements of a small table. This is zero for small maps, which
+    /// are searched linearly.
+    pub fn load_factor(&self) -> f64 {
+        if self.disps.is_empty() {
+            0.
+        } else {
+            self.len() as f64 / self.buckets() as f64
+        }
+    }
+
+    /// Returns the largest displacement in the `Map`, or zero for small maps
+    /// which are searched linearly.
+    ///
+    /// Large displacements mean that the generator had to search long for
+    /// the table. Requires the `stats` feature.
+    #[cfg(feature = "stats")]
+    pub fn max_displacement(&self) -> u32 {
+        self.disps.max()
+    }
+
     /// Determines if `key` is in the `Map`.
     pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
     where
@@ -67,6 +535,9 @@ impl<K, V> Map<K, V> {
     }
 
     /// Returns a reference to the value that `key` maps to.
+    ///
+    /// Lookups have no panicking branches of their own, so this only panics
+    /// if the `PhfHash` or `PhfBorrow` impls of the key do.
     pub fn get<T: ?Sized>(&self, key: &T) -> Option<&V>
     where
         T: Eq + PhfHash,
@@ -75,16 +546,79 @@ impl<K, V> Map<K, V> {
         self.get_entry(key).map(|e| e.1)
     }
 
+    /// Like `get`, but copies the value out of the map.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// #[derive(Clone, Copy, Debug, PartialEq)]
+    /// enum Level {
+    ///     Low,
+    ///     High,
+    /// }
+    ///
+    /// static LEVELS: Map<&'static str, Level> = phf_map! {
+    ///     "low" => Level::Low,
+    ///     "high" => Level::High,
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!(Some(Level::High), LEVELS.get_copied("high"));
+    ///     assert_eq!(None, LEVELS.get_copied("medium"));
+    /// }
+    /// ```
+    pub fn get_copied<T: ?Sized>(&self, key: &T) -> Option<V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+        V: Copy,
+    {
+        self.get(key).copied()
+    }
+
+    /// Like `get`, but clones the value out of the map.
+    pub fn get_cloned<T: ?Sized>(&self, key: &T) -> Option<V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+        V: Clone,
+    {
+        self.get(key).cloned()
+    }
+
     /// Returns a reference to the map's internal static instance of the given
     /// key.
     ///
-    /// This can be useful for interning schemes.
-    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&K>
+    /// This can be useful for interning schemes: the key is borrowed for the
+    /// lifetime of the tables, `'static` for a generated map, rather than for
+    /// as long as the map, so a short-lived `&str` can be exchanged for the
+    /// `&'static str` stored in the map.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static COLORS: Map<&'static str, u32> = phf_map! {
+    ///     "red" => 0xff0000,
+    ///     "green" => 0x00ff00,
+    /// };
+    ///
+    /// fn intern(s: &str) -> Option<&'static str> {
+    ///     COLORS.get_key(s).copied()
+    /// }
+    ///
+    /// fn main() {
+    ///     let input = String::from("red");
+    ///     assert_eq!(Some("red"), intern(&input));
+    ///     assert_eq!(None, intern("blue"));
+    /// }
+    /// ```
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
     where
         T: Eq + PhfHash,
         K: PhfBorrow<T>,
     {
-        self.get_entry(key).map(|e| e.0)
+        self.find_index(key, |k| k.borrow() == key)
+            .and_then(|i| self.entries.get_key(i))
     }
 
     /// Like `get`, but returns both the key and the value.
@@ -93,32 +627,183 @@ impl<K, V> Map<K, V> {
         T: Eq + PhfHash,
         K: PhfBorrow<T>,
     {
+        self.find(key, |k| k.borrow() == key)
+    }
+
+    /// Like `get_entry`, but also returns the index of the entry in the order
+    /// of `entries`.
+    ///
+    /// This is the same as `get_full` of `indexmap`, although the index isn't
+    /// the position of the key in the map's definition.
+    pub fn get_full<T: ?Sized>(&self, key: &T) -> Option<(usize, &K, &V)>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        let i = self.find_index(key, |k| k.borrow() == key)?;
+        let (k, v) = self.entries.get_entry(i)?;
+        Some((i, k, v))
+    }
+
+    /// Returns the entry whose key `eq` accepts, given something which hashes
+    /// the same as that key.
+    pub(crate) fn find<T: ?Sized + PhfHash>(
+        &self,
+        hashed: &T,
+        eq: impl Fn(&K) -> bool,
+    ) -> Option<(&'a K, &'a V)> {
+        self.find_index(hashed, eq)
+            .and_then(|i| self.entries.get_entry(i))
+    }
+
+    /// Like `find`, but returns the index of the entry.
+    fn find_index<T: ?Sized + PhfHash>(
+        &self,
+        hashed: &T,
+        eq: impl Fn(&K) -> bool,
+    ) -> Option<usize> {
         if self.disps.is_empty() {
+            // Small maps don't store displacements and are searched linearly,
+            // which is cheaper than hashing the key.
+            return self.entries.position(eq);
+        }
+        // Nothing below indexes or divides without checking, so that lookups
+        // can't panic even if the fields are inconsistent.
+        let hashes = phf_shared::hash_with(hashed, &self.key, self.hasher);
+        let side = self.side.unwrap_or(&SideTables::EMPTY);
+        // The filter is much smaller than the map, so checking it first
+        // rejects most misses without touching the displacements or entries.
+        if !phf_shared::filter_contains(side.filter, &hashes) {
+            return None;
+        }
+        let index = self.disps.try_get_index(&hashes, self.entries.len())?;
+        // Comparing fingerprints first is cheap and rules out most misses
+        // without having to look at the stored key.
+        if !side.fingerprints.is_empty()
+            && side.fingerprints.get(index) != Some(&phf_shared::fingerprint(&hashes))
+        {
             return None;
-        } //Prevent panic on empty map
-        let hashes = phf_shared::hash(key, &self.key);
-        let index = phf_shared::get_index(&hashes, &*self.disps, self.entries.len());
-        let entry = &self.entries[index as usize];
-        let b: &T = entry.0.borrow();
-        if b == key {
-            Some((&entry.0, &entry.1))
+        }
+        if eq(self.entries.get_key(index)?) {
+            Some(index)
         } else {
             None
         }
     }
 
+    /// Checks that looking up each stored key finds that very entry.
+    ///
+    /// A map only works if its keys hash the same way when it's generated and
+    /// when it's used. Otherwise, e.g. with a custom `PhfHash` impl whose
+    /// output depends on the platform, lookups silently miss. This returns
+    /// the first key whose lookup fails, and is meant to be called in a test.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static MAP: Map<&'static str, u32> = phf_map! {
+    ///     "a" => 1,
+    ///     "b" => 2,
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!(Ok(()), MAP.verify());
+    /// }
+    /// ```
+    pub fn verify(&self) -> Result<(), &K>
+    where
+        K: PhfHash,
+    {
+        for i in 0..self.entries.len() {
+            let key = self.entries.key(i);
+            if self.find_index(key, |k| ptr::eq(k, key)) != Some(i) {
+                return Err(key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the value that `key` maps to, without checking
+    /// that `key` is actually in the map.
+    ///
+    /// This skips the final key comparison (and the bounds check) done by
+    /// `get`, which is wasted work when every key looked up is known to be
+    /// in the map, e.g. because it came from the map's own `keys`.
+    ///
+    /// # Safety
+    ///
+    /// `key` must be in the map. Passing any other key is undefined behavior.
+    pub unsafe fn get_unchecked<T: ?Sized>(&self, key: &T) -> &V
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        let len = self.entries.len();
+        let index = if self.disps.is_empty() {
+            // if the key isn't any of the others it must be the last one
+            (0..len.saturating_sub(1))
+                .find(|&i| self.entries.key(i).borrow() == key)
+                .unwrap_or(len.wrapping_sub(1))
+        } else {
+            let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+            self.disps.get_index(&hashes, len)
+        };
+        self.entries.value_unchecked(index)
+    }
+
+    /// Returns a key which maps to `value`.
+    ///
+    /// This is a linear scan of the entries. If several keys map to `value`,
+    /// the first in the order of `entries` is returned.
+    pub fn find_key_by_value(&self, value: &V) -> Option<&K>
+    where
+        V: PartialEq,
+    {
+        self.entries().find(|e| e.1 == value).map(|e| e.0)
+    }
+
+    /// Returns a `HashMap` from the values of the map to their keys.
+    ///
+    /// If several keys map to the same value, the first in the order of
+    /// `entries` is kept, as with `find_key_by_value`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn invert(&self) -> HashMap<&V, &K>
+    where
+        V: Eq + Hash,
+    {
+        let mut inverted = HashMap::with_capacity(self.len());
+        for (k, v) in self.entries() {
+            inverted.entry(v).or_insert(k);
+        }
+        inverted
+    }
+
     /// Returns an iterator over the key/value pairs in the map.
     ///
-    /// Entries are returned in an arbitrary but fixed order.
+    /// Entries are returned in an arbitrary but fixed order, or in definition
+    /// order if the map was generated with the `@definition_order;` flag or
+    /// its `phf_codegen` equivalent.
     pub fn entries(&self) -> Entries<'_, K, V> {
-        Entries {
-            iter: self.entries.iter(),
-        }
+        let order = self.side.map_or(&[][..], |side| side.order);
+        let direct = order.is_empty();
+        let iter = match &self.entries {
+            Slots::Pairs(entries) if direct => SlotsIter::Pairs(entries.iter()),
+            Slots::Split(keys, values) if direct => SlotsIter::Split(keys.iter(), values.iter()),
+            Slots::Aligned(entries) if direct => SlotsIter::Aligned(*entries, 0..entries.len()),
+            Slots::Chunked(entries) if direct => SlotsIter::Chunked(*entries, 0..entries.len()),
+            Slots::Pairs(entries) => SlotsIter::IndirectPairs(entries, order.iter()),
+            Slots::Split(keys, values) => SlotsIter::IndirectSplit(keys, values, order.iter()),
+            Slots::Aligned(entries) => SlotsIter::IndirectAligned(*entries, order.iter()),
+            Slots::Chunked(entries) => SlotsIter::IndirectChunked(*entries, order.iter()),
+        };
+        Entries { iter }
     }
 
     /// Returns an iterator over the keys in the map.
     ///
-    /// Keys are returned in an arbitrary but fixed order.
+    /// Keys are returned in the same order as by `entries`.
     pub fn keys(&self) -> Keys<'_, K, V> {
         Keys {
             iter: self.entries(),
@@ -127,15 +812,242 @@ impl<K, V> Map<K, V> {
 
     /// Returns an iterator over the values in the map.
     ///
-    /// Values are returned in an arbitrary but fixed order.
+    /// Values are returned in the same order as by `entries`.
     pub fn values(&self) -> Values<'_, K, V> {
         Values {
             iter: self.entries(),
         }
     }
+
+    /// Returns an adapter which displays the keys as a list, for error
+    /// messages such as "expected one of `a`, `b`".
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static LEVELS: Map<&'static str, u8> = phf_map! {
+    ///     "low" => 0,
+    ///     "medium" => 1,
+    ///     "high" => 2,
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!(
+    ///         "expected one of `high`, `low`, and 1 more",
+    ///         format!("expected one of {}", LEVELS.keys_display().sorted().truncate(2))
+    ///     );
+    /// }
+    /// ```
+    pub fn keys_display(&self) -> KeysDisplay<'_, K, V> {
+        KeysDisplay {
+            map: self,
+            cmp: None,
+            limit: None,
+        }
+    }
+
+    /// Returns a view of the keys of the map as a set.
+    ///
+    /// The view uses the map's own table, so code which only needs membership
+    /// tests can share a map rather than generating a second table.
+    #[inline]
+    pub const fn as_set(&self) -> SetView<'_, K, V> {
+        SetView { map: self }
+    }
+}
+
+impl<'a, K, V> Map<'a, K, V>
+where
+    K: Eq + PhfHash,
+{
+    /// Returns an iterator over the keys in both `self` and `other`, along
+    /// with their values in each map.
+    ///
+    /// The smaller map is iterated and the larger one probed, so entries are
+    /// returned in the order of the smaller map.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static OLD: Map<&'static str, u32> = phf_map! {
+    ///     "a" => 1,
+    ///     "b" => 2,
+    /// };
+    /// static NEW: Map<&'static str, u32> = phf_map! {
+    ///     "b" => 3,
+    ///     "c" => 4,
+    /// };
+    ///
+    /// fn main() {
+    ///     let changed = OLD.intersect_keys(&NEW).collect::<Vec<_>>();
+    ///     assert_eq!(vec![(&"b", &2, &3)], changed);
+    ///     let removed = OLD.difference_keys(&NEW).collect::<Vec<_>>();
+    ///     assert_eq!(vec![(&"a", &1)], removed);
+    /// }
+    /// ```
+    pub fn intersect_keys<'b, V2>(
+        &'b self,
+        other: &'b Map<'_, K, V2>,
+    ) -> IntersectKeys<'b, K, V, V2> {
+        let iter = if self.len() <= other.len() {
+            IntersectIter::Left(self.entries())
+        } else {
+            IntersectIter::Right(other.entries())
+        };
+        IntersectKeys {
+            iter,
+            left: self,
+            right: other,
+        }
+    }
+
+    /// Returns an iterator over the entries of `self` whose keys aren't in
+    /// `other`.
+    pub fn difference_keys<'b, V2>(
+        &'b self,
+        other: &'b Map<'_, K, V2>,
+    ) -> DifferenceKeys<'b, K, V, V2> {
+        DifferenceKeys {
+            iter: self.entries(),
+            other,
+        }
+    }
 }
 
-impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+impl<K: Ord, V> Map<'_, K, V> {
+    /// Returns an iterator over the entries in ascending order of key.
+    ///
+    /// Unlike `entries`, the order doesn't depend on the generated table, so
+    /// it is stable across builds. The entries are sorted into a `Vec` up
+    /// front. Requires the `alloc` feature.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static SIZES: Map<&'static str, u32> = phf_map! {
+    ///     "small" => 1,
+    ///     "medium" => 2,
+    ///     "large" => 3,
+    /// };
+    ///
+    /// fn main() {
+    ///     let keys = SIZES.iter_sorted().map(|e| *e.0).collect::<Vec<_>>();
+    ///     assert_eq!(vec!["large", "medium", "small"], keys);
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn iter_sorted(&self) -> SortedIter<'_, K, V> {
+        let mut entries = self.entries().collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        SortedIter {
+            iter: entries.into_iter(),
+        }
+    }
+
+    /// Returns an adapter which prints the entries in ascending order of key,
+    /// with either `{:?}` or `{:#?}`.
+    ///
+    /// Unless the map keeps definition order, the iteration order printed by
+    /// the map itself depends on the hash key, so sorting makes dumps of the
+    /// same entries comparable across builds. This doesn't allocate, so it
+    /// takes time quadratic in the number of entries.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static MAP: Map<u32, char> = phf_map! {
+    ///     3u32 => 'c',
+    ///     1u32 => 'a',
+    ///     2u32 => 'b',
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!("{1: 'a', 2: 'b', 3: 'c'}", format!("{:?}", MAP.debug_sorted()));
+    /// }
+    /// ```
+    pub fn debug_sorted(&self) -> DebugSorted<'_, K, V> {
+        DebugSorted {
+            entries: self.entries(),
+        }
+    }
+}
+
+impl<V> Map<'_, &str, V> {
+    /// Determines if `key` is in the `Map` in a constant context, for
+    /// `phf_assert_key!`.
+    #[doc(hidden)]
+    pub const fn __contains_str_const(&self, key: &str) -> bool {
+        let mut i = 0;
+        while i < self.entries.len() {
+            let k = match &self.entries {
+                Slots::Pairs(entries) => entries[i].0,
+                Slots::Split(keys, _) => keys[i],
+                Slots::Aligned(entries) => entries.entry(i).0,
+                Slots::Chunked(entries) => entries.entry(i).0,
+            };
+            if str_eq_const(k, key) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl<'k, V> Map<'_, &'k str, V> {
+    /// Returns a reference to the value that the NFC normal form of `key`
+    /// maps to.
+    ///
+    /// Requires the `unicode-normalization` feature. This is meant for maps
+    /// generated with `@normalize = nfc;`, whose keys are normalized. `key` is
+    /// only copied if it isn't already in normal form.
+    ///
+    /// ```
+    /// use phf::{phf_map, Map};
+    ///
+    /// static MAP: Map<&'static str, u32> = phf_map! {
+    ///     @normalize = nfc;
+    ///     "cafe\u{301}" => 1,
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!(Some(&1), MAP.get_nfc("cafe\u{301}"));
+    ///     assert_eq!(Some(&1), MAP.get_nfc("caf\u{e9}"));
+    ///     assert_eq!(None, MAP.get("cafe\u{301}"));
+    /// }
+    /// ```
+    pub fn get_nfc(&self, key: &str) -> Option<&V> {
+        self.get_entry_nfc(key).map(|e| e.1)
+    }
+
+    pub(crate) fn get_entry_nfc(&self, key: &str) -> Option<(&&'k str, &V)> {
+        use alloc::string::String;
+        use unicode_normalization_::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+        match is_nfc_quick(key.chars()) {
+            IsNormalized::Yes => self.get_entry(key),
+            _ => self.get_entry(&*key.nfc().collect::<String>()),
+        }
+    }
+}
+
+const fn str_eq_const(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+impl<'a, K, V> IntoIterator for &'a Map<'_, K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Entries<'a, K, V>;
 
@@ -144,17 +1056,142 @@ impl<'a, K, V> IntoIterator for &'a Map<K, V> {
     }
 }
 
+/// A view of the keys of a `Map` as a set, returned by `Map::as_set`.
+pub struct SetView<'a, K, V> {
+    map: &'a Map<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for SetView<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for SetView<'a, K, V> {}
+
+impl<'a, K, V> fmt::Debug for SetView<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> SetView<'a, K, V> {
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the set contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if `value` is in the set.
+    pub fn contains<T: ?Sized>(&self, value: &T) -> bool
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.map.get_key(key)
+    }
+
+    /// Returns an iterator over the values in the set.
+    ///
+    /// Values are returned in the same order as the keys of the map.
+    pub fn iter(&self) -> Keys<'a, K, V> {
+        self.map.keys()
+    }
+}
+
+impl<'a, K, V> IntoIterator for SetView<'a, K, V> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, V>;
+
+    fn into_iter(self) -> Keys<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &SetView<'a, K, V> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, V>;
+
+    fn into_iter(self) -> Keys<'a, K, V> {
+        self.iter()
+    }
+}
+
 /// An iterator over the key/value pairs in a `Map`.
 pub struct Entries<'a, K, V> {
-    iter: slice::Iter<'a, (K, V)>,
+    iter: SlotsIter<'a, K, V>,
+}
+
+enum SlotsIter<'a, K, V> {
+    Pairs(slice::Iter<'a, (K, V)>),
+    Split(slice::Iter<'a, K>, slice::Iter<'a, V>),
+    IndirectPairs(&'a [(K, V)], slice::Iter<'a, usize>),
+    IndirectSplit(&'a [K], &'a [V], slice::Iter<'a, usize>),
+    Aligned(AlignedSlots<'a, K, V>, Range<usize>),
+    IndirectAligned(AlignedSlots<'a, K, V>, slice::Iter<'a, usize>),
+    Chunked(ChunkedSlots<'a, K, V>, Range<usize>),
+    IndirectChunked(ChunkedSlots<'a, K, V>, slice::Iter<'a, usize>),
+}
+
+impl<'a, K, V> SlotsIter<'a, K, V> {
+    /// Returns the entry at `slot` of an indirect iterator.
+    fn slot(&self, slot: usize) -> Option<(&'a K, &'a V)> {
+        match *self {
+            SlotsIter::IndirectPairs(entries, _) => entries.get(slot).map(|e| (&e.0, &e.1)),
+            SlotsIter::IndirectSplit(keys, values, _) => Some((keys.get(slot)?, values.get(slot)?)),
+            SlotsIter::Aligned(entries, _) | SlotsIter::IndirectAligned(entries, _) => {
+                entries.get(slot).map(|e| (&e.0, &e.1))
+            }
+            SlotsIter::Chunked(entries, _) | SlotsIter::IndirectChunked(entries, _) => {
+                entries.get(slot).map(|e| (&e.0, &e.1))
+            }
+            SlotsIter::Pairs(_) | SlotsIter::Split(..) => None,
+        }
+    }
 }
 
 impl<'a, K, V> Clone for Entries<'a, K, V> {
     #[inline]
     fn clone(&self) -> Self {
-        Self {
-            iter: self.iter.clone(),
-        }
+        let iter = match &self.iter {
+            SlotsIter::Pairs(iter) => SlotsIter::Pairs(iter.clone()),
+            SlotsIter::Split(keys, values) => SlotsIter::Split(keys.clone(), values.clone()),
+            SlotsIter::IndirectPairs(entries, order) => {
+                SlotsIter::IndirectPairs(entries, order.clone())
+            }
+            SlotsIter::IndirectSplit(keys, values, order) => {
+                SlotsIter::IndirectSplit(keys, values, order.clone())
+            }
+            SlotsIter::Aligned(entries, range) => SlotsIter::Aligned(*entries, range.clone()),
+            SlotsIter::IndirectAligned(entries, order) => {
+                SlotsIter::IndirectAligned(*entries, order.clone())
+            }
+            SlotsIter::Chunked(entries, range) => SlotsIter::Chunked(*entries, range.clone()),
+            SlotsIter::IndirectChunked(entries, order) => {
+                SlotsIter::IndirectChunked(*entries, order.clone())
+            }
+        };
+        Self { iter }
     }
 }
 
@@ -172,17 +1209,53 @@ impl<'a, K, V> Iterator for Entries<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next().map(|&(ref k, ref v)| (k, v))
+        match &mut self.iter {
+            SlotsIter::Pairs(iter) => iter.next().map(|(k, v)| (k, v)),
+            SlotsIter::Split(keys, values) => Some((keys.next()?, values.next()?)),
+            SlotsIter::Aligned(_, range) | SlotsIter::Chunked(_, range) => {
+                let slot = range.next()?;
+                self.iter.slot(slot)
+            }
+            SlotsIter::IndirectPairs(_, order)
+            | SlotsIter::IndirectSplit(_, _, order)
+            | SlotsIter::IndirectAligned(_, order)
+            | SlotsIter::IndirectChunked(_, order) => {
+                let slot = *order.next()?;
+                self.iter.slot(slot)
+            }
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        match &self.iter {
+            SlotsIter::Pairs(iter) => iter.size_hint(),
+            SlotsIter::Split(keys, _) => keys.size_hint(),
+            SlotsIter::Aligned(_, range) | SlotsIter::Chunked(_, range) => range.size_hint(),
+            SlotsIter::IndirectPairs(_, order)
+            | SlotsIter::IndirectSplit(_, _, order)
+            | SlotsIter::IndirectAligned(_, order)
+            | SlotsIter::IndirectChunked(_, order) => order.size_hint(),
+        }
     }
 }
 
 impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
     fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next_back().map(|e| (&e.0, &e.1))
+        match &mut self.iter {
+            SlotsIter::Pairs(iter) => iter.next_back().map(|e| (&e.0, &e.1)),
+            SlotsIter::Split(keys, values) => Some((keys.next_back()?, values.next_back()?)),
+            SlotsIter::Aligned(_, range) | SlotsIter::Chunked(_, range) => {
+                let slot = range.next_back()?;
+                self.iter.slot(slot)
+            }
+            SlotsIter::IndirectPairs(_, order)
+            | SlotsIter::IndirectSplit(_, _, order)
+            | SlotsIter::IndirectAligned(_, order)
+            | SlotsIter::IndirectChunked(_, order) => {
+                let slot = *order.next_back()?;
+                self.iter.slot(slot)
+            }
+        }
     }
 }
 
@@ -190,6 +1263,207 @@ impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
 
 impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
 
+/// Prints the entries of a `Map` in ascending order of key, see
+/// `Map::debug_sorted`.
+pub struct DebugSorted<'a, K, V> {
+    entries: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> fmt::Debug for DebugSorted<'a, K, V>
+where
+    K: fmt::Debug + Ord,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map()
+            .entries(SortedEntries::new(self.entries.clone()))
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a `Map` in ascending order of key.
+///
+/// This doesn't allocate, so it takes time quadratic in the number of
+/// entries. It's only meant for debug output.
+pub(crate) struct SortedEntries<'a, K, V> {
+    entries: Entries<'a, K, V>,
+    last: Option<&'a K>,
+}
+
+impl<'a, K, V> SortedEntries<'a, K, V> {
+    pub(crate) fn new(entries: Entries<'a, K, V>) -> Self {
+        SortedEntries {
+            entries,
+            last: None,
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for SortedEntries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let last = self.last;
+        let next = self
+            .entries
+            .clone()
+            .filter(|e| match last {
+                Some(last) => e.0 > last,
+                None => true,
+            })
+            .min_by(|a, b| a.0.cmp(b.0))?;
+        self.last = Some(next.0);
+        Some(next)
+    }
+}
+
+/// An iterator over the entries of a `Map` in ascending order of key, see
+/// `Map::iter_sorted`.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub struct SortedIter<'a, K, V> {
+    iter: vec::IntoIter<(&'a K, &'a V)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> Clone for SortedIter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> fmt::Debug for SortedIter<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> Iterator for SortedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> DoubleEndedIterator for SortedIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> ExactSizeIterator for SortedIter<'a, K, V> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V> FusedIterator for SortedIter<'a, K, V> {}
+
+/// An adapter for displaying the keys of a `Map` as a list, returned by
+/// `Map::keys_display`.
+///
+/// Each key is printed with its `Display` impl between backticks, and the
+/// keys are separated by commas. They are in iteration order unless `sorted`
+/// is called.
+pub struct KeysDisplay<'a, K, V> {
+    map: &'a Map<'a, K, V>,
+    cmp: Option<fn(&K, &K) -> Ordering>,
+    limit: Option<usize>,
+}
+
+impl<'a, K, V> Clone for KeysDisplay<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for KeysDisplay<'a, K, V> {}
+
+impl<'a, K, V> KeysDisplay<'a, K, V> {
+    /// Displays the keys in ascending order.
+    ///
+    /// Like `Map::debug_sorted`, this doesn't allocate, so it takes
+    /// time quadratic in the number of keys displayed.
+    pub fn sorted(mut self) -> Self
+    where
+        K: Ord,
+    {
+        self.cmp = Some(K::cmp);
+        self
+    }
+
+    /// Displays at most `limit` keys, followed by the number of keys left
+    /// out, as in "`a`, `b`, and 3 more".
+    pub fn truncate(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a, K: fmt::Display, V> fmt::Display for KeysDisplay<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.map.len();
+        let shown = match self.limit {
+            Some(limit) => limit.min(len),
+            None => len,
+        };
+        match self.cmp {
+            None => {
+                for (i, key) in self.map.keys().take(shown).enumerate() {
+                    write_list_item(f, i, key)?;
+                }
+            }
+            Some(cmp) => {
+                let mut last: Option<&K> = None;
+                for i in 0..shown {
+                    let key = self
+                        .map
+                        .keys()
+                        .filter(|k| match last {
+                            Some(last) => cmp(k, last) == Ordering::Greater,
+                            None => true,
+                        })
+                        .min_by(|a, b| cmp(a, b));
+                    if let Some(key) = key {
+                        write_list_item(f, i, key)?;
+                    }
+                    last = key;
+                }
+            }
+        }
+        if shown < len {
+            if shown > 0 {
+                write!(f, ", and ")?;
+            }
+            write!(f, "{} more", len - shown)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the `i`th item of a list displayed by `KeysDisplay`.
+fn write_list_item<K: fmt::Display>(f: &mut fmt::Formatter<'_>, i: usize, key: &K) -> fmt::Result {
+    if i > 0 {
+        write!(f, ", ")?;
+    }
+    write!(f, "`{}`", key)
+}
+
 /// An iterator over the keys in a `Map`.
 pub struct Keys<'a, K, V> {
     iter: Entries<'a, K, V>,
@@ -279,3 +1553,118 @@ impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
 impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
 
 impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+/// An iterator over the keys in two `Map`s along with their values in each,
+/// returned by `Map::intersect_keys`.
+pub struct IntersectKeys<'a, K, V, V2> {
+    iter: IntersectIter<'a, K, V, V2>,
+    left: &'a Map<'a, K, V>,
+    right: &'a Map<'a, K, V2>,
+}
+
+enum IntersectIter<'a, K, V, V2> {
+    Left(Entries<'a, K, V>),
+    Right(Entries<'a, K, V2>),
+}
+
+impl<'a, K, V, V2> Clone for IntersectKeys<'a, K, V, V2> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let iter = match &self.iter {
+            IntersectIter::Left(iter) => IntersectIter::Left(iter.clone()),
+            IntersectIter::Right(iter) => IntersectIter::Right(iter.clone()),
+        };
+        Self {
+            iter,
+            left: self.left,
+            right: self.right,
+        }
+    }
+}
+
+impl<'a, K, V, V2> fmt::Debug for IntersectKeys<'a, K, V, V2>
+where
+    K: fmt::Debug + Eq + PhfHash + PhfBorrow<K>,
+    V: fmt::Debug,
+    V2: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, V2> Iterator for IntersectKeys<'a, K, V, V2>
+where
+    K: Eq + PhfHash,
+{
+    type Item = (&'a K, &'a V, &'a V2);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V, &'a V2)> {
+        match &mut self.iter {
+            IntersectIter::Left(iter) => {
+                let right = self.right;
+                iter.find_map(|(k, v)| right.find(k, |x| x == k).map(|(_, v2)| (k, v, v2)))
+            }
+            IntersectIter::Right(iter) => {
+                let left = self.left;
+                iter.find_map(|(k, v2)| left.find(k, |x| x == k).map(|(k, v)| (k, v, v2)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.iter {
+            IntersectIter::Left(iter) => iter.len(),
+            IntersectIter::Right(iter) => iter.len(),
+        };
+        (0, Some(len))
+    }
+}
+
+impl<'a, K, V, V2> FusedIterator for IntersectKeys<'a, K, V, V2> where K: Eq + PhfHash {}
+
+/// An iterator over the entries of a `Map` whose keys aren't in another,
+/// returned by `Map::difference_keys`.
+pub struct DifferenceKeys<'a, K, V, V2> {
+    iter: Entries<'a, K, V>,
+    other: &'a Map<'a, K, V2>,
+}
+
+impl<'a, K, V, V2> Clone for DifferenceKeys<'a, K, V, V2> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<'a, K, V, V2> fmt::Debug for DifferenceKeys<'a, K, V, V2>
+where
+    K: fmt::Debug + Eq + PhfHash + PhfBorrow<K>,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, V2> Iterator for DifferenceKeys<'a, K, V, V2>
+where
+    K: Eq + PhfHash,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let other = self.other;
+        self.iter
+            .find(|(k, _)| other.find(*k, |x| x == *k).is_none())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len()))
+    }
+}
+
+impl<'a, K, V, V2> FusedIterator for DifferenceKeys<'a, K, V, V2> where K: Eq + PhfHash {}
@@ -1,10 +1,17 @@
 //! An order-preserving immutable map constructed at compile time.
+use core::cmp::Ordering;
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
-use core::ops::Index;
+use core::ops::{Bound, Index, Range, RangeBounds};
 use core::slice;
-use phf_shared::{self, HashKey, PhfBorrow, PhfHash};
+use phf_shared::{self, HashFn, HashKey, PhfBorrow, PhfHash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use crate::map::Disps;
 
 /// An order-preserving immutable map constructed at compile time.
 ///
@@ -16,18 +23,24 @@ use phf_shared::{self, HashKey, PhfBorrow, PhfHash};
 /// The fields of this struct are public so that they may be initialized by the
 /// `phf_ordered_map!` macro and code generation. They are subject to change at
 /// any time and should never be accessed directly.
-pub struct OrderedMap<K: 'static, V: 'static> {
+pub struct OrderedMap<'a, K, V> {
     #[doc(hidden)]
     pub key: HashKey,
     #[doc(hidden)]
-    pub disps: &'static [(u32, u32)],
+    pub hasher: HashFn,
+    #[doc(hidden)]
+    pub disps: Disps<'a>,
+    #[doc(hidden)]
+    pub idxs: &'a [usize],
     #[doc(hidden)]
-    pub idxs: &'static [usize],
+    pub entries: &'a [(K, V)],
+    /// Empty if `entries` is in definition order. Otherwise `entries` is in
+    /// slot order, and this maps definition order to slots.
     #[doc(hidden)]
-    pub entries: &'static [(K, V)],
+    pub order: &'a [usize],
 }
 
-impl<K, V> fmt::Debug for OrderedMap<K, V>
+impl<K, V> fmt::Debug for OrderedMap<'_, K, V>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -37,19 +50,19 @@ where
     }
 }
 
-impl<'a, K, V, T: ?Sized> Index<&'a T> for OrderedMap<K, V>
+impl<K, V, T: ?Sized> Index<&T> for OrderedMap<'_, K, V>
 where
     T: Eq + PhfHash,
     K: PhfBorrow<T>,
 {
     type Output = V;
 
-    fn index(&self, k: &'a T) -> &V {
+    fn index(&self, k: &T) -> &V {
         self.get(k).expect("invalid key")
     }
 }
 
-impl<K, V> OrderedMap<K, V> {
+impl<'a, K, V> OrderedMap<'a, K, V> {
     /// Returns the number of entries in the `OrderedMap`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -71,16 +84,36 @@ impl<K, V> OrderedMap<K, V> {
         self.get_entry(key).map(|e| e.1)
     }
 
+    /// Like `get`, but copies the value out of the map.
+    pub fn get_copied<T: ?Sized>(&self, key: &T) -> Option<V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+        V: Copy,
+    {
+        self.get(key).copied()
+    }
+
+    /// Like `get`, but clones the value out of the map.
+    pub fn get_cloned<T: ?Sized>(&self, key: &T) -> Option<V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+        V: Clone,
+    {
+        self.get(key).cloned()
+    }
+
     /// Returns a reference to the map's internal static instance of the given
     /// key.
     ///
-    /// This can be useful for interning schemes.
-    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&K>
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
     where
         T: Eq + PhfHash,
         K: PhfBorrow<T>,
     {
-        self.get_entry(key).map(|e| e.0)
+        self.get_internal(key).map(|(_, e)| e.0)
     }
 
     /// Determines if `key` is in the `OrderedMap`.
@@ -102,10 +135,83 @@ impl<K, V> OrderedMap<K, V> {
         self.get_internal(key).map(|(i, _)| i)
     }
 
+    /// Returns the index of the key within the list used to initialize
+    /// the ordered map.
+    ///
+    /// This is the same as `get_index`, under the name used by `indexmap`.
+    pub fn get_index_of<T: ?Sized>(&self, key: &T) -> Option<usize>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get_index(key)
+    }
+
+    /// Returns the index `key` would have within the list used to initialize
+    /// the ordered map, without checking that `key` is actually in the map.
+    ///
+    /// If `key` is in the map this is the same as `.get_index(key).unwrap()`,
+    /// but cheaper. Otherwise the returned index is unspecified, and may be
+    /// out of bounds if the map is empty.
+    pub fn get_index_unchecked<T: ?Sized>(&self, key: &T) -> usize
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.position_unchecked(key).0
+    }
+
+    /// Returns a reference to the value that `key` maps to, without checking
+    /// that `key` is actually in the map.
+    ///
+    /// See `OrderedMap::get_index_unchecked`.
+    ///
+    /// # Safety
+    ///
+    /// `key` must be in the map. Passing any other key is undefined behavior.
+    pub unsafe fn get_unchecked<T: ?Sized>(&self, key: &T) -> &V
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        &self.entries.get_unchecked(self.position_unchecked(key).1).1
+    }
+
+    /// Returns the index of `key` in definition order and in `entries`,
+    /// assuming that it is in the map.
+    fn position_unchecked<T: ?Sized>(&self, key: &T) -> (usize, usize)
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        if self.disps.is_empty() {
+            // if the key isn't any of the others it must be the last one
+            let last = self.entries.len().saturating_sub(1);
+            let idx = self.entries[..last]
+                .iter()
+                .position(|entry| entry.0.borrow() == key)
+                .unwrap_or(last);
+            return (idx, idx);
+        }
+        let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+        let idx_index = self.disps.get_index(&hashes, self.idxs.len());
+        let idx = self.idxs[idx_index];
+        if self.order.is_empty() {
+            (idx, idx)
+        } else {
+            (idx, idx_index)
+        }
+    }
+
     /// Returns references to both the key and values at an index
     /// within the list used to initialize the ordered map. See `.get_index(key)`.
     pub fn index(&self, index: usize) -> Option<(&K, &V)> {
-        self.entries.get(index).map(|&(ref k, ref v)| (k, v))
+        let entry = if self.order.is_empty() {
+            self.entries.get(index)
+        } else {
+            self.order.get(index).map(|&slot| &self.entries[slot])
+        };
+        entry.map(|(k, v)| (k, v))
     }
 
     /// Like `get`, but returns both the key and the value.
@@ -117,18 +223,67 @@ impl<K, V> OrderedMap<K, V> {
         self.get_internal(key).map(|(_, e)| e)
     }
 
-    fn get_internal<T: ?Sized>(&self, key: &T) -> Option<(usize, (&K, &V))>
+    /// Like `get_entry`, but also returns the index of the key within the
+    /// list used to initialize the ordered map, in a single probe.
+    ///
+    /// This is the same as `get_full` of `indexmap`.
+    pub fn get_full<T: ?Sized>(&self, key: &T) -> Option<(usize, &K, &V)>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get_internal(key).map(|(i, (k, v))| (i, k, v))
+    }
+
+    /// Checks that looking up each stored key finds that very entry.
+    ///
+    /// See `Map::verify`.
+    pub fn verify(&self) -> Result<(), &K>
+    where
+        K: PhfHash,
+    {
+        if self.disps.is_empty() {
+            return Ok(());
+        }
+        for (slot, &idx) in self.idxs.iter().enumerate() {
+            let key = if self.order.is_empty() {
+                &self.entries[idx].0
+            } else {
+                &self.entries[slot].0
+            };
+            let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+            if self.disps.get_index(&hashes, self.idxs.len()) != slot {
+                return Err(key);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_internal<T: ?Sized>(&self, key: &T) -> Option<(usize, (&'a K, &'a V))>
     where
         T: Eq + PhfHash,
         K: PhfBorrow<T>,
     {
+        let entries = self.entries;
         if self.disps.is_empty() {
-            return None;
-        } //Prevent panic on empty map
-        let hashes = phf_shared::hash(key, &self.key);
-        let idx_index = phf_shared::get_index(&hashes, &*self.disps, self.idxs.len());
-        let idx = self.idxs[idx_index as usize];
-        let entry = &self.entries[idx];
+            // Small maps don't store displacements and are searched linearly,
+            // which is cheaper than hashing the key.
+            return entries
+                .iter()
+                .enumerate()
+                .find(|(_, entry)| entry.0.borrow() == key)
+                .map(|(idx, entry)| (idx, (&entry.0, &entry.1)));
+        }
+        // like `Map`, this doesn't index without checking, so that lookups
+        // can't panic
+        let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+        let idx_index = self.disps.try_get_index(&hashes, self.idxs.len())?;
+        let idx = *self.idxs.get(idx_index)?;
+        let entry = if self.order.is_empty() {
+            entries.get(idx)?
+        } else {
+            entries.get(idx_index)?
+        };
 
         let b: &T = entry.0.borrow();
         if b == key {
@@ -138,13 +293,44 @@ impl<K, V> OrderedMap<K, V> {
         }
     }
 
+    /// Returns the first key, in definition order, which maps to `value`.
+    ///
+    /// This is a linear scan of the entries.
+    pub fn find_key_by_value(&self, value: &V) -> Option<&K>
+    where
+        V: PartialEq,
+    {
+        self.entries().find(|e| e.1 == value).map(|e| e.0)
+    }
+
+    /// Returns a `HashMap` from the values of the map to their keys.
+    ///
+    /// If several keys map to the same value, the first in definition order
+    /// is kept.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn invert(&self) -> HashMap<&V, &K>
+    where
+        V: Eq + Hash,
+    {
+        let mut inverted = HashMap::with_capacity(self.len());
+        for (k, v) in self.entries() {
+            inverted.entry(v).or_insert(k);
+        }
+        inverted
+    }
+
     /// Returns an iterator over the key/value pairs in the map.
     ///
     /// Entries are returned in the same order in which they were defined.
     pub fn entries(&self) -> Entries<'_, K, V> {
-        Entries {
-            iter: self.entries.iter(),
-        }
+        let iter = if self.order.is_empty() {
+            OrderIter::Direct(self.entries.iter())
+        } else {
+            OrderIter::Indirect(self.entries, self.order.iter())
+        };
+        Entries { iter }
     }
 
     /// Returns an iterator over the keys in the map.
@@ -164,9 +350,206 @@ impl<K, V> OrderedMap<K, V> {
             iter: self.entries(),
         }
     }
+
+    /// Binary searches the entries, in definition order, for one whose key
+    /// `f` maps to `b`.
+    ///
+    /// The entries must be sorted by `f`, e.g. by having been defined with
+    /// ascending keys, which the `@sorted;` flag of `phf_ordered_map!`
+    /// enforces. Otherwise the result is unspecified. Like
+    /// `slice::binary_search_by_key`, returns `Ok` with the index of a match,
+    /// or `Err` with the index at which a matching entry could be inserted.
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: Ord,
+        F: FnMut(&K, &V) -> B,
+    {
+        self.binary_search_by(|k, v| f(k, v).cmp(b))
+    }
+
+    /// Returns the index, in definition order, of the first entry for which
+    /// `pred` returns false.
+    ///
+    /// The entries must be partitioned by `pred`, with those for which it
+    /// returns true first. This answers queries which hash lookups can't, such
+    /// as finding the greatest key not above a bound:
+    ///
+    /// ```
+    /// use phf::{phf_ordered_map, OrderedMap};
+    ///
+    /// static LIMITS: OrderedMap<u32, &'static str> = phf_ordered_map! {
+    ///     @sorted;
+    ///     0u32 => "free",
+    ///     10u32 => "basic",
+    ///     100u32 => "pro",
+    /// };
+    ///
+    /// fn main() {
+    ///     let tier = |n| LIMITS.partition_point(|&k, _| k <= n).checked_sub(1);
+    ///     assert_eq!(Some((&10, &"basic")), tier(42).and_then(|i| LIMITS.index(i)));
+    ///     assert_eq!(Some((&100, &"pro")), tier(100).and_then(|i| LIMITS.index(i)));
+    /// }
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&K, &V) -> bool,
+    {
+        self.binary_search_by(|k, v| {
+            if pred(k, v) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+
+    fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&K, &V) -> Ordering,
+    {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (k, v) = self.index(mid).unwrap();
+            match f(k, v) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+
+    /// Returns an iterator over the key/value pairs at the given range of
+    /// indices within the list used to initialize the ordered map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing.
+    pub fn entries_range<R: RangeBounds<usize>>(&self, range: R) -> Entries<'_, K, V> {
+        let range = slice_range(range, self.len());
+        let iter = if self.order.is_empty() {
+            OrderIter::Direct(self.entries[range].iter())
+        } else {
+            OrderIter::Indirect(self.entries, self.order[range].iter())
+        };
+        Entries { iter }
+    }
+
+    /// Returns the entries at the given range of indices as a slice.
+    ///
+    /// Returns `None` if the map was generated with `slot_order`, since the
+    /// entries aren't stored in definition order then.
+    ///
+    /// ```
+    /// use phf::{phf_ordered_map, OrderedMap};
+    ///
+    /// static CATALOG: OrderedMap<&'static str, u32> = phf_ordered_map! {
+    ///     "apple" => 3,
+    ///     "banana" => 1,
+    ///     "cherry" => 7,
+    /// };
+    ///
+    /// fn main() {
+    ///     let page = CATALOG.entries_slice(1..).unwrap();
+    ///     assert_eq!(&[("banana", 1), ("cherry", 7)], page);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing.
+    pub fn entries_slice<R: RangeBounds<usize>>(&self, range: R) -> Option<&[(K, V)]> {
+        let range = slice_range(range, self.len());
+        if self.order.is_empty() {
+            Some(&self.entries[range])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the keys at the given range of indices.
+    ///
+    /// See `entries_range`.
+    pub fn keys_range<R: RangeBounds<usize>>(&self, range: R) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries_range(range),
+        }
+    }
+
+    /// Returns an iterator over the values at the given range of indices.
+    ///
+    /// See `entries_range`.
+    pub fn values_range<R: RangeBounds<usize>>(&self, range: R) -> Values<'_, K, V> {
+        Values {
+            iter: self.entries_range(range),
+        }
+    }
+}
+
+impl<K, V> OrderedMap<'_, K, V>
+where
+    K: PhfBorrow<str>,
+{
+    /// Returns an iterator over the entries whose keys start with `prefix`.
+    ///
+    /// The entries must have been defined with ascending keys, which the
+    /// `@sorted;` flag of `phf_ordered_map!` enforces, so that they are found
+    /// with a binary search. Otherwise the result is unspecified.
+    ///
+    /// ```
+    /// use phf::{phf_ordered_map, OrderedMap};
+    ///
+    /// static COMMANDS: OrderedMap<&'static str, u32> = phf_ordered_map! {
+    ///     @sorted;
+    ///     "branch" => 0,
+    ///     "checkout" => 1,
+    ///     "cherry-pick" => 2,
+    ///     "clone" => 3,
+    ///     "commit" => 4,
+    /// };
+    ///
+    /// fn main() {
+    ///     let completions = COMMANDS.keys_with_prefix("ch").collect::<Vec<_>>();
+    ///     assert_eq!(vec![&"checkout", &"cherry-pick"], completions);
+    /// }
+    /// ```
+    pub fn entries_with_prefix(&self, prefix: &str) -> Entries<'_, K, V> {
+        let start = self.partition_point(|k, _| k.borrow() < prefix);
+        let end = self.partition_point(|k, _| {
+            let k = k.borrow();
+            k < prefix || k.starts_with(prefix)
+        });
+        self.entries_range(start..end)
+    }
+
+    /// Returns an iterator over the keys which start with `prefix`.
+    ///
+    /// See `entries_with_prefix`.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries_with_prefix(prefix),
+        }
+    }
 }
 
-impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
+/// Resolves `range` against a slice of length `len`.
+fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflows"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflows"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    start..end
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedMap<'_, K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Entries<'a, K, V>;
 
@@ -177,7 +560,22 @@ impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
 
 /// An iterator over the entries in a `OrderedMap`.
 pub struct Entries<'a, K, V> {
-    iter: slice::Iter<'a, (K, V)>,
+    iter: OrderIter<'a, K, V>,
+}
+
+enum OrderIter<'a, K, V> {
+    Direct(slice::Iter<'a, (K, V)>),
+    Indirect(&'a [(K, V)], slice::Iter<'a, usize>),
+}
+
+impl<'a, K, V> Clone for OrderIter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            OrderIter::Direct(iter) => OrderIter::Direct(iter.clone()),
+            OrderIter::Indirect(entries, order) => OrderIter::Indirect(entries, order.clone()),
+        }
+    }
 }
 
 impl<'a, K, V> Clone for Entries<'a, K, V> {
@@ -203,17 +601,28 @@ impl<'a, K, V> Iterator for Entries<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next().map(|e| (&e.0, &e.1))
+        let entry = match &mut self.iter {
+            OrderIter::Direct(iter) => iter.next(),
+            OrderIter::Indirect(entries, order) => order.next().map(|&slot| &entries[slot]),
+        };
+        entry.map(|e| (&e.0, &e.1))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        match &self.iter {
+            OrderIter::Direct(iter) => iter.size_hint(),
+            OrderIter::Indirect(_, order) => order.size_hint(),
+        }
     }
 }
 
 impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
     fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next_back().map(|e| (&e.0, &e.1))
+        let entry = match &mut self.iter {
+            OrderIter::Direct(iter) => iter.next_back(),
+            OrderIter::Indirect(entries, order) => order.next_back().map(|&slot| &entries[slot]),
+        };
+        entry.map(|e| (&e.0, &e.1))
     }
 }
 
@@ -3,6 +3,7 @@ use crate::{ordered_map, OrderedMap, PhfHash};
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
+use core::ops::RangeBounds;
 use phf_shared::PhfBorrow;
 
 /// An order-preserving immutable set constructed at compile time.
@@ -15,12 +16,12 @@ use phf_shared::PhfBorrow;
 /// The fields of this struct are public so that they may be initialized by the
 /// `phf_ordered_set!` macro and code generation. They are subject to change at
 /// any time and should never be accessed directly.
-pub struct OrderedSet<T: 'static> {
+pub struct OrderedSet<'a, T> {
     #[doc(hidden)]
-    pub map: OrderedMap<T, ()>,
+    pub map: OrderedMap<'a, T, ()>,
 }
 
-impl<T> fmt::Debug for OrderedSet<T>
+impl<T> fmt::Debug for OrderedSet<'_, T>
 where
     T: fmt::Debug,
 {
@@ -29,7 +30,7 @@ where
     }
 }
 
-impl<T> OrderedSet<T> {
+impl<'a, T> OrderedSet<'a, T> {
     /// Returns the number of elements in the `OrderedSet`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -45,8 +46,8 @@ impl<T> OrderedSet<T> {
     /// Returns a reference to the set's internal static instance of the given
     /// key.
     ///
-    /// This can be useful for interning schemes.
-    pub fn get_key<U: ?Sized>(&self, key: &U) -> Option<&T>
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key<U: ?Sized>(&self, key: &U) -> Option<&'a T>
     where
         U: Eq + PhfHash,
         T: PhfBorrow<U>,
@@ -64,6 +65,50 @@ impl<T> OrderedSet<T> {
         self.map.get_index(key)
     }
 
+    /// Returns the index of the key within the list used to initialize
+    /// the ordered set.
+    ///
+    /// This is the same as `get_index`, under the name used by `indexmap`.
+    pub fn get_index_of<U: ?Sized>(&self, key: &U) -> Option<usize>
+    where
+        U: Eq + PhfHash,
+        T: PhfBorrow<U>,
+    {
+        self.map.get_index_of(key)
+    }
+
+    /// Returns the index `key` would have within the list used to initialize
+    /// the ordered set, without checking that `key` is actually in the set.
+    ///
+    /// See `OrderedMap::get_index_unchecked`.
+    pub fn get_index_unchecked<U: ?Sized>(&self, key: &U) -> usize
+    where
+        U: Eq + PhfHash,
+        T: PhfBorrow<U>,
+    {
+        self.map.get_index_unchecked(key)
+    }
+
+    /// Like `get_key`, but also returns the index of the key within the list
+    /// used to initialize the ordered set, in a single probe.
+    pub fn get_full<U: ?Sized>(&self, key: &U) -> Option<(usize, &T)>
+    where
+        U: Eq + PhfHash,
+        T: PhfBorrow<U>,
+    {
+        self.map.get_full(key).map(|(i, k, &())| (i, k))
+    }
+
+    /// Checks that looking up each stored key finds that very key.
+    ///
+    /// See `Map::verify`.
+    pub fn verify(&self) -> Result<(), &T>
+    where
+        T: PhfHash,
+    {
+        self.map.verify()
+    }
+
     /// Returns a reference to the key at an index
     /// within the list used to initialize the ordered set. See `.get_index(key)`.
     pub fn index(&self, index: usize) -> Option<&T> {
@@ -87,32 +132,130 @@ impl<T> OrderedSet<T> {
             iter: self.map.keys(),
         }
     }
+
+    /// Binary searches the values, in definition order, for one which `f`
+    /// maps to `b`.
+    ///
+    /// See `OrderedMap::binary_search_by_key`.
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: Ord,
+        F: FnMut(&T) -> B,
+    {
+        self.map.binary_search_by_key(b, |k, _| f(k))
+    }
+
+    /// Returns the index, in definition order, of the first value for which
+    /// `pred` returns false.
+    ///
+    /// See `OrderedMap::partition_point`.
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.map.partition_point(|k, _| pred(k))
+    }
+
+    /// Returns an iterator over the values at the given range of indices
+    /// within the list used to initialize the ordered set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like slicing.
+    pub fn iter_range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, T> {
+        Iter {
+            iter: self.map.keys_range(range),
+        }
+    }
+}
+
+impl<T> OrderedSet<'_, T>
+where
+    T: PhfBorrow<str>,
+{
+    /// Returns an iterator over the values which start with `prefix`.
+    ///
+    /// The values must have been defined in ascending order, which the
+    /// `@sorted;` flag of `phf_ordered_set!` enforces. See
+    /// `OrderedMap::entries_with_prefix`.
+    pub fn iter_with_prefix(&self, prefix: &str) -> Iter<'_, T> {
+        Iter {
+            iter: self.map.keys_with_prefix(prefix),
+        }
+    }
 }
 
-impl<T> OrderedSet<T>
+impl<T> OrderedSet<'_, T>
 where
     T: Eq + PhfHash + PhfBorrow<T>,
 {
     /// Returns true if `other` shares no elements with `self`.
     #[inline]
-    pub fn is_disjoint(&self, other: &OrderedSet<T>) -> bool {
+    pub fn is_disjoint(&self, other: &OrderedSet<'_, T>) -> bool {
         !self.iter().any(|value| other.contains(value))
     }
 
     /// Returns true if `other` contains all values in `self`.
     #[inline]
-    pub fn is_subset(&self, other: &OrderedSet<T>) -> bool {
+    pub fn is_subset(&self, other: &OrderedSet<'_, T>) -> bool {
         self.iter().all(|value| other.contains(value))
     }
 
     /// Returns true if `self` contains all values in `other`.
     #[inline]
-    pub fn is_superset(&self, other: &OrderedSet<T>) -> bool {
+    pub fn is_superset(&self, other: &OrderedSet<'_, T>) -> bool {
         other.is_subset(self)
     }
+
+    /// Returns an iterator over the values in both `self` and `other`, in the
+    /// order of `self`.
+    ///
+    /// ```
+    /// use phf::{phf_ordered_set, OrderedSet};
+    ///
+    /// // TLS versions, most preferred first
+    /// static PREFERRED: OrderedSet<u16> = phf_ordered_set! {
+    ///     0x0304u16, 0x0303u16, 0x0302u16,
+    /// };
+    /// static SUPPORTED: OrderedSet<u16> = phf_ordered_set! {
+    ///     0x0301u16, 0x0303u16, 0x0304u16,
+    /// };
+    ///
+    /// fn main() {
+    ///     let both = PREFERRED.intersection(&SUPPORTED).collect::<Vec<_>>();
+    ///     assert_eq!(vec![&0x0304, &0x0303], both);
+    /// }
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a OrderedSet<'_, T>) -> Intersection<'a, T> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the values in `self` but not in `other`, in
+    /// the order of `self`.
+    pub fn difference<'a>(&'a self, other: &'a OrderedSet<'_, T>) -> Difference<'a, T> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the values in `self` or `other`.
+    ///
+    /// The values of `self` are returned first, in its order, followed by
+    /// those only in `other`, in its order.
+    pub fn union<'a>(&'a self, other: &'a OrderedSet<'_, T>) -> Union<'a, T> {
+        Union {
+            iter: self.iter(),
+            other_iter: other.iter(),
+            set: self,
+        }
+    }
 }
 
-impl<'a, T> IntoIterator for &'a OrderedSet<T> {
+impl<'a, T> IntoIterator for &'a OrderedSet<'_, T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -168,3 +311,141 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
 impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
 
 impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// An iterator over the values in both of two `OrderedSet`s, returned by
+/// `OrderedSet::intersection`.
+pub struct Intersection<'a, T> {
+    iter: Iter<'a, T>,
+    other: &'a OrderedSet<'a, T>,
+}
+
+impl<'a, T> Clone for Intersection<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Intersection<'a, T>
+where
+    T: fmt::Debug + Eq + PhfHash + PhfBorrow<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Intersection<'a, T>
+where
+    T: Eq + PhfHash + PhfBorrow<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|value| other.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for Intersection<'a, T> where T: Eq + PhfHash + PhfBorrow<T> {}
+
+/// An iterator over the values in one `OrderedSet` but not another, returned
+/// by `OrderedSet::difference`.
+pub struct Difference<'a, T> {
+    iter: Iter<'a, T>,
+    other: &'a OrderedSet<'a, T>,
+}
+
+impl<'a, T> Clone for Difference<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Difference<'a, T>
+where
+    T: fmt::Debug + Eq + PhfHash + PhfBorrow<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Difference<'a, T>
+where
+    T: Eq + PhfHash + PhfBorrow<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|value| !other.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for Difference<'a, T> where T: Eq + PhfHash + PhfBorrow<T> {}
+
+/// An iterator over the values in either of two `OrderedSet`s, returned by
+/// `OrderedSet::union`.
+pub struct Union<'a, T> {
+    iter: Iter<'a, T>,
+    other_iter: Iter<'a, T>,
+    set: &'a OrderedSet<'a, T>,
+}
+
+impl<'a, T> Clone for Union<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            other_iter: self.other_iter.clone(),
+            set: self.set,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Union<'a, T>
+where
+    T: fmt::Debug + Eq + PhfHash + PhfBorrow<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Union<'a, T>
+where
+    T: Eq + PhfHash + PhfBorrow<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if let Some(value) = self.iter.next() {
+            return Some(value);
+        }
+        let set = self.set;
+        self.other_iter.find(|value| !set.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len();
+        (len, Some(len + self.other_iter.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for Union<'a, T> where T: Eq + PhfHash + PhfBorrow<T> {}
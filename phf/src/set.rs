@@ -4,8 +4,13 @@ use core::iter::FusedIterator;
 use core::iter::IntoIterator;
 
 use phf_shared::{PhfBorrow, PhfHash};
+#[cfg(feature = "std")]
+use std::collections::{hash_set, HashSet};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
 
-use crate::{map, Map};
+use crate::map::{self, SortedEntries};
+use crate::Map;
 
 /// An immutable set constructed at compile time.
 ///
@@ -14,12 +19,14 @@ use crate::{map, Map};
 /// The fields of this struct are public so that they may be initialized by the
 /// `phf_set!` macro and code generation. They are subject to change at any
 /// time and should never be accessed directly.
-pub struct Set<T: 'static> {
+pub struct Set<'a, T> {
     #[doc(hidden)]
-    pub map: Map<T, ()>,
+    pub map: Map<'a, T, ()>,
 }
 
-impl<T> fmt::Debug for Set<T>
+/// Prints the elements in iteration order, even with `{:#?}`, see
+/// `Set::debug_sorted` for sorted output.
+impl<T> fmt::Debug for Set<'_, T>
 where
     T: fmt::Debug,
 {
@@ -28,7 +35,35 @@ where
     }
 }
 
-impl<T> Set<T> {
+/// Prints the elements of a `Set` in ascending order, see
+/// `Set::debug_sorted`.
+pub struct DebugSorted<'a, T> {
+    entries: map::Entries<'a, T, ()>,
+}
+
+impl<'a, T> fmt::Debug for DebugSorted<'a, T>
+where
+    T: fmt::Debug + Ord,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set()
+            .entries(SortedEntries::new(self.entries.clone()).map(|e| e.0))
+            .finish()
+    }
+}
+
+impl<'a, T> Set<'a, T> {
+    /// Returns an adapter which prints the elements in ascending order, with
+    /// either `{:?}` or `{:#?}`, see `Map::debug_sorted`.
+    pub fn debug_sorted(&self) -> DebugSorted<'_, T>
+    where
+        T: Ord,
+    {
+        DebugSorted {
+            entries: self.map.entries(),
+        }
+    }
+
     /// Returns the number of elements in the `Set`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -41,11 +76,39 @@ impl<T> Set<T> {
         self.len() == 0
     }
 
+    /// Returns the number of slots in the `Set`, see `Map::capacity`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns the number of displacement buckets the elements are hashed
+    /// into, see `Map::buckets`.
+    #[inline]
+    pub const fn buckets(&self) -> usize {
+        self.map.buckets()
+    }
+
+    /// Returns the average number of elements per displacement bucket, see
+    /// `Map::load_factor`.
+    pub fn load_factor(&self) -> f64 {
+        self.map.load_factor()
+    }
+
+    /// Returns the largest displacement in the `Set`, see
+    /// `Map::max_displacement`.
+    ///
+    /// Requires the `stats` feature.
+    #[cfg(feature = "stats")]
+    pub fn max_displacement(&self) -> u32 {
+        self.map.max_displacement()
+    }
+
     /// Returns a reference to the set's internal static instance of the given
     /// key.
     ///
-    /// This can be useful for interning schemes.
-    pub fn get_key<U: ?Sized>(&self, key: &U) -> Option<&T>
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key<U: ?Sized>(&self, key: &U) -> Option<&'a T>
     where
         U: Eq + PhfHash,
         T: PhfBorrow<U>,
@@ -53,6 +116,16 @@ impl<T> Set<T> {
         self.map.get_key(key)
     }
 
+    /// Checks that looking up each stored key finds that very key.
+    ///
+    /// See `Map::verify`.
+    pub fn verify(&self) -> Result<(), &T>
+    where
+        T: PhfHash,
+    {
+        self.map.verify()
+    }
+
     /// Returns true if `value` is in the `Set`.
     pub fn contains<U: ?Sized>(&self, value: &U) -> bool
     where
@@ -62,9 +135,73 @@ impl<T> Set<T> {
         self.map.contains_key(value)
     }
 
+    /// Returns true if all of `values` are in the `Set`.
+    ///
+    /// Stops at the first value which isn't in the set, and returns true if
+    /// `values` is empty.
+    pub fn contains_all<'b, U: ?Sized + 'b, I>(&self, values: I) -> bool
+    where
+        I: IntoIterator<Item = &'b U>,
+        U: Eq + PhfHash,
+        T: PhfBorrow<U>,
+    {
+        values.into_iter().all(|value| self.contains(value))
+    }
+
+    /// Returns true if any of `values` is in the `Set`.
+    ///
+    /// Stops at the first value which is in the set, and returns false if
+    /// `values` is empty.
+    ///
+    /// ```
+    /// use phf::{phf_set, Set};
+    ///
+    /// static RESERVED: Set<&'static str> = phf_set! {
+    ///     "fn",
+    ///     "let",
+    /// };
+    ///
+    /// fn main() {
+    ///     let tokens = ["let", "x", "=", "1"];
+    ///     assert!(RESERVED.contains_any(tokens.iter().copied()));
+    ///     assert!(!RESERVED.contains_all(tokens.iter().copied()));
+    /// }
+    /// ```
+    pub fn contains_any<'b, U: ?Sized + 'b, I>(&self, values: I) -> bool
+    where
+        I: IntoIterator<Item = &'b U>,
+        U: Eq + PhfHash,
+        T: PhfBorrow<U>,
+    {
+        values.into_iter().any(|value| self.contains(value))
+    }
+
+    /// Returns an adapter which displays the values as a list, for error
+    /// messages such as "expected one of `a`, `b`".
+    ///
+    /// See `Map::keys_display`.
+    ///
+    /// ```
+    /// use phf::{phf_set, Set};
+    ///
+    /// static COLORS: Set<&'static str> = phf_set!("auto", "always", "never");
+    ///
+    /// fn main() {
+    ///     assert_eq!(
+    ///         "`always`, `auto`, `never`",
+    ///         COLORS.display_expected().sorted().to_string()
+    ///     );
+    /// }
+    /// ```
+    pub fn display_expected(&self) -> map::KeysDisplay<'_, T, ()> {
+        self.map.keys_display()
+    }
+
     /// Returns an iterator over the values in the set.
     ///
-    /// Values are returned in an arbitrary but fixed order.
+    /// Values are returned in an arbitrary but fixed order, or in definition
+    /// order if the set was generated with the `@definition_order;` flag or
+    /// its `phf_codegen` equivalent.
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             iter: self.map.keys(),
@@ -72,27 +209,92 @@ impl<T> Set<T> {
     }
 }
 
-impl<T> Set<T>
+impl<T: Ord> Set<'_, T> {
+    /// Returns an iterator over the values in ascending order.
+    ///
+    /// See `Map::iter_sorted`. Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn iter_sorted(&self) -> SortedIter<'_, T> {
+        SortedIter {
+            iter: self.map.iter_sorted(),
+        }
+    }
+}
+
+impl Set<'_, &str> {
+    /// Determines if `value` is in the `Set` in a constant context, for
+    /// `phf_assert_key!`.
+    #[doc(hidden)]
+    pub const fn __contains_str_const(&self, value: &str) -> bool {
+        self.map.__contains_str_const(value)
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl Set<'_, &str> {
+    /// Returns true if the NFC normal form of `value` is in the `Set`.
+    ///
+    /// Requires the `unicode-normalization` feature. This is meant for sets
+    /// generated with `@normalize = nfc;`, see [`Map::get_nfc`].
+    pub fn contains_nfc(&self, value: &str) -> bool {
+        self.map.get_entry_nfc(value).is_some()
+    }
+}
+
+impl<T> Set<'_, T>
 where
     T: Eq + PhfHash + PhfBorrow<T>,
 {
     /// Returns true if `other` shares no elements with `self`.
-    pub fn is_disjoint(&self, other: &Set<T>) -> bool {
+    pub fn is_disjoint(&self, other: &Set<'_, T>) -> bool {
         !self.iter().any(|value| other.contains(value))
     }
 
     /// Returns true if `other` contains all values in `self`.
-    pub fn is_subset(&self, other: &Set<T>) -> bool {
+    pub fn is_subset(&self, other: &Set<'_, T>) -> bool {
         self.iter().all(|value| other.contains(value))
     }
 
     /// Returns true if `self` contains all values in `other`.
-    pub fn is_superset(&self, other: &Set<T>) -> bool {
+    pub fn is_superset(&self, other: &Set<'_, T>) -> bool {
         other.is_subset(self)
     }
+
+    /// Returns an iterator over the values in `self` or `other`, but not in
+    /// both.
+    ///
+    /// The values of `self` are returned first.
+    pub fn symmetric_difference<'b>(&'b self, other: &'b Set<'_, T>) -> SymmetricDifference<'b, T> {
+        SymmetricDifference {
+            iter: self.iter(),
+            set: self,
+            other_iter: other.iter(),
+            other,
+        }
+    }
+
+    /// Like `symmetric_difference`, but with a `HashSet`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn symmetric_difference_hash_set<'b, S>(
+        &'b self,
+        other: &'b HashSet<T, S>,
+    ) -> HashSetSymmetricDifference<'b, T, S>
+    where
+        T: Hash,
+        S: BuildHasher,
+    {
+        HashSetSymmetricDifference {
+            iter: self.iter(),
+            set: self,
+            other_iter: other.iter(),
+            other,
+        }
+    }
 }
 
-impl<'a, T> IntoIterator for &'a Set<T> {
+impl<'a, T> IntoIterator for &'a Set<'_, T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -102,7 +304,7 @@ impl<'a, T> IntoIterator for &'a Set<T> {
 }
 
 /// An iterator over the values in a `Set`.
-pub struct Iter<'a, T: 'static> {
+pub struct Iter<'a, T> {
     iter: map::Keys<'a, T, ()>,
 }
 
@@ -145,3 +347,175 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
 impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
 
 impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// An iterator over the values in a `Set` in ascending order, see
+/// `Set::iter_sorted`.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub struct SortedIter<'a, T> {
+    iter: map::SortedIter<'a, T, ()>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Clone for SortedIter<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> fmt::Debug for SortedIter<'a, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for SortedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> DoubleEndedIterator for SortedIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> ExactSizeIterator for SortedIter<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> FusedIterator for SortedIter<'a, T> {}
+
+/// An iterator over the values in either of two `Set`s but not in both.
+pub struct SymmetricDifference<'a, T> {
+    iter: Iter<'a, T>,
+    set: &'a Set<'a, T>,
+    other_iter: Iter<'a, T>,
+    other: &'a Set<'a, T>,
+}
+
+impl<'a, T> Clone for SymmetricDifference<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            set: self.set,
+            other_iter: self.other_iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for SymmetricDifference<'a, T>
+where
+    T: fmt::Debug + Eq + PhfHash + PhfBorrow<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for SymmetricDifference<'a, T>
+where
+    T: Eq + PhfHash + PhfBorrow<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        if let Some(value) = self.iter.find(|value| !other.contains(*value)) {
+            return Some(value);
+        }
+        let set = self.set;
+        self.other_iter.find(|value| !set.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len() + self.other_iter.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for SymmetricDifference<'a, T> where T: Eq + PhfHash + PhfBorrow<T> {}
+
+/// An iterator over the values in either a `Set` or a `HashSet` but not in
+/// both.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub struct HashSetSymmetricDifference<'a, T, S> {
+    iter: Iter<'a, T>,
+    set: &'a Set<'a, T>,
+    other_iter: hash_set::Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, S> Clone for HashSetSymmetricDifference<'a, T, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            set: self.set,
+            other_iter: self.other_iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, S> fmt::Debug for HashSetSymmetricDifference<'a, T, S>
+where
+    T: fmt::Debug + Eq + Hash + PhfHash + PhfBorrow<T>,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, S> Iterator for HashSetSymmetricDifference<'a, T, S>
+where
+    T: Eq + Hash + PhfHash + PhfBorrow<T>,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        if let Some(value) = self.iter.find(|value| !other.contains(*value)) {
+            return Some(value);
+        }
+        let set = self.set;
+        self.other_iter.find(|value| !set.contains(*value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len() + self.other_iter.len()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, S> FusedIterator for HashSetSymmetricDifference<'a, T, S>
+where
+    T: Eq + Hash + PhfHash + PhfBorrow<T>,
+    S: BuildHasher,
+{
+}
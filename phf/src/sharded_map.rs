@@ -0,0 +1,295 @@
+//! An immutable map split into shards, constructed at compile time.
+use core::fmt;
+use core::iter::{FlatMap, FusedIterator, IntoIterator};
+use core::ops::Index;
+use core::slice;
+use phf_shared::{self, HashFn, HashKey, PhfBorrow, PhfHash};
+
+use crate::{map, Map};
+
+/// An immutable map split into shards, constructed at compile time.
+///
+/// A top-level hash of the key picks one of several independent `Map`s,
+/// which is then searched as usual. Splitting a huge table this way keeps
+/// each generated `static` to a size which rustc and the linker handle
+/// comfortably, and lets `phf_codegen` write every shard to its own file.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct ShardedMap<'a, K, V> {
+    #[doc(hidden)]
+    pub key: HashKey,
+    #[doc(hidden)]
+    pub hasher: HashFn,
+    #[doc(hidden)]
+    pub len: usize,
+    #[doc(hidden)]
+    pub shards: &'a [&'a Map<'a, K, V>],
+}
+
+impl<K, V> fmt::Debug for ShardedMap<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<K, V, T: ?Sized> Index<&T> for ShardedMap<'_, K, V>
+where
+    T: Eq + PhfHash,
+    K: PhfBorrow<T>,
+{
+    type Output = V;
+
+    fn index(&self, k: &T) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<'a, K, V> ShardedMap<'a, K, V> {
+    /// Returns the number of entries in the `ShardedMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the `ShardedMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `ShardedMap`.
+    pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T: ?Sized>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.shard(key)?.get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T: ?Sized>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash,
+        K: PhfBorrow<T>,
+    {
+        self.shard(key)?.get_entry(key)
+    }
+
+    /// Returns the shard which would hold `key`.
+    fn shard<T: ?Sized + PhfHash>(&self, key: &T) -> Option<&'a Map<'a, K, V>> {
+        let hashes = phf_shared::hash_with(key, &self.key, self.hasher);
+        let shard = phf_shared::try_get_shard(&hashes, self.shards.len())?;
+        self.shards.get(shard as usize).copied()
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            iter: self.shards.iter().flat_map(|shard| shard.entries()),
+            remaining: self.len,
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in an arbitrary but fixed order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ShardedMap<'_, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+type ShardEntries<'a, K, V> = fn(&'a &'a Map<'a, K, V>) -> map::Entries<'a, K, V>;
+type ShardsIter<'a, K, V> = slice::Iter<'a, &'a Map<'a, K, V>>;
+
+/// An iterator over the key/value pairs in a `ShardedMap`.
+pub struct Entries<'a, K, V> {
+    iter: FlatMap<ShardsIter<'a, K, V>, map::Entries<'a, K, V>, ShardEntries<'a, K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let entry = self.iter.next()?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        let entry = self.iter.next_back()?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
+
+/// An iterator over the keys in a `ShardedMap`.
+pub struct Keys<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Keys<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values in a `ShardedMap`.
+pub struct Values<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Values<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Values<'a, K, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back().map(|e| e.1)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
@@ -0,0 +1,124 @@
+//! Did-you-mean suggestions for keys missing from tables of strings.
+//!
+//! Requires the `suggest` feature.
+//!
+//! ```
+//! use phf::{phf_map, phf_set};
+//!
+//! static COMMANDS: phf::Set<&'static str> = phf_set!("build", "check", "install");
+//! static OPTIONS: phf::Map<&'static str, char> = phf_map! {
+//!     "verbose" => 'v',
+//!     "quiet" => 'q',
+//! };
+//!
+//! fn main() {
+//!     assert_eq!(Ok("check"), COMMANDS.get_or_suggest("check"));
+//!     let err = COMMANDS.get_or_suggest("biuld").unwrap_err();
+//!     assert_eq!("did you mean `build`?", err.to_string());
+//!     assert_eq!(Some("install"), COMMANDS.get_or_suggest("inst").unwrap_err().closest());
+//!
+//!     assert_eq!(Ok(&'q'), OPTIONS.get_or_suggest("quiet"));
+//!     assert_eq!(Some("verbose"), OPTIONS.get_or_suggest("verbos").unwrap_err().closest());
+//!     assert_eq!(None, OPTIONS.get_or_suggest("help").unwrap_err().closest());
+//! }
+//! ```
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Map, Set};
+
+/// The reason a lookup with `Map::get_or_suggest` or `Set::get_or_suggest`
+/// failed, along with the closest key in the table, if any is close enough.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suggestion<'a> {
+    closest: Option<&'a str>,
+}
+
+impl<'a> Suggestion<'a> {
+    /// Returns the key closest to the one looked up.
+    ///
+    /// A key is close if it starts with the key looked up, or is at most a
+    /// third of its characters, rounded up, away from it in edit distance. The key with the smallest distance wins, and ties go to the
+    /// key which sorts first, so the result doesn't depend on the table.
+    pub fn closest(&self) -> Option<&'a str> {
+        self.closest
+    }
+}
+
+/// Prints "did you mean `key`?", or "no similar key" if no key is close.
+impl<'a> fmt::Display for Suggestion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.closest {
+            Some(key) => write!(f, "did you mean `{}`?", key),
+            None => write!(f, "no similar key"),
+        }
+    }
+}
+
+impl<'k, V> Map<'_, &'k str, V> {
+    /// Like `get`, but returns the closest key on a miss.
+    ///
+    /// Misses scan all keys, so this is meant for interactive input such as
+    /// command lines. See [`Suggestion::closest`] for which keys are close.
+    ///
+    /// Requires the `suggest` feature.
+    pub fn get_or_suggest(&self, key: &str) -> Result<&V, Suggestion<'k>> {
+        self.get(key).ok_or_else(|| suggest(key, self.keys()))
+    }
+}
+
+impl<'k> Set<'_, &'k str> {
+    /// Like `get_key`, but returns the closest key on a miss.
+    ///
+    /// See `Map::get_or_suggest`. Requires the `suggest` feature.
+    pub fn get_or_suggest(&self, key: &str) -> Result<&'k str, Suggestion<'k>> {
+        match self.get_key(key) {
+            Some(key) => Ok(*key),
+            None => Err(suggest(key, self.iter())),
+        }
+    }
+}
+
+fn suggest<'a, 'k: 'a>(key: &str, keys: impl Iterator<Item = &'a &'k str>) -> Suggestion<'k> {
+    // `usize::div_ceil` is newer than our MSRV
+    #[allow(clippy::manual_div_ceil)]
+    let max_distance = (key.chars().count() + 2) / 3;
+    let mut row = Vec::new();
+    let mut closest: Option<(usize, &'k str)> = None;
+    for &candidate in keys {
+        let distance = distance(key, candidate, &mut row);
+        let close = distance <= max_distance || (!key.is_empty() && candidate.starts_with(key));
+        if !close {
+            continue;
+        }
+        closest = match closest {
+            Some(best) if best <= (distance, candidate) => Some(best),
+            _ => Some((distance, candidate)),
+        };
+    }
+    Suggestion {
+        closest: closest.map(|(_, key)| key),
+    }
+}
+
+/// Returns the Levenshtein distance between `a` and `b` in characters, using
+/// `row` as scratch space.
+fn distance(a: &str, b: &str, row: &mut Vec<usize>) -> usize {
+    row.clear();
+    row.extend(0..=b.chars().count());
+    for (i, ca) in a.chars().enumerate() {
+        // the distance between the previous prefixes of both strings
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.chars().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[row.len() - 1]
+}
@@ -0,0 +1,406 @@
+//! An immutable map of string or byte string keys constructed at compile
+//! time, supporting prefix lookups.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::iter::IntoIterator;
+use core::ops::Index;
+use core::slice;
+
+/// An immutable map of string or byte string keys constructed at compile
+/// time, supporting prefix lookups.
+///
+/// Unlike a `Map`, a `TrieMap` can find the entries whose keys are prefixes
+/// of a given key, e.g. to route a URL path. Lookups walk a byte-wise trie,
+/// so they take time proportional to the length of the key rather than to
+/// the number of entries.
+///
+/// Entries are iterated in the order they were defined in.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_trie_map!` macro and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct TrieMap<'a, K, V> {
+    #[doc(hidden)]
+    pub nodes: &'a [Node<'a>],
+    #[doc(hidden)]
+    pub entries: &'a [(K, V)],
+}
+
+/// A node of a `TrieMap`, the root being the first.
+#[doc(hidden)]
+pub struct Node<'a> {
+    /// The index of the entry whose key ends at this node.
+    pub entry: Option<usize>,
+    /// The children of this node, sorted by byte.
+    pub edges: &'a [(u8, usize)],
+}
+
+impl Node<'_> {
+    #[inline]
+    fn child(&self, byte: u8) -> Option<usize> {
+        let i = self.edges.binary_search_by_key(&byte, |&(b, _)| b).ok()?;
+        self.edges.get(i).map(|&(_, child)| child)
+    }
+}
+
+impl<K, V> fmt::Debug for TrieMap<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<K, V, T: ?Sized> Index<&T> for TrieMap<'_, K, V>
+where
+    T: AsRef<[u8]>,
+{
+    type Output = V;
+
+    fn index(&self, k: &T) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<'a, K, V> TrieMap<'a, K, V> {
+    /// Returns the number of entries in the `TrieMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the `TrieMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `TrieMap`.
+    pub fn contains_key<T: ?Sized>(&self, key: &T) -> bool
+    where
+        T: AsRef<[u8]>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T: ?Sized>(&self, key: &T) -> Option<&V>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This can be useful for interning schemes, see `Map::get_key`.
+    pub fn get_key<T: ?Sized>(&self, key: &T) -> Option<&'a K>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.find(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T: ?Sized>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.find(key)
+    }
+
+    fn find<T: ?Sized>(&self, key: &T) -> Option<(&'a K, &'a V)>
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut node = self.nodes.first()?;
+        for &byte in key.as_ref() {
+            node = self.nodes.get(node.child(byte)?)?;
+        }
+        self.entry(node.entry?)
+    }
+
+    /// Returns the entry with the longest key which is a prefix of `key`.
+    ///
+    /// ```
+    /// use phf::{phf_trie_map, TrieMap};
+    ///
+    /// static ROUTES: TrieMap<&'static str, u32> = phf_trie_map! {
+    ///     "/" => 0,
+    ///     "/api/" => 1,
+    /// };
+    ///
+    /// fn main() {
+    ///     assert_eq!(Some((&"/api/", &1)), ROUTES.longest_prefix("/api/users"));
+    ///     assert_eq!(Some((&"/", &0)), ROUTES.longest_prefix("/index.html"));
+    ///     assert_eq!(None, ROUTES.longest_prefix("index.html"));
+    /// }
+    /// ```
+    pub fn longest_prefix<T: ?Sized>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.get_prefix_matches(key).last()
+    }
+
+    /// Returns an iterator over the entries whose keys are prefixes of `key`,
+    /// including `key` itself.
+    ///
+    /// Entries are returned in order of ascending key length.
+    pub fn get_prefix_matches<'b, 'k, T: ?Sized>(
+        &'b self,
+        key: &'k T,
+    ) -> PrefixMatches<'b, 'k, K, V>
+    where
+        T: AsRef<[u8]>,
+    {
+        PrefixMatches {
+            map: self,
+            node: self.nodes.first(),
+            rest: key.as_ref(),
+        }
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in the same order in which they are defined.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in the same order in which they are defined.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned in the same order in which they are defined.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+
+    #[inline]
+    fn entry(&self, index: usize) -> Option<(&'a K, &'a V)> {
+        let (key, value) = self.entries.get(index)?;
+        Some((key, value))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TrieMap<'_, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the entries of a `TrieMap` whose keys are prefixes of a
+/// given key.
+pub struct PrefixMatches<'a, 'k, K, V> {
+    map: &'a TrieMap<'a, K, V>,
+    // `None` once the walk has fallen off the trie
+    node: Option<&'a Node<'a>>,
+    rest: &'k [u8],
+}
+
+impl<'a, 'k, K, V> Clone for PrefixMatches<'a, 'k, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map,
+            node: self.node,
+            rest: self.rest,
+        }
+    }
+}
+
+impl<'a, 'k, K, V> fmt::Debug for PrefixMatches<'a, 'k, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, 'k, K, V> Iterator for PrefixMatches<'a, 'k, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        while let Some(node) = self.node {
+            let entry = node.entry;
+            self.node = match self.rest.split_first() {
+                Some((&byte, rest)) => {
+                    self.rest = rest;
+                    node.child(byte).and_then(|i| self.map.nodes.get(i))
+                }
+                None => None,
+            };
+            if let Some(entry) = entry.and_then(|i| self.map.entry(i)) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.node {
+            Some(_) => (0, Some(self.rest.len() + 1)),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl<'a, 'k, K, V> FusedIterator for PrefixMatches<'a, 'k, K, V> {}
+
+/// An iterator over the key/value pairs in a `TrieMap`.
+pub struct Entries<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next().map(|e| (&e.0, &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next_back().map(|e| (&e.0, &e.1))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
+
+/// An iterator over the keys in a `TrieMap`.
+pub struct Keys<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Keys<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.iter.next_back().map(|e| e.0)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values in a `TrieMap`.
+pub struct Values<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Values<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Values<'a, K, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.iter.next_back().map(|e| e.1)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
@@ -101,6 +101,51 @@
 //! }
 //! ```
 //!
+//! ##### Hash Functions and Strategies
+//!
+//! Each builder picks its hash function and, for maps with string keys, the
+//! data structure it generates, so tables with different keys can be tuned
+//! separately. The generated type depends on the strategy: a `phf::Map` for
+//! [`Strategy::Chd`] and [`Strategy::SortedSlice`], and a `phf::LengthMap`
+//! for [`Strategy::LengthBucketed`].
+//!
+//! ```rust
+//! use phf_codegen::{HashFn, Strategy};
+//!
+//! let mut keywords = phf_codegen::Map::new();
+//! keywords
+//!     .hasher(HashFn::ShortKey)
+//!     .strategy(Strategy::LengthBucketed)
+//!     .entry("loop", "1")
+//!     .entry("while", "2");
+//! assert!(keywords.build().to_string().starts_with("::phf::LengthMap {"));
+//!
+//! let mut paths = phf_codegen::Map::new();
+//! paths
+//!     .hasher(HashFn::Sip)
+//!     .entry("/usr/bin", "1")
+//!     .entry("/usr/lib", "2");
+//! assert!(paths.build().to_string().starts_with("::phf::Map {"));
+//! ```
+//!
+//! ##### Generic Value Types
+//!
+//! Values are written exactly as given, in expression position, so generic
+//! paths in them need a turbofish: `Cow::<str>::Borrowed("a")` rather than
+//! `Cow<str>::Borrowed("a")`, which doesn't parse. The builders never name
+//! the value type, so it's spelled out in full in the item signature:
+//!
+//! ```rust
+//! let mut map = phf_codegen::Map::new();
+//! map.entry(1u32, "::std::borrow::Cow::<'static, str>::Borrowed(\"one\")")
+//!     .entry(2u32, "::std::borrow::Cow::Borrowed(\"two\")");
+//! let code = format!(
+//!     "static NUMBERS: ::phf::Map<u32, ::std::borrow::Cow<'static, str>> = {};",
+//!     map.build()
+//! );
+//! assert!(code.contains("(1, ::std::borrow::Cow::<'static, str>::Borrowed(\"one\"))"));
+//! ```
+//!
 //! # Note
 //!
 //! The compiler's stack will overflow when processing extremely long method
@@ -124,13 +169,19 @@
 //! // ...
 //! ```
 #![doc(html_root_url = "https://docs.rs/phf_codegen/0.10")]
+#![allow(clippy::new_without_default)]
 
-use phf_shared::{FmtConst, PhfHash};
-use std::collections::HashSet;
+#[cfg(feature = "caseless")]
+use phf_shared::UnicodeFolded;
+use phf_shared::{AsciiFolded, FmtConst, PhfBitIndex, PhfBorrow, PhfHash};
+pub use phf_shared::{HashFn, HashKey};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
 
-use phf_generator::HashState;
+use phf_generator::{BitSetState, CharTable, HashState, IntervalError, PackedDisps, TrieNode};
 
 struct Delegate<T>(T);
 
@@ -140,11 +191,146 @@ impl<T: FmtConst> fmt::Display for Delegate<T> {
     }
 }
 
+/// Returns the `phf::map::Disps` variant with the narrowest integer type
+/// which fits all of `disps`.
+fn disps_variant(disps: &[(u32, u32)]) -> &'static str {
+    let max = disps.iter().map(|&(d1, d2)| d1.max(d2)).max().unwrap_or(0);
+    if max <= u32::from(u8::MAX) {
+        "U8"
+    } else if max <= u32::from(u16::MAX) {
+        "U16"
+    } else {
+        "U32"
+    }
+}
+
+/// Returns the size in bytes of a displacement pair of the type picked by
+/// `disps_variant`.
+fn disp_size(disps: &[(u32, u32)]) -> usize {
+    match disps_variant(disps) {
+        "U8" => 2,
+        "U16" => 4,
+        _ => 8,
+    }
+}
+
+/// Returns the size of `usize` on the target being built for.
+///
+/// Build scripts run on the host, but Cargo tells them the target's pointer
+/// width.
+fn target_usize_size() -> usize {
+    std::env::var("CARGO_CFG_TARGET_POINTER_WIDTH")
+        .ok()
+        .and_then(|width| width.parse::<usize>().ok())
+        .map_or(std::mem::size_of::<usize>(), |width| width / 8)
+}
+
+/// Generates the hash of `keys` with the configured hasher and key, if any.
+fn generate_hash<H: PhfHash>(
+    keys: &[H],
+    hasher: Option<HashFn>,
+    key: Option<HashKey>,
+) -> HashState {
+    let hasher = hasher.unwrap_or_else(|| phf_generator::default_hash_fn(keys.len()));
+    match key {
+        Some(key) => phf_generator::generate_hash_seeded(keys, hasher, key),
+        None => phf_generator::generate_hash_with(keys, hasher),
+    }
+}
+
+/// What `build` does with an entry whose key was already added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Panic. This is the default.
+    Error,
+    /// Keep the value of the last entry, at the position of the first, so
+    /// that later entries override earlier ones.
+    Last,
+    /// Keep the first entry, printing a `cargo:warning` for each one ignored.
+    First,
+}
+
+/// Returns the indices of the entries to build, in the order they were
+/// added, applying `duplicates` to repeated keys.
+fn resolve_duplicates<K: Hash + Eq + FmtConst>(keys: &[K], duplicates: Duplicates) -> Vec<usize> {
+    let mut entries = Vec::with_capacity(keys.len());
+    let mut slots = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        match slots.get(key) {
+            None => {
+                slots.insert(key, entries.len());
+                entries.push(i);
+            }
+            Some(&slot) => match duplicates {
+                Duplicates::Error => panic!("duplicate key `{}`", Delegate(key)),
+                Duplicates::Last => entries[slot] = i,
+                Duplicates::First => println!(
+                    "cargo:warning=duplicate key `{}` is ignored, the first entry is kept",
+                    Delegate(key)
+                ),
+            },
+        }
+    }
+    entries
+}
+
+/// How the entries of a `phf::Map` or `phf::Set` are laid out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// A single array of `(key, value)` pairs. This is the default.
+    Pairs,
+    /// Parallel arrays of keys and values, so that probing the map only
+    /// touches key data. Useful when values are large.
+    Split,
+}
+
+/// The data structure generated by a [`Map`](Map) builder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// A single perfect hash table, printed as a `phf::Map`. This is the
+    /// default.
+    Chd,
+    /// A `phf::LengthMap`, which first dispatches on the length of the key
+    /// and then searches a small per-length `phf::Map`. Good for keyword
+    /// tables. Only available for `&str` keys.
+    LengthBucketed,
+    /// A `phf::Map` whose entries are sorted by hash and binary searched
+    /// rather than indexed by a perfect hash function. Lookups are slower,
+    /// but generating the map can't fail, see [`Map::fallback`].
+    SortedSlice,
+}
+
+/// The number of hash keys tried by a [`Map`](Map) with
+/// [`fallback`](Map::fallback) before sorting its entries by hash instead.
+pub const FALLBACK_ATTEMPTS: usize = 16;
+
 /// A builder for the `phf::Map` type.
 pub struct Map<K> {
     keys: Vec<K>,
     values: Vec<String>,
+    comments: Vec<String>,
     path: String,
+    layout: Layout,
+    // the alignment of padded `(key, value)` pairs, with `Map::align`
+    align: Option<usize>,
+    hasher: Option<HashFn>,
+    key: Option<HashKey>,
+    fingerprints: bool,
+    filter: bool,
+    duplicates: Duplicates,
+    // set along with `Strategy::LengthBucketed`, which needs the key lengths
+    key_len: Option<fn(&K) -> usize>,
+    // set with `Map::strict`, which needs the keys as strings
+    #[cfg(feature = "strict")]
+    strict: Option<fn(&K) -> &str>,
+    // the type of the values, if they are stored once in a shared static
+    shared_values: Option<String>,
+    sorted: bool,
+    fallback: bool,
+    definition_order: bool,
+    pack_disps: bool,
+    // the budget in bytes and the size of an entry, with `Map::size_budget`
+    size_budget: Option<(usize, usize)>,
 }
 
 impl<K: Hash + PhfHash + Eq + FmtConst> Map<K> {
@@ -160,167 +346,2464 @@ impl<K: Hash + PhfHash + Eq + FmtConst> Map<K> {
         fn noop_fix_for_27438() {}
         noop_fix_for_27438();
 
-        Map {
-            keys: vec![],
+        Map {
+            keys: vec![],
+            values: vec![],
+            comments: vec![],
+            path: String::from("::phf"),
+            layout: Layout::Pairs,
+            align: None,
+            hasher: None,
+            key: None,
+            fingerprints: false,
+            filter: false,
+            duplicates: Duplicates::Error,
+            key_len: None,
+            #[cfg(feature = "strict")]
+            strict: None,
+            shared_values: None,
+            sorted: false,
+            fallback: false,
+            definition_order: false,
+            pack_disps: false,
+            size_budget: None,
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut Map<K> {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Set the layout of the generated entries.
+    pub fn layout(&mut self, layout: Layout) -> &mut Map<K> {
+        self.layout = layout;
+        self
+    }
+
+    /// Pad each `(key, value)` pair to a multiple of `align` bytes, and align
+    /// the entries to the same boundary.
+    ///
+    /// If the padded size divides the cache line size, for instance 24 byte
+    /// pairs padded to 32, a probe never straddles two cache lines. This
+    /// trades space for lookup latency on large, hot tables. It only applies
+    /// to [`Layout::Pairs`], and `build` panics with [`Layout::Split`].
+    ///
+    /// ```
+    /// let mut map = phf_codegen::Map::new();
+    /// map.align(32).entry(1u64, "[0u8; 16]");
+    /// let code = map.build().to_string();
+    /// assert!(code.contains("::phf::map::AlignedSlots::A32(&["));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` isn't 16, 32, 64 or 128.
+    pub fn align(&mut self, align: usize) -> &mut Map<K> {
+        assert!(
+            [16, 32, 64, 128].contains(&align),
+            "unsupported alignment {}, expected 16, 32, 64 or 128",
+            align
+        );
+        self.align = Some(align);
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// By default this is picked by the size of the map, see
+    /// [`phf_generator::default_hash_fn`].
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut Map<K> {
+        self.hasher = Some(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// By default keys are derived from a fixed seed, so anyone can work out
+    /// the key of a map from its contents. A secret or per-crate key avoids
+    /// this, and can also be used to get a different table for the same
+    /// entries.
+    pub fn key(&mut self, key: HashKey) -> &mut Map<K> {
+        self.key = Some(key);
+        self
+    }
+
+    /// Store a 16-bit fingerprint of each key in the generated map.
+    ///
+    /// Lookups compare fingerprints before comparing keys, which speeds up
+    /// misses on maps with long keys at the cost of two bytes per entry.
+    pub fn fingerprints(&mut self, fingerprints: bool) -> &mut Map<K> {
+        self.fingerprints = fingerprints;
+        self
+    }
+
+    /// Generate a small Bloom filter of the keys alongside the map.
+    ///
+    /// Lookups check the filter before probing the map, which rejects most
+    /// missing keys after a single load. This costs at least two bytes per
+    /// entry, and is only worth it if most lookups miss. Maps small enough to
+    /// be searched linearly never get a filter.
+    pub fn filter(&mut self, filter: bool) -> &mut Map<K> {
+        self.filter = filter;
+        self
+    }
+
+    /// Sort the entries by hash if no perfect hash function is found within
+    /// [`FALLBACK_ATTEMPTS`] hash keys, as with [`Strategy::SortedSlice`].
+    ///
+    /// Some key sets have no perfect hash function with a given hash
+    /// function, and generating such a map would never finish. The map is
+    /// still a `phf::Map` either way.
+    pub fn fallback(&mut self, fallback: bool) -> &mut Map<K> {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Make the generated map iterate its entries in the order they were
+    /// added, at the cost of an extra index per entry.
+    ///
+    /// With [`Strategy::LengthBucketed`], each length keeps the order of its
+    /// own entries.
+    pub fn definition_order(&mut self, definition_order: bool) -> &mut Map<K> {
+        self.definition_order = definition_order;
+        self
+    }
+
+    /// Set what `build` does with an entry whose key was already added.
+    ///
+    /// By default it panics. Entries which are layered on top of each other
+    /// on purpose, such as defaults followed by overrides, can use
+    /// [`Duplicates::Last`] instead.
+    ///
+    /// ```
+    /// use phf_codegen::Duplicates;
+    ///
+    /// let mut map = phf_codegen::Map::new();
+    /// map.duplicates(Duplicates::Last)
+    ///     .entry("colour", "\"auto\"")
+    ///     .entry("jobs", "1")
+    ///     .entry("colour", "\"never\"");
+    /// let code = map.build().to_string();
+    /// assert!(code.contains("\"never\""));
+    /// assert!(!code.contains("\"auto\""));
+    /// ```
+    pub fn duplicates(&mut self, duplicates: Duplicates) -> &mut Map<K> {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Pack the displacements of the generated map as varints.
+    ///
+    /// Most displacements of a large map are small, so they take a byte or
+    /// two each instead of the width of the largest one, at the cost of
+    /// decoding up to 16 of them in each lookup. This is only worth it for
+    /// maps with many thousands of entries.
+    pub fn pack_disps(&mut self, pack_disps: bool) -> &mut Map<K> {
+        self.pack_disps = pack_disps;
+        self
+    }
+
+    /// Make `build` panic if the generated tables take more than `bytes` bytes
+    /// of static data, given entries of `entry_size` bytes.
+    ///
+    /// The size counts the entries and the arrays which index them, such as
+    /// the displacements and fingerprints, but not data the entries point to,
+    /// e.g. the text of `&'static str` keys or values shared with
+    /// [`share_values`](Map::share_values). `entry_size` is the size of a
+    /// `(K, V)` pair on the target, which the builder can't know. With
+    /// [`align`](Map::align), it is rounded up to the padded size.
+    ///
+    /// ```rust,should_panic
+    /// let mut map = phf_codegen::Map::new();
+    /// map.size_budget(64, 16);
+    /// for i in 0..10u32 {
+    ///     map.entry(i, "[0u8; 12]");
+    /// }
+    /// // panics with "generated table takes 164 bytes of static data, over its
+    /// // budget of 64 bytes"
+    /// map.build();
+    /// ```
+    pub fn size_budget(&mut self, bytes: usize, entry_size: usize) -> &mut Map<K> {
+        self.size_budget = Some((bytes, entry_size));
+        self
+    }
+
+    /// Store each distinct value once, in a static array of `ty`, and make the
+    /// entries refer to it.
+    ///
+    /// The generated map is then a `phf::Map<K, &'static ty>`. Values are
+    /// compared by their source text, so this shrinks maps whose values repeat
+    /// a few large expressions.
+    ///
+    /// ```rust
+    /// let mut map = phf_codegen::Map::new();
+    /// map.share_values("&'static str")
+    ///     .entry(1u32, "\"odd\"")
+    ///     .entry(2u32, "\"even\"")
+    ///     .entry(3u32, "\"odd\"");
+    /// let code = map.build().to_string();
+    /// assert_eq!(1, code.matches("\"odd\"").count());
+    /// assert!(code.contains("(3, &__PHF_VALUES[0])"));
+    /// ```
+    pub fn share_values(&mut self, ty: &str) -> &mut Map<K> {
+        self.shared_values = Some(ty.to_owned());
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: &str) -> &mut Map<K> {
+        self.keys.push(key);
+        self.values.push(value.to_owned());
+        self.comments.push(String::new());
+        self
+    }
+
+    /// Adds an entry whose value is the `phf::Map` built by `map`.
+    ///
+    /// The inner map is built right away, so it panics like
+    /// [`build`](Map::build) would. Its type is only given by the type of
+    /// the outer map, e.g. `phf::Map<&'static str, phf::Map<u32, u32>>`.
+    ///
+    /// ```rust
+    /// let mut inner = phf_codegen::Map::new();
+    /// inner.entry(1u32, "2");
+    /// let mut outer = phf_codegen::Map::new();
+    /// outer.entry_map("a", &inner);
+    /// let code = outer.build().to_string();
+    /// assert!(code.contains("(\"a\", ::phf::Map {"));
+    /// ```
+    pub fn entry_map<K2>(&mut self, key: K, map: &Map<K2>) -> &mut Map<K>
+    where
+        K2: Hash + PhfHash + Eq + FmtConst,
+    {
+        let value = map.build().to_string();
+        self.entry(key, &value)
+    }
+
+    /// Attaches a comment to the entry added last, e.g. to note where its
+    /// value comes from.
+    ///
+    /// The comment is written as `//` lines before the entry.
+    ///
+    /// ```rust
+    /// let mut map = phf_codegen::Map::new();
+    /// map.entry("nbsp", "'\\u{a0}'").comment("HTML 4.01, section 24.2");
+    /// let code = map.build().to_string();
+    /// assert!(code.contains("// HTML 4.01, section 24.2\n        (\"nbsp\", '\\u{a0}'),"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if no entry has been added.
+    pub fn comment(&mut self, comment: &str) -> &mut Map<K> {
+        *self.comments.last_mut().expect("no entry to comment on") = comment.to_owned();
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed `phf::Map`,
+    /// or `phf::LengthMap` with [`Strategy::LengthBucketed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys, unless allowed by
+    /// [`duplicates`](Map::duplicates), or keys which only differ by ASCII
+    /// case or Unicode normalization with [`strict`](Map::strict).
+    pub fn build(&self) -> DisplayMap<'_, K> {
+        self.check_align();
+        let entries = resolve_duplicates(&self.keys, self.duplicates);
+        #[cfg(feature = "strict")]
+        if let Some(as_str) = self.strict {
+            let keys = entries
+                .iter()
+                .map(|&i| as_str(&self.keys[i]))
+                .collect::<Vec<_>>();
+            if let Some(dup) = phf_generator::find_near_duplicate(&keys) {
+                panic!(
+                    "keys {:?} and {:?} differ only by {}",
+                    keys[dup.first],
+                    keys[dup.second],
+                    dup.reason()
+                );
+            }
+        }
+
+        let (shared_values, values) = match self.shared_values {
+            None => (None, self.values.iter().map(|v| Cow::from(&**v)).collect()),
+            Some(ref ty) => {
+                let mut distinct = vec![];
+                let mut indices = HashMap::new();
+                let values = self
+                    .values
+                    .iter()
+                    .map(|value| {
+                        let idx = *indices.entry(&**value).or_insert_with(|| {
+                            distinct.push(&**value);
+                            distinct.len() - 1
+                        });
+                        Cow::from(format!("&__PHF_VALUES[{}]", idx))
+                    })
+                    .collect::<Vec<_>>();
+                (Some((&**ty, distinct)), values)
+            }
+        };
+
+        let (min_len, tables) = match self.key_len {
+            None => (None, vec![self.build_table(entries, &values)]),
+            Some(key_len) => {
+                let lens = entries
+                    .iter()
+                    .map(|&i| key_len(&self.keys[i]))
+                    .collect::<Vec<_>>();
+                let min_len = lens.iter().cloned().min().unwrap_or(0);
+                let max_len = lens.iter().cloned().max().unwrap_or(0);
+                let buckets_len = if lens.is_empty() {
+                    0
+                } else {
+                    max_len - min_len + 1
+                };
+                let mut buckets = vec![vec![]; buckets_len];
+                for (i, len) in entries.into_iter().zip(lens) {
+                    buckets[len - min_len].push(i);
+                }
+                let tables = buckets
+                    .into_iter()
+                    .map(|entries| self.build_table(entries, &values))
+                    .collect();
+                (Some(min_len), tables)
+            }
+        };
+
+        if let Some((budget, entry_size)) = self.size_budget {
+            let entry_size = match self.align {
+                Some(align) => (entry_size + align - 1) & !(align - 1),
+                None => entry_size,
+            };
+            let size = tables
+                .iter()
+                .map(|table| table.size(entry_size))
+                .sum::<usize>();
+            if size > budget {
+                panic!(
+                    "generated table takes {} bytes of static data, over its budget of {} bytes",
+                    size, budget
+                );
+            }
+        }
+
+        DisplayMap {
+            path: &self.path,
+            layout: self.layout,
+            align: self.align,
+            min_len,
+            shared_values,
+            tables,
+        }
+    }
+
+    fn check_align(&self) {
+        if self.align.is_some() && self.layout == Layout::Split {
+            panic!("`align` pads `(key, value)` pairs and can't be combined with `Layout::Split`");
+        }
+    }
+
+    fn check_duplicates(&self) {
+        let mut set = HashSet::new();
+        for key in &self.keys {
+            if !set.insert(key) {
+                panic!("duplicate key `{}`", Delegate(key));
+            }
+        }
+    }
+
+    /// Generates a single hash table of the given entries.
+    fn build_table<'a>(&'a self, entries: Vec<usize>, values: &[Cow<'a, str>]) -> Table<'a, K> {
+        let keys = entries.iter().map(|&i| &self.keys[i]).collect::<Vec<_>>();
+        let values = entries.iter().map(|&i| values[i].clone()).collect();
+        let comments = entries.iter().map(|&i| &*self.comments[i]).collect();
+        let hash = if self.sorted {
+            None
+        } else if self.fallback {
+            let hasher = self
+                .hasher
+                .unwrap_or_else(|| phf_generator::default_hash_fn(keys.len()));
+            phf_generator::try_generate_hash(&keys, hasher, self.key, FALLBACK_ATTEMPTS)
+        } else {
+            Some(generate_hash(&keys, self.hasher, self.key))
+        };
+        let (state, sorted) = match hash {
+            Some(state) => (state, vec![]),
+            None => {
+                let hasher = self
+                    .hasher
+                    .unwrap_or_else(|| phf_generator::default_hash_fn(keys.len()));
+                let sorted = phf_generator::generate_sorted(&keys, hasher, self.key);
+                let state = HashState {
+                    key: sorted.key,
+                    hasher: sorted.hasher,
+                    disps: vec![],
+                    map: sorted.map,
+                };
+                (state, sorted.hashes)
+            }
+        };
+
+        // linearly searched maps never hash their keys, so fingerprints and
+        // filters are useless, and they are already in definition order
+        let searched_linearly = state.disps.is_empty() && sorted.is_empty();
+        let hashes = if (self.fingerprints || self.filter) && !searched_linearly {
+            state
+                .map
+                .iter()
+                .map(|&idx| phf_shared::hash_with(keys[idx], &state.key, state.hasher))
+                .collect()
+        } else {
+            vec![]
+        };
+        let fingerprints = if self.fingerprints {
+            hashes.iter().map(phf_shared::fingerprint).collect()
+        } else {
+            vec![]
+        };
+        let filter = if self.filter && !hashes.is_empty() {
+            phf_generator::generate_filter(&hashes)
+        } else {
+            vec![]
+        };
+
+        let packed = if self.pack_disps && !state.disps.is_empty() {
+            Some(phf_generator::pack_disps(&state.disps))
+        } else {
+            None
+        };
+
+        let mut order = vec![];
+        if self.definition_order && !searched_linearly {
+            order = vec![0; keys.len()];
+            for (slot, &idx) in state.map.iter().enumerate() {
+                order[idx] = slot;
+            }
+        }
+
+        Table {
+            state,
+            sorted,
+            keys,
+            values,
+            comments,
+            fingerprints,
+            filter,
+            order,
+            packed,
+        }
+    }
+}
+
+impl<K: Hash + PhfHash + PhfBorrow<str> + Eq + FmtConst> Map<K> {
+    /// Set the data structure to generate.
+    ///
+    /// Only maps with string keys support strategies other than
+    /// [`Strategy::Chd`].
+    pub fn strategy(&mut self, strategy: Strategy) -> &mut Map<K> {
+        self.key_len = match strategy {
+            Strategy::Chd | Strategy::SortedSlice => None,
+            Strategy::LengthBucketed => Some(|key: &K| key.borrow().len()),
+        };
+        self.sorted = strategy == Strategy::SortedSlice;
+        self
+    }
+
+    /// Make `build` panic if two keys only differ by ASCII case or Unicode
+    /// normalization, such as `"Foo"` and `"foo"`, which are usually mistakes
+    /// in the input.
+    ///
+    ///
+    /// Requires the `strict` feature.
+    ///
+    /// ```rust,should_panic
+    /// let mut map = phf_codegen::Map::new();
+    /// map.strict(true).entry("Foo", "1").entry("foo", "2");
+    /// // panics with "keys "Foo" and "foo" differ only by ASCII case"
+    /// map.build();
+    /// ```
+    #[cfg(feature = "strict")]
+    pub fn strict(&mut self, strict: bool) -> &mut Map<K> {
+        self.strict = if strict {
+            Some(|key: &K| key.borrow())
+        } else {
+            None
+        };
+        self
+    }
+}
+
+/// A single hash table of a [`DisplayMap`](DisplayMap).
+struct Table<'a, K> {
+    state: HashState,
+    // the sorted hashes of the entries with `Strategy::SortedSlice`, in which
+    // case `state.disps` is empty
+    sorted: Vec<u64>,
+    keys: Vec<&'a K>,
+    values: Vec<Cow<'a, str>>,
+    comments: Vec<&'a str>,
+    fingerprints: Vec<u16>,
+    filter: Vec<u64>,
+    // the slot of each entry in definition order, with `Map::definition_order`
+    order: Vec<usize>,
+    // `state.disps` packed as varints, with `Map::pack_disps`
+    packed: Option<PackedDisps>,
+}
+
+impl<'a, K> Table<'a, K> {
+    /// Returns the size in bytes of the table's arrays, with entries of
+    /// `entry_size` bytes.
+    fn size(&self, entry_size: usize) -> usize {
+        let disps = match &self.packed {
+            Some(packed) => packed.offsets.len() * 4 + packed.bytes.len(),
+            None => self.state.disps.len() * disp_size(&self.state.disps),
+        };
+        self.keys.len() * entry_size
+            + disps
+            + self.sorted.len() * 8
+            + self.fingerprints.len() * 2
+            + self.filter.len() * 8
+            + self.order.len() * target_usize_size()
+    }
+}
+
+/// An adapter for printing a [`Map`](Map).
+pub struct DisplayMap<'a, K> {
+    path: &'a str,
+    layout: Layout,
+    align: Option<usize>,
+    // the length of the shortest key with `Strategy::LengthBucketed`
+    min_len: Option<usize>,
+    // the type and the distinct values with `Map::share_values`
+    shared_values: Option<(&'a str, Vec<&'a str>)>,
+    tables: Vec<Table<'a, K>>,
+}
+
+impl<'a, K: FmtConst + 'a> DisplayMap<'a, K> {
+    fn fmt_table(&self, f: &mut fmt::Formatter<'_>, table: &Table<'a, K>) -> fmt::Result {
+        self.fmt_disps(f, table)?;
+        self.fmt_entries(f, table)?;
+        self.fmt_side(f, table)
+    }
+
+    /// Writes the fields of a `phf::Map` up to its entries.
+    fn fmt_disps(&self, f: &mut fmt::Formatter<'_>, table: &Table<'a, K>) -> fmt::Result {
+        // funky formatting here for nice output
+        write!(
+            f,
+            "{}::Map {{
+    key: {:?},
+    hasher: {}::HashFn::{:?},
+    disps: {}::map::Disps::",
+            self.path, table.state.key, self.path, table.state.hasher, self.path,
+        )?;
+
+        match &table.packed {
+            Some(packed) => {
+                write!(
+                    f,
+                    "Packed(&{}::map::PackedDisps {{
+        buckets: {},
+        offsets: &[",
+                    self.path,
+                    table.state.disps.len()
+                )?;
+                for offset in &packed.offsets {
+                    write!(f, "{}, ", offset)?;
+                }
+                write!(f, "],\n        bytes: &[")?;
+                for (i, byte) in packed.bytes.iter().enumerate() {
+                    if i % 16 == 0 {
+                        write!(f, "\n           ")?;
+                    }
+                    write!(f, " {:#04x},", byte)?;
+                }
+                write!(f, "\n        ],\n    }}),")?;
+            }
+            None => {
+                let variant = if table.sorted.is_empty() {
+                    disps_variant(&table.state.disps)
+                } else {
+                    "Sorted"
+                };
+                write!(f, "{}(&[", variant)?;
+
+                // write map displacements
+                for &(d1, d2) in &table.state.disps {
+                    write!(
+                        f,
+                        "
+        ({}, {}),",
+                        d1, d2
+                    )?;
+                }
+
+                for hash in &table.sorted {
+                    write!(f, "\n        {:#x},", hash)?;
+                }
+
+                write!(f, "\n    ]),")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_entries(&self, f: &mut fmt::Formatter<'_>, table: &Table<'a, K>) -> fmt::Result {
+        match (self.layout, self.align) {
+            (Layout::Pairs, Some(align)) => {
+                write!(
+                    f,
+                    "
+    entries: {}::map::Slots::Aligned({}::map::AlignedSlots::A{}(&[",
+                    self.path, self.path, align
+                )?;
+
+                for &idx in &table.state.map {
+                    write_comment(f, table.comments[idx])?;
+                    write!(
+                        f,
+                        "
+        {}::map::Aligned {{ align: [], value: ({}, {}) }},",
+                        self.path,
+                        Delegate(table.keys[idx]),
+                        table.values[idx]
+                    )?;
+                }
+
+                write!(f, "\n    ]))")?;
+            }
+            (Layout::Pairs, None) => {
+                write!(
+                    f,
+                    "
+    entries: {}::map::Slots::Pairs(&[",
+                    self.path
+                )?;
+
+                // write map entries
+                for &idx in &table.state.map {
+                    write_comment(f, table.comments[idx])?;
+                    write!(
+                        f,
+                        "
+        ({}, {}),",
+                        Delegate(table.keys[idx]),
+                        table.values[idx]
+                    )?;
+                }
+
+                write!(f, "\n    ])")?;
+            }
+            (Layout::Split, _) => {
+                write!(
+                    f,
+                    "
+    entries: {}::map::Slots::Split(&[",
+                    self.path
+                )?;
+
+                // write map keys, then values in the same order
+                for &idx in &table.state.map {
+                    write_comment(f, table.comments[idx])?;
+                    write!(
+                        f,
+                        "
+        {},",
+                        Delegate(table.keys[idx])
+                    )?;
+                }
+
+                write!(f, "\n    ], &[")?;
+
+                for &idx in &table.state.map {
+                    write!(
+                        f,
+                        "
+        {},",
+                        table.values[idx]
+                    )?;
+                }
+
+                write!(f, "\n    ])")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the fields of a `phf::Map` after its entries.
+    fn fmt_side(&self, f: &mut fmt::Formatter<'_>, table: &Table<'a, K>) -> fmt::Result {
+        if table.fingerprints.is_empty() && table.filter.is_empty() && table.order.is_empty() {
+            return write!(f, ",\n    side: None,\n}}");
+        }
+
+        write!(
+            f,
+            ",\n    side: Some(&{}::map::SideTables {{\n        fingerprints: &[",
+            self.path
+        )?;
+
+        for fingerprint in &table.fingerprints {
+            write!(f, "{}, ", fingerprint)?;
+        }
+
+        write!(f, "],\n        filter: &[")?;
+
+        for word in &table.filter {
+            write!(f, "{:#x}, ", word)?;
+        }
+
+        write!(f, "],\n        order: &[")?;
+
+        for slot in &table.order {
+            write!(f, "{}, ", slot)?;
+        }
+
+        write!(f, "],\n    }}),\n}}")
+    }
+
+    fn fmt_tables(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let min_len = match self.min_len {
+            Some(min_len) => min_len,
+            None => return self.fmt_table(f, &self.tables[0]),
+        };
+
+        let len = self
+            .tables
+            .iter()
+            .map(|table| table.keys.len())
+            .sum::<usize>();
+        write!(
+            f,
+            "{}::LengthMap {{
+min_len: {},
+len: {},
+maps: &[
+",
+            self.path, min_len, len
+        )?;
+        for table in &self.tables {
+            self.fmt_table(f, table)?;
+            writeln!(f, ",")?;
+        }
+        write!(f, "],\n}}")
+    }
+}
+
+impl<'a, K: FmtConst + 'a> DisplayMap<'a, K> {
+    /// Returns adapters printing the entries of the map as slices of at most
+    /// `chunk_len` entries each.
+    ///
+    /// Each chunk can be written to its own file, so that rustc doesn't have
+    /// to parse one huge expression, and the map is then printed with
+    /// [`chunked_root`](DisplayMap::chunked_root).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_len` is zero, or if the map uses
+    /// `Strategy::LengthBucketed`, `Layout::Split` or [`Map::align`], whose
+    /// entries can't be split into chunks.
+    pub fn chunks(&self, chunk_len: usize) -> Vec<DisplayChunk<'_, 'a, K>> {
+        let table = self.chunked_table(chunk_len);
+        table
+            .state
+            .map
+            .chunks(chunk_len)
+            .map(|slots| DisplayChunk { table, slots })
+            .collect()
+    }
+
+    /// Returns an adapter printing the `phf::Map` with its entries gathered
+    /// from the chunks written from [`chunks`](DisplayMap::chunks).
+    ///
+    /// `chunks` holds an expression for every chunk, such as an `include!` of
+    /// the file it was written to.
+    ///
+    /// ```rust
+    /// let mut map = phf_codegen::Map::new();
+    /// for i in 0..10u32 {
+    ///     map.entry(i, &i.to_string());
+    /// }
+    /// let map = map.build();
+    /// let paths = (0..map.chunks(4).len())
+    ///     .map(|i| format!("include!(concat!(env!(\"OUT_DIR\"), \"/chunk_{}.rs\"))", i))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(3, paths.len());
+    /// let code = map.chunked_root(4, &paths).to_string();
+    /// assert!(code.contains("/chunk_2.rs"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of expressions doesn't match the number of
+    /// chunks, or in the same cases as [`chunks`](DisplayMap::chunks).
+    pub fn chunked_root<'b, S: AsRef<str>>(
+        &'b self,
+        chunk_len: usize,
+        chunks: &'b [S],
+    ) -> DisplayChunkedRoot<'b, 'a, K, S> {
+        let table = self.chunked_table(chunk_len);
+        assert_eq!(
+            chunks.len(),
+            table.state.map.chunks(chunk_len).len(),
+            "expected an expression for every chunk"
+        );
+        DisplayChunkedRoot {
+            map: self,
+            chunk_len,
+            chunks,
+        }
+    }
+
+    fn chunked_table(&self, chunk_len: usize) -> &Table<'a, K> {
+        assert!(chunk_len > 0, "chunks need at least one entry");
+        assert!(
+            self.min_len.is_none() && self.layout == Layout::Pairs && self.align.is_none(),
+            "only maps of unaligned pairs can be split into chunks"
+        );
+        &self.tables[0]
+    }
+
+    /// Writes `tables`, inside a block declaring the values shared by the
+    /// entries, if any.
+    fn fmt_values(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        tables: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        let (ty, values) = match self.shared_values {
+            Some((ty, ref values)) => (ty, values),
+            None => return tables(f),
+        };
+
+        // the entries refer to the values by index, so the map has to be
+        // written inside a block which declares them
+        write!(f, "{{\nstatic __PHF_VALUES: [{}; {}] = [", ty, values.len())?;
+        for value in values {
+            write!(f, "\n    {},", value)?;
+        }
+        write!(f, "\n];\n")?;
+        tables(f)?;
+        write!(f, "\n}}")
+    }
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_values(f, |f| self.fmt_tables(f))
+    }
+}
+
+/// An adapter for printing a chunk of the entries of a
+/// [`DisplayMap`](DisplayMap), see [`DisplayMap::chunks`].
+pub struct DisplayChunk<'b, 'a, K> {
+    table: &'b Table<'a, K>,
+    slots: &'b [usize],
+}
+
+impl<'b, 'a, K: FmtConst + 'a> fmt::Display for DisplayChunk<'b, 'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "&[")?;
+        for &idx in self.slots {
+            write_comment(f, self.table.comments[idx])?;
+            write!(
+                f,
+                "
+        ({}, {}),",
+                Delegate(self.table.keys[idx]),
+                self.table.values[idx]
+            )?;
+        }
+        write!(f, "\n]")
+    }
+}
+
+/// An adapter for printing a [`DisplayMap`](DisplayMap) whose entries are
+/// written in chunks, see [`DisplayMap::chunked_root`].
+pub struct DisplayChunkedRoot<'b, 'a, K, S> {
+    map: &'b DisplayMap<'a, K>,
+    chunk_len: usize,
+    chunks: &'b [S],
+}
+
+impl<'b, 'a, K: FmtConst + 'a, S: AsRef<str>> fmt::Display for DisplayChunkedRoot<'b, 'a, K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let map = self.map;
+        let table = &map.tables[0];
+        map.fmt_values(f, |f| {
+            map.fmt_disps(f, table)?;
+            write!(
+                f,
+                "
+    entries: {}::map::Slots::Chunked({}::map::ChunkedSlots {{
+        len: {},
+        chunk_len: {},
+        chunks: &[",
+                map.path,
+                map.path,
+                table.keys.len(),
+                self.chunk_len
+            )?;
+            for chunk in self.chunks {
+                write!(f, "\n            {},", chunk.as_ref())?;
+            }
+            write!(f, "\n        ],\n    }})")?;
+            map.fmt_side(f, table)
+        })
+    }
+}
+
+/// Writes each line of `comment` as a `//` comment on its own line, indented
+/// like the entries.
+fn write_comment(f: &mut fmt::Formatter<'_>, comment: &str) -> fmt::Result {
+    for line in comment.lines() {
+        if line.is_empty() {
+            write!(f, "\n        //")?;
+        } else {
+            write!(f, "\n        // {}", line)?;
+        }
+    }
+    Ok(())
+}
+
+/// A builder for the `phf::ArrayMap` type.
+pub struct ArrayMap<K> {
+    keys: Vec<K>,
+    values: Vec<String>,
+    path: String,
+    hasher: Option<HashFn>,
+    key: Option<HashKey>,
+}
+
+impl<K: Hash + PhfHash + Eq + FmtConst> ArrayMap<K> {
+    /// Constructs a new `phf::ArrayMap` builder.
+    pub fn new() -> ArrayMap<K> {
+        ArrayMap {
+            keys: vec![],
+            values: vec![],
+            path: String::from("::phf"),
+            hasher: None,
+            key: None,
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut ArrayMap<K> {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// By default this is picked by the size of the map, see
+    /// [`phf_generator::default_hash_fn`].
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut ArrayMap<K> {
+        self.hasher = Some(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut ArrayMap<K> {
+        self.key = Some(key);
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: &str) -> &mut ArrayMap<K> {
+        self.keys.push(key);
+        self.values.push(value.to_owned());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::ArrayMap`.
+    ///
+    /// The type of the generated map is `phf::ArrayMap<K, V, N>`, where `N`
+    /// is the number of entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplayArrayMap<'_, K> {
+        let mut set = HashSet::new();
+        for key in &self.keys {
+            if !set.insert(key) {
+                panic!("duplicate key `{}`", Delegate(key));
+            }
+        }
+
+        DisplayArrayMap {
+            path: &self.path,
+            state: generate_hash(&self.keys, self.hasher, self.key),
+            keys: &self.keys,
+            values: &self.values,
+        }
+    }
+}
+
+/// An adapter for printing a [`ArrayMap`](ArrayMap).
+pub struct DisplayArrayMap<'a, K> {
+    path: &'a str,
+    state: HashState,
+    keys: &'a [K],
+    values: &'a [String],
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayArrayMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::ArrayMap {{
+    key: {:?},
+    hasher: {}::HashFn::{:?},
+    buckets: {},
+    disps: [",
+            self.path,
+            self.state.key,
+            self.path,
+            self.state.hasher,
+            self.state.disps.len(),
+        )?;
+        // the displacements are padded to the length of the entries
+        for i in 0..self.keys.len() {
+            let (d1, d2) = self.state.disps.get(i).copied().unwrap_or((0, 0));
+            write!(
+                f,
+                "
+        ({}, {}),",
+                d1, d2
+            )?;
+        }
+        write!(
+            f,
+            "
+    ],
+    entries: [",
+        )?;
+        for &idx in &self.state.map {
+            write!(
+                f,
+                "
+        ({}, {}),",
+                Delegate(&self.keys[idx]),
+                &self.values[idx]
+            )?;
+        }
+        write!(
+            f,
+            "
+    ],
+}}"
+        )
+    }
+}
+
+/// A builder for the `phf::Set` type.
+pub struct Set<T> {
+    map: Map<T>,
+}
+
+impl<T: Hash + PhfHash + Eq + FmtConst> Set<T> {
+    /// Constructs a new `phf::Set` builder.
+    pub fn new() -> Set<T> {
+        Set { map: Map::new() }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut Set<T> {
+        self.map.phf_path(path);
+        self
+    }
+
+    /// Set the layout of the generated entries.
+    pub fn layout(&mut self, layout: Layout) -> &mut Set<T> {
+        self.map.layout(layout);
+        self
+    }
+
+    /// Pad each entry to a multiple of `align` bytes, see [`Map::align`].
+    pub fn align(&mut self, align: usize) -> &mut Set<T> {
+        self.map.align(align);
+        self
+    }
+
+    /// Set the hash function used by the generated set.
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut Set<T> {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated set from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut Set<T> {
+        self.map.key(key);
+        self
+    }
+
+    /// Store a 16-bit fingerprint of each entry in the generated set.
+    ///
+    /// See [`Map::fingerprints`](Map::fingerprints).
+    pub fn fingerprints(&mut self, fingerprints: bool) -> &mut Set<T> {
+        self.map.fingerprints(fingerprints);
+        self
+    }
+
+    /// Generate a small Bloom filter of the entries alongside the set.
+    ///
+    /// See [`Map::filter`](Map::filter).
+    pub fn filter(&mut self, filter: bool) -> &mut Set<T> {
+        self.map.filter(filter);
+        self
+    }
+
+    /// Sort the entries by hash if no perfect hash function is found.
+    ///
+    /// See [`Map::fallback`](Map::fallback).
+    pub fn fallback(&mut self, fallback: bool) -> &mut Set<T> {
+        self.map.fallback(fallback);
+        self
+    }
+
+    /// Pack the displacements of the generated set as varints.
+    ///
+    /// See [`Map::pack_disps`](Map::pack_disps).
+    pub fn pack_disps(&mut self, pack_disps: bool) -> &mut Set<T> {
+        self.map.pack_disps(pack_disps);
+        self
+    }
+
+    /// Make `build` panic if the generated set takes more than `bytes` bytes
+    /// of static data, given elements of `entry_size` bytes.
+    ///
+    /// See [`Map::size_budget`](Map::size_budget).
+    pub fn size_budget(&mut self, bytes: usize, entry_size: usize) -> &mut Set<T> {
+        self.map.size_budget(bytes, entry_size);
+        self
+    }
+
+    /// Make the generated set iterate its entries in the order they were
+    /// added.
+    ///
+    /// See [`Map::definition_order`](Map::definition_order).
+    pub fn definition_order(&mut self, definition_order: bool) -> &mut Set<T> {
+        self.map.definition_order(definition_order);
+        self
+    }
+
+    /// Set what `build` does with an entry which was already added.
+    ///
+    /// See [`Map::duplicates`](Map::duplicates).
+    pub fn duplicates(&mut self, duplicates: Duplicates) -> &mut Set<T> {
+        self.map.duplicates(duplicates);
+        self
+    }
+
+    /// Adds an entry to the builder.
+    pub fn entry(&mut self, entry: T) -> &mut Set<T> {
+        self.map.entry(entry, "()");
+        self
+    }
+
+    /// Attaches a comment to the entry added last.
+    ///
+    /// See [`Map::comment`](Map::comment).
+    pub fn comment(&mut self, comment: &str) -> &mut Set<T> {
+        self.map.comment(comment);
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed `phf::Set`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplaySet<'_, T> {
+        DisplaySet {
+            inner: self.map.build(),
+        }
+    }
+}
+
+impl<T: Hash + PhfHash + PhfBorrow<str> + Eq + FmtConst> Set<T> {
+    /// Make `build` panic if two entries only differ by ASCII case or Unicode
+    /// normalization.
+    ///
+    /// See [`Map::strict`](Map::strict). Requires the `strict` feature.
+    #[cfg(feature = "strict")]
+    pub fn strict(&mut self, strict: bool) -> &mut Set<T> {
+        self.map.strict(strict);
+        self
+    }
+}
+
+/// An adapter for printing a [`Set`](Set).
+pub struct DisplaySet<'a, T> {
+    inner: DisplayMap<'a, T>,
+}
+
+impl<'a, T: FmtConst + 'a> fmt::Display for DisplaySet<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::Set {{ map: {} }}", self.inner.path, self.inner)
+    }
+}
+
+/// A builder for the `phf::OrderedMap` type.
+pub struct OrderedMap<K> {
+    keys: Vec<K>,
+    values: Vec<String>,
+    comments: Vec<String>,
+    path: String,
+    hasher: Option<HashFn>,
+    key: Option<HashKey>,
+    slot_order: bool,
+    duplicates: Duplicates,
+}
+
+impl<K: Hash + PhfHash + Eq + FmtConst> OrderedMap<K> {
+    /// Constructs a enw `phf::OrderedMap` builder.
+    pub fn new() -> OrderedMap<K> {
+        OrderedMap {
+            keys: vec![],
+            values: vec![],
+            comments: vec![],
+            path: String::from("::phf"),
+            hasher: None,
+            key: None,
+            slot_order: false,
+            duplicates: Duplicates::Error,
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut OrderedMap<K> {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// By default this is picked by the size of the map, see
+    /// [`phf_generator::default_hash_fn`].
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut OrderedMap<K> {
+        self.hasher = Some(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut OrderedMap<K> {
+        self.key = Some(key);
+        self
+    }
+
+    /// Store the entries in the order they are probed rather than the order
+    /// they were defined in.
+    ///
+    /// This improves the cache behavior of lookups in large maps, at the cost
+    /// of an extra index per entry to iterate in definition order.
+    pub fn slot_order(&mut self, slot_order: bool) -> &mut OrderedMap<K> {
+        self.slot_order = slot_order;
+        self
+    }
+
+    /// Set what `build` does with an entry whose key was already added.
+    ///
+    /// See [`Map::duplicates`](Map::duplicates). With [`Duplicates::Last`],
+    /// an overridden key keeps the position of its first entry.
+    pub fn duplicates(&mut self, duplicates: Duplicates) -> &mut OrderedMap<K> {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: &str) -> &mut OrderedMap<K> {
+        self.keys.push(key);
+        self.values.push(value.to_owned());
+        self.comments.push(String::new());
+        self
+    }
+
+    /// Attaches a comment to the entry added last.
+    ///
+    /// See [`Map::comment`](Map::comment).
+    ///
+    /// # Panics
+    ///
+    /// Panics if no entry has been added.
+    pub fn comment(&mut self, comment: &str) -> &mut OrderedMap<K> {
+        *self.comments.last_mut().expect("no entry to comment on") = comment.to_owned();
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::OrderedMap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys, unless allowed by
+    /// [`duplicates`](OrderedMap::duplicates).
+    pub fn build(&self) -> DisplayOrderedMap<'_, K> {
+        let entries = resolve_duplicates(&self.keys, self.duplicates);
+        let keys = entries.iter().map(|&i| &self.keys[i]).collect::<Vec<_>>();
+
+        let state = generate_hash(&keys, self.hasher, self.key);
+
+        // linearly searched maps are never probed, so keep them in definition order
+        let slot_order = self.slot_order && !state.disps.is_empty();
+
+        DisplayOrderedMap {
+            path: &self.path,
+            state,
+            keys,
+            values: entries.iter().map(|&i| &*self.values[i]).collect(),
+            comments: entries.iter().map(|&i| &*self.comments[i]).collect(),
+            slot_order,
+        }
+    }
+}
+
+/// An adapter for printing a [`OrderedMap`](OrderedMap).
+pub struct DisplayOrderedMap<'a, K> {
+    path: &'a str,
+    state: HashState,
+    keys: Vec<&'a K>,
+    values: Vec<&'a str>,
+    comments: Vec<&'a str>,
+    slot_order: bool,
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::OrderedMap {{
+    key: {:?},
+    hasher: {}::HashFn::{:?},
+    disps: {}::map::Disps::{}(&[",
+            self.path,
+            self.state.key,
+            self.path,
+            self.state.hasher,
+            self.path,
+            disps_variant(&self.state.disps)
+        )?;
+        for &(d1, d2) in &self.state.disps {
+            write!(
+                f,
+                "
+        ({}, {}),",
+                d1, d2
+            )?;
+        }
+        write!(
+            f,
+            "
+    ]),
+    idxs: &[",
+        )?;
+        for &idx in &self.state.map {
+            write!(
+                f,
+                "
+        {},",
+                idx
+            )?;
+        }
+        write!(
+            f,
+            "
+    ],
+    entries: &[",
+        )?;
+        let entries: Vec<usize> = if self.slot_order {
+            self.state.map.clone()
+        } else {
+            (0..self.keys.len()).collect()
+        };
+        for idx in entries {
+            write_comment(f, self.comments[idx])?;
+            write!(
+                f,
+                "
+        ({}, {}),",
+                Delegate(self.keys[idx]),
+                self.values[idx]
+            )?;
+        }
+        write!(f, "\n    ],\n    order: &[")?;
+        if self.slot_order {
+            let mut order = vec![0; self.keys.len()];
+            for (slot, &idx) in self.state.map.iter().enumerate() {
+                order[idx] = slot;
+            }
+            for slot in order {
+                write!(f, "{}, ", slot)?;
+            }
+        }
+        write!(f, "],\n}}")
+    }
+}
+
+/// A builder for the `phf::OrderedSet` type.
+pub struct OrderedSet<T> {
+    map: OrderedMap<T>,
+}
+
+impl<T: Hash + PhfHash + Eq + FmtConst> OrderedSet<T> {
+    /// Constructs a new `phf::OrderedSet` builder.
+    pub fn new() -> OrderedSet<T> {
+        OrderedSet {
+            map: OrderedMap::new(),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut OrderedSet<T> {
+        self.map.phf_path(path);
+        self
+    }
+
+    /// Set the hash function used by the generated set.
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut OrderedSet<T> {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated set from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut OrderedSet<T> {
+        self.map.key(key);
+        self
+    }
+
+    /// Store the entries in the order they are probed.
+    ///
+    /// See [`OrderedMap::slot_order`](OrderedMap::slot_order).
+    pub fn slot_order(&mut self, slot_order: bool) -> &mut OrderedSet<T> {
+        self.map.slot_order(slot_order);
+        self
+    }
+
+    /// Set what `build` does with an entry which was already added.
+    ///
+    /// See [`Map::duplicates`](Map::duplicates).
+    pub fn duplicates(&mut self, duplicates: Duplicates) -> &mut OrderedSet<T> {
+        self.map.duplicates(duplicates);
+        self
+    }
+
+    /// Adds an entry to the builder.
+    pub fn entry(&mut self, entry: T) -> &mut OrderedSet<T> {
+        self.map.entry(entry, "()");
+        self
+    }
+
+    /// Attaches a comment to the entry added last.
+    ///
+    /// See [`Map::comment`](Map::comment).
+    pub fn comment(&mut self, comment: &str) -> &mut OrderedSet<T> {
+        self.map.comment(comment);
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::OrderedSet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplayOrderedSet<'_, T> {
+        DisplayOrderedSet {
+            inner: self.map.build(),
+        }
+    }
+}
+
+/// An adapter for printing a [`OrderedSet`](OrderedSet).
+pub struct DisplayOrderedSet<'a, T> {
+    inner: DisplayOrderedMap<'a, T>,
+}
+
+impl<'a, T: FmtConst + 'a> fmt::Display for DisplayOrderedSet<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::OrderedSet {{ map: {} }}",
+            self.inner.path, self.inner
+        )
+    }
+}
+
+/// A builder for the `phf::Interner` type.
+pub struct Interner {
+    set: OrderedSet<String>,
+}
+
+impl Interner {
+    /// Constructs a new `phf::Interner` builder.
+    pub fn new() -> Interner {
+        Interner {
+            set: OrderedSet::new(),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut Interner {
+        self.set.phf_path(path);
+        self
+    }
+
+    /// Set the hash function used by the generated interner.
+    ///
+    /// See [`Map::hasher`](Map::hasher).
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut Interner {
+        self.set.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated interner from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut Interner {
+        self.set.key(key);
+        self
+    }
+
+    /// Store the strings in the order they are probed.
+    ///
+    /// See [`OrderedMap::slot_order`](OrderedMap::slot_order).
+    pub fn slot_order(&mut self, slot_order: bool) -> &mut Interner {
+        self.set.slot_order(slot_order);
+        self
+    }
+
+    /// Adds a string to the builder. Its symbol is the number of strings
+    /// added before it.
+    pub fn entry(&mut self, s: &str) -> &mut Interner {
+        self.set.entry(s.to_owned());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::Interner`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate strings.
+    pub fn build(&self) -> DisplayInterner<'_> {
+        DisplayInterner {
+            inner: self.set.build(),
+        }
+    }
+}
+
+/// An adapter for printing an [`Interner`](Interner).
+pub struct DisplayInterner<'a> {
+    inner: DisplayOrderedSet<'a, String>,
+}
+
+impl<'a> fmt::Display for DisplayInterner<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::Interner {{ set: {} }}",
+            self.inner.inner.path, self.inner
+        )
+    }
+}
+
+/// A builder for an enum of string keywords along with a `phf::Map` from the
+/// strings to the variants.
+///
+/// This keeps the enum and the map in sync, like the `phf_enum!` macro.
+///
+/// ```rust
+/// let mut keywords = phf_codegen::Enum::new("Keyword", "KEYWORDS");
+/// keywords.entry("loop").entry("continue").variant("Fn", "fn").as_str(true);
+/// let code = keywords.build().to_string();
+/// assert!(code.contains("pub enum Keyword {"));
+/// assert!(code.contains("pub static KEYWORDS: ::phf::Map<&'static str, Keyword> = "));
+/// ```
+pub struct Enum {
+    name: String,
+    map_name: String,
+    variants: Vec<(String, String)>,
+    map: Map<String>,
+    as_str: bool,
+}
+
+impl Enum {
+    /// Constructs a new builder for an enum called `name` and a map called
+    /// `map_name`.
+    pub fn new(name: &str, map_name: &str) -> Enum {
+        Enum {
+            name: name.to_owned(),
+            map_name: map_name.to_owned(),
+            variants: vec![],
+            map: Map::new(),
+            as_str: false,
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut Enum {
+        self.map.phf_path(path);
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// See [`Map::hasher`](Map::hasher).
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut Enum {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut Enum {
+        self.map.key(key);
+        self
+    }
+
+    /// Also generate a `const fn as_str(&self) -> &'static str` returning the
+    /// string of each variant.
+    pub fn as_str(&mut self, as_str: bool) -> &mut Enum {
+        self.as_str = as_str;
+        self
+    }
+
+    /// Adds a string, with a variant named after it in camel case, e.g.
+    /// `FooBar` for `foo_bar` or `foo-bar`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string has no letters or digits, or starts with a digit.
+    pub fn entry(&mut self, s: &str) -> &mut Enum {
+        let ident = camel_case(s);
+        if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            panic!("can't name a variant after `{}`", s);
+        }
+        self.variant(&ident, s)
+    }
+
+    /// Adds a string with a variant called `ident`.
+    pub fn variant(&mut self, ident: &str, s: &str) -> &mut Enum {
+        self.map
+            .entry(s.to_owned(), &format!("{}::{}", self.name, ident));
+        self.variants.push((ident.to_owned(), s.to_owned()));
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the enum, the map,
+    /// and the `as_str` method if enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate strings or variants.
+    pub fn build(&self) -> DisplayEnum<'_> {
+        let mut set = HashSet::new();
+        for (ident, _) in &self.variants {
+            if !set.insert(ident) {
+                panic!("duplicate variant `{}`", ident);
+            }
+        }
+
+        DisplayEnum {
+            def: self,
+            map: self.map.build(),
+        }
+    }
+}
+
+/// Converts `s` to a camel case identifier, dropping characters other than
+/// ASCII letters and digits.
+fn camel_case(s: &str) -> String {
+    let mut ident = String::new();
+    for word in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            ident.push(first.to_ascii_uppercase());
+            ident.extend(chars);
+        }
+    }
+    ident
+}
+
+/// An adapter for printing an [`Enum`](Enum).
+pub struct DisplayEnum<'a> {
+    def: &'a Enum,
+    map: DisplayMap<'a, String>,
+}
+
+impl<'a> fmt::Display for DisplayEnum<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &self.def.name;
+        write!(
+            f,
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\npub enum {} {{",
+            name
+        )?;
+        for (ident, _) in &self.def.variants {
+            write!(f, "\n    {},", ident)?;
+        }
+        write!(
+            f,
+            "\n}}\n\npub static {}: {}::Map<&'static str, {}> = \n{};\n",
+            self.def.map_name, self.map.path, name, self.map
+        )?;
+        if self.def.as_str {
+            write!(
+                f,
+                "
+impl {} {{
+    /// Returns the string of the variant.
+    pub const fn as_str(&self) -> &'static str {{
+        match self {{",
+                name
+            )?;
+            for (ident, s) in &self.def.variants {
+                write!(f, "\n            {}::{} => {:?},", name, ident, s)?;
+            }
+            write!(f, "\n        }}\n    }}\n}}\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// A builder for a C-callable function which looks up a key in a generated
+/// `phf::Map` with string or byte string keys, along with its C declaration.
+///
+/// The function returns a pointer to the value of the key, or a null pointer
+/// if the key isn't in the map. The values should have a C-compatible type,
+/// e.g. a `#[repr(C)]` struct.
+///
+/// ```rust
+/// let mut lookup = phf_codegen::CLookup::new("KEYWORDS", "Keyword");
+/// lookup.name("keyword_lookup").c_value_type("struct keyword");
+/// let code = lookup.build().to_string();
+/// assert!(code.contains("pub unsafe extern \"C\" fn keyword_lookup("));
+/// assert!(lookup
+///     .header()
+///     .contains("const struct keyword *keyword_lookup(const char *key_ptr, size_t key_len);"));
+/// ```
+pub struct CLookup {
+    map: String,
+    value_type: String,
+    name: String,
+    c_value_type: String,
+    bytes: bool,
+}
+
+impl CLookup {
+    /// Constructs a new builder for a function which looks up keys in the
+    /// map called `map`, whose values have the type `value_type`.
+    pub fn new(map: &str, value_type: &str) -> CLookup {
+        CLookup {
+            map: map.to_owned(),
+            value_type: value_type.to_owned(),
+            name: String::from("phf_lookup"),
+            c_value_type: String::from("void"),
+            bytes: false,
+        }
+    }
+
+    /// Set the name of the function, which is `phf_lookup` by default.
+    ///
+    /// The function isn't mangled, so the name must be unique among all the
+    /// linked crates and C code.
+    pub fn name(&mut self, name: &str) -> &mut CLookup {
+        self.name = name.to_owned();
+        self
+    }
+
+    /// Set the C type of the values in the C declaration, which is `void` by
+    /// default.
+    pub fn c_value_type(&mut self, ty: &str) -> &mut CLookup {
+        self.c_value_type = ty.to_owned();
+        self
+    }
+
+    /// Look up byte strings, for a map with `&'static [u8]` keys.
+    ///
+    /// By default the map has `&'static str` keys, and keys which aren't
+    /// UTF-8 are never found.
+    pub fn bytes(&mut self, bytes: bool) -> &mut CLookup {
+        self.bytes = bytes;
+        self
+    }
+
+    /// Return a struct implementing [`Display`](::std::fmt::Display) which
+    /// will print the Rust function, to be written next to the map.
+    pub fn build(&self) -> DisplayCLookup<'_> {
+        DisplayCLookup { def: self }
+    }
+
+    /// Returns the C declaration of the function, along with the headers it
+    /// needs, to be written to a header file.
+    pub fn header(&self) -> String {
+        let (includes, key_type) = if self.bytes {
+            ("#include <stddef.h>\n#include <stdint.h>\n", "uint8_t")
+        } else {
+            ("#include <stddef.h>\n", "char")
+        };
+        format!(
+            "{}
+#ifdef __cplusplus
+extern \"C\" {{
+#endif
+
+const {} *{}(const {} *key_ptr, size_t key_len);
+
+#ifdef __cplusplus
+}}
+#endif
+",
+            includes, self.c_value_type, self.name, key_type
+        )
+    }
+}
+
+/// An adapter for printing a [`CLookup`](CLookup).
+pub struct DisplayCLookup<'a> {
+    def: &'a CLookup,
+}
+
+impl<'a> fmt::Display for DisplayCLookup<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let def = self.def;
+        let (utf8, lookup) = if def.bytes {
+            ("", format!("{}.get(key)", def.map))
+        } else {
+            (
+                " or isn't UTF-8",
+                format!(
+                    "::core::str::from_utf8(key).ok().and_then(|key| {}.get(key))",
+                    def.map
+                ),
+            )
+        };
+        write!(
+            f,
+            "/// Looks up the `key_len` bytes at `key_ptr` in `{map}`, returning a null
+/// pointer if the key isn't in the map{utf8}.
+///
+/// # Safety
+///
+/// `key_ptr` must be null or point to `key_len` readable bytes.
+#[no_mangle]
+pub unsafe extern \"C\" fn {name}(key_ptr: *const u8, key_len: usize) -> *const {ty} {{
+    if key_ptr.is_null() {{
+        return ::core::ptr::null();
+    }}
+    let key = ::core::slice::from_raw_parts(key_ptr, key_len);
+    match {lookup} {{
+        Some(value) => value,
+        None => ::core::ptr::null(),
+    }}
+}}
+",
+            map = def.map,
+            utf8 = utf8,
+            name = def.name,
+            ty = def.value_type,
+            lookup = lookup
+        )
+    }
+}
+
+/// A builder for the `phf::BitSet` type.
+pub struct BitSet<T> {
+    keys: Vec<T>,
+    path: String,
+}
+
+impl<T: Hash + PhfBitIndex + Eq + FmtConst> BitSet<T> {
+    /// Constructs a new `phf::BitSet` builder.
+    pub fn new() -> BitSet<T> {
+        BitSet {
+            keys: vec![],
+            path: String::from("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut BitSet<T> {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Adds an entry to the builder.
+    pub fn entry(&mut self, entry: T) -> &mut BitSet<T> {
+        self.keys.push(entry);
+        self
+    }
+
+    /// Calculate the bits and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::BitSet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys, or if the keys span more than
+    /// 65536 values.
+    pub fn build(&self) -> DisplayBitSet<'_> {
+        let mut set = HashSet::new();
+        for key in &self.keys {
+            if !set.insert(key) {
+                panic!("duplicate key `{}`", Delegate(key));
+            }
+        }
+
+        let indices = self
+            .keys
+            .iter()
+            .map(|key| key.bit_index())
+            .collect::<Vec<_>>();
+        let state = phf_generator::generate_bit_set(&indices)
+            .expect("keys span too many values for a bit set, use `Set` instead");
+
+        DisplayBitSet {
+            path: &self.path,
+            len: self.keys.len(),
+            state,
+        }
+    }
+}
+
+/// An adapter for printing a [`BitSet`](BitSet).
+pub struct DisplayBitSet<'a> {
+    path: &'a str,
+    len: usize,
+    state: BitSetState,
+}
+
+impl<'a> fmt::Display for DisplayBitSet<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::BitSet {{
+    offset: {},
+    len: {},
+    words: &[",
+            self.path, self.state.offset, self.len
+        )?;
+        for word in &self.state.words {
+            write!(
+                f,
+                "
+        {:#x},",
+                word
+            )?;
+        }
+        write!(
+            f,
+            "
+    ],
+    marker: ::core::marker::PhantomData,
+}}"
+        )
+    }
+}
+
+/// A key of an [`AsciiCaseInsensitiveMap`](AsciiCaseInsensitiveMap), which
+/// hashes and compares like its ASCII lowercase form but is written out as
+/// declared.
+struct AsciiFoldedKey(String);
+
+impl Hash for AsciiFoldedKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+    }
+}
+
+impl PartialEq for AsciiFoldedKey {
+    fn eq(&self, other: &AsciiFoldedKey) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for AsciiFoldedKey {}
+
+impl PhfHash for AsciiFoldedKey {
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        AsciiFolded(&self.0).phf_hash(state)
+    }
+}
+
+impl FmtConst for AsciiFoldedKey {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_const(f)
+    }
+}
+
+/// A builder for the `phf::AsciiCaseInsensitiveMap` type.
+pub struct AsciiCaseInsensitiveMap {
+    map: Map<AsciiFoldedKey>,
+}
+
+impl AsciiCaseInsensitiveMap {
+    /// Constructs a new `phf::AsciiCaseInsensitiveMap` builder.
+    pub fn new() -> AsciiCaseInsensitiveMap {
+        AsciiCaseInsensitiveMap { map: Map::new() }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut AsciiCaseInsensitiveMap {
+        self.map.phf_path(path);
+        self
+    }
+
+    /// Set the layout of the generated entries.
+    pub fn layout(&mut self, layout: Layout) -> &mut AsciiCaseInsensitiveMap {
+        self.map.layout(layout);
+        self
+    }
+
+    /// Pad each entry to a multiple of `align` bytes, see [`Map::align`].
+    pub fn align(&mut self, align: usize) -> &mut AsciiCaseInsensitiveMap {
+        self.map.align(align);
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// See [`Map::hasher`](Map::hasher).
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut AsciiCaseInsensitiveMap {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut AsciiCaseInsensitiveMap {
+        self.map.key(key);
+        self
+    }
+
+    /// Store a 16-bit fingerprint of each key in the generated map.
+    ///
+    /// See [`Map::fingerprints`](Map::fingerprints).
+    pub fn fingerprints(&mut self, fingerprints: bool) -> &mut AsciiCaseInsensitiveMap {
+        self.map.fingerprints(fingerprints);
+        self
+    }
+
+    /// Generate a small Bloom filter of the keys alongside the map.
+    ///
+    /// See [`Map::filter`](Map::filter).
+    pub fn filter(&mut self, filter: bool) -> &mut AsciiCaseInsensitiveMap {
+        self.map.filter(filter);
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: &str, value: &str) -> &mut AsciiCaseInsensitiveMap {
+        self.map.entry(AsciiFoldedKey(key.to_owned()), value);
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::AsciiCaseInsensitiveMap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any two keys are equal ignoring ASCII case.
+    pub fn build(&self) -> DisplayAsciiCaseInsensitiveMap<'_> {
+        DisplayAsciiCaseInsensitiveMap {
+            map: self.map.build(),
+        }
+    }
+}
+
+/// An adapter for printing an
+/// [`AsciiCaseInsensitiveMap`](AsciiCaseInsensitiveMap).
+pub struct DisplayAsciiCaseInsensitiveMap<'a> {
+    map: DisplayMap<'a, AsciiFoldedKey>,
+}
+
+impl<'a> fmt::Display for DisplayAsciiCaseInsensitiveMap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::AsciiCaseInsensitiveMap {{\n    map: {},\n}}",
+            self.map.path, self.map
+        )
+    }
+}
+
+/// A key of a [`CaselessMap`](CaselessMap), which hashes and compares like
+/// its full Unicode case folding but is written out as declared.
+#[cfg(feature = "caseless")]
+struct UnicodeFoldedKey(String);
+
+#[cfg(feature = "caseless")]
+impl Hash for UnicodeFoldedKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unicase::UniCase::unicode(&self.0).hash(state)
+    }
+}
+
+#[cfg(feature = "caseless")]
+impl PartialEq for UnicodeFoldedKey {
+    fn eq(&self, other: &UnicodeFoldedKey) -> bool {
+        unicase::UniCase::unicode(&self.0) == unicase::UniCase::unicode(&other.0)
+    }
+}
+
+#[cfg(feature = "caseless")]
+impl Eq for UnicodeFoldedKey {}
+
+#[cfg(feature = "caseless")]
+impl PhfHash for UnicodeFoldedKey {
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        UnicodeFolded(&self.0).phf_hash(state)
+    }
+}
+
+#[cfg(feature = "caseless")]
+impl FmtConst for UnicodeFoldedKey {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_const(f)
+    }
+}
+
+/// A builder for the `phf::CaselessMap` type.
+///
+/// Requires the `caseless` feature.
+#[cfg(feature = "caseless")]
+pub struct CaselessMap {
+    map: Map<UnicodeFoldedKey>,
+}
+
+#[cfg(feature = "caseless")]
+impl CaselessMap {
+    /// Constructs a new `phf::CaselessMap` builder.
+    pub fn new() -> CaselessMap {
+        CaselessMap { map: Map::new() }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: &str) -> &mut CaselessMap {
+        self.map.phf_path(path);
+        self
+    }
+
+    /// Set the layout of the generated entries.
+    pub fn layout(&mut self, layout: Layout) -> &mut CaselessMap {
+        self.map.layout(layout);
+        self
+    }
+
+    /// Pad each entry to a multiple of `align` bytes, see [`Map::align`].
+    pub fn align(&mut self, align: usize) -> &mut CaselessMap {
+        self.map.align(align);
+        self
+    }
+
+    /// Set the hash function used by the generated map.
+    ///
+    /// See [`Map::hasher`](Map::hasher).
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut CaselessMap {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash key of the generated map from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut CaselessMap {
+        self.map.key(key);
+        self
+    }
+
+    /// Store a 16-bit fingerprint of each key in the generated map.
+    ///
+    /// See [`Map::fingerprints`](Map::fingerprints).
+    pub fn fingerprints(&mut self, fingerprints: bool) -> &mut CaselessMap {
+        self.map.fingerprints(fingerprints);
+        self
+    }
+
+    /// Generate a small Bloom filter of the keys alongside the map.
+    ///
+    /// See [`Map::filter`](Map::filter).
+    pub fn filter(&mut self, filter: bool) -> &mut CaselessMap {
+        self.map.filter(filter);
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: &str, value: &str) -> &mut CaselessMap {
+        self.map.entry(UnicodeFoldedKey(key.to_owned()), value);
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::CaselessMap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any two keys have the same Unicode case folding.
+    pub fn build(&self) -> DisplayCaselessMap<'_> {
+        DisplayCaselessMap {
+            map: self.map.build(),
+        }
+    }
+}
+
+/// An adapter for printing a [`CaselessMap`](CaselessMap).
+#[cfg(feature = "caseless")]
+pub struct DisplayCaselessMap<'a> {
+    map: DisplayMap<'a, UnicodeFoldedKey>,
+}
+
+#[cfg(feature = "caseless")]
+impl<'a> fmt::Display for DisplayCaselessMap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::CaselessMap {{\n    map: {},\n}}",
+            self.map.path, self.map
+        )
+    }
+}
+
+/// A builder for the `phf::IntervalMap` type.
+pub struct IntervalMap<K> {
+    bounds: Vec<(K, K)>,
+    values: Vec<String>,
+    path: String,
+}
+
+impl<K: Ord + FmtConst> IntervalMap<K> {
+    /// Constructs a new `phf::IntervalMap` builder.
+    pub fn new() -> IntervalMap<K> {
+        IntervalMap {
+            bounds: vec![],
             values: vec![],
             path: String::from("::phf"),
         }
     }
 
     /// Set the path to the `phf` crate from the global namespace
-    pub fn phf_path(&mut self, path: &str) -> &mut Map<K> {
+    pub fn phf_path(&mut self, path: &str) -> &mut IntervalMap<K> {
         self.path = path.to_owned();
         self
     }
 
-    /// Adds an entry to the builder.
+    /// Adds a range to the builder.
     ///
     /// `value` will be written exactly as provided in the constructed source.
-    pub fn entry(&mut self, key: K, value: &str) -> &mut Map<K> {
-        self.keys.push(key);
+    pub fn entry(&mut self, range: RangeInclusive<K>, value: &str) -> &mut IntervalMap<K> {
+        self.bounds.push(range.into_inner());
         self.values.push(value.to_owned());
         self
     }
 
-    /// Calculate the hash parameters and return a struct implementing
-    /// [`Display`](::std::fmt::Display) which will print the constructed `phf::Map`.
+    /// Sort the ranges and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::IntervalMap`.
     ///
     /// # Panics
     ///
-    /// Panics if there are any duplicate keys.
-    pub fn build(&self) -> DisplayMap<'_, K> {
-        let mut set = HashSet::new();
-        for key in &self.keys {
-            if !set.insert(key) {
-                panic!("duplicate key `{}`", Delegate(key));
-            }
-        }
+    /// Panics if any range is empty or if any two ranges overlap.
+    pub fn build(&self) -> DisplayIntervalMap<'_, K> {
+        let order = match phf_generator::generate_intervals(&self.bounds) {
+            Ok(order) => order,
+            Err(IntervalError::Empty(i)) => panic!(
+                "empty range `{}..={}`",
+                Delegate(&self.bounds[i].0),
+                Delegate(&self.bounds[i].1)
+            ),
+            Err(IntervalError::Overlap(i, j)) => panic!(
+                "range `{}..={}` overlaps range `{}..={}`",
+                Delegate(&self.bounds[i].0),
+                Delegate(&self.bounds[i].1),
+                Delegate(&self.bounds[j].0),
+                Delegate(&self.bounds[j].1)
+            ),
+        };
 
-        let state = phf_generator::generate_hash(&self.keys);
-
-        DisplayMap {
+        DisplayIntervalMap {
             path: &self.path,
-            keys: &self.keys,
+            order,
+            bounds: &self.bounds,
             values: &self.values,
-            state,
         }
     }
 }
 
-/// An adapter for printing a [`Map`](Map).
-pub struct DisplayMap<'a, K> {
+/// An adapter for printing an [`IntervalMap`](IntervalMap).
+pub struct DisplayIntervalMap<'a, K> {
     path: &'a str,
-    state: HashState,
-    keys: &'a [K],
+    order: Vec<usize>,
+    bounds: &'a [(K, K)],
     values: &'a [String],
 }
 
-impl<'a, K: FmtConst + 'a> fmt::Display for DisplayMap<'a, K> {
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayIntervalMap<'a, K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // funky formatting here for nice output
-        write!(
-            f,
-            "{}::Map {{
-    key: {:?},
-    disps: &[",
-            self.path, self.state.key
-        )?;
-
-        // write map displacements
-        for &(d1, d2) in &self.state.disps {
+        write!(f, "{}::IntervalMap {{\n    bounds: &[", self.path)?;
+        for &i in &self.order {
+            let (start, end) = &self.bounds[i];
             write!(
                 f,
                 "
         ({}, {}),",
-                d1, d2
+                Delegate(start),
+                Delegate(end)
             )?;
         }
-
-        write!(
-            f,
-            "
-    ],
-    entries: &[",
-        )?;
-
-        // write map entries
-        for &idx in &self.state.map {
+        write!(f, "\n    ],\n    values: &[")?;
+        for &i in &self.order {
             write!(
                 f,
                 "
-        ({}, {}),",
-                Delegate(&self.keys[idx]),
-                &self.values[idx]
+        {},",
+                self.values[i]
             )?;
         }
-
-        write!(
-            f,
-            "
-    ],
-}}"
-        )
+        write!(f, "\n    ],\n}}")
     }
 }
 
-/// A builder for the `phf::Set` type.
-pub struct Set<T> {
-    map: Map<T>,
+/// A builder for the `phf::TrieMap` type.
+pub struct TrieMap<K> {
+    keys: Vec<K>,
+    values: Vec<String>,
+    path: String,
 }
 
-impl<T: Hash + PhfHash + Eq + FmtConst> Set<T> {
-    /// Constructs a new `phf::Set` builder.
-    pub fn new() -> Set<T> {
-        Set { map: Map::new() }
+impl<K: Hash + AsRef<[u8]> + Eq + FmtConst> TrieMap<K> {
+    /// Constructs a new `phf::TrieMap` builder.
+    pub fn new() -> TrieMap<K> {
+        TrieMap {
+            keys: vec![],
+            values: vec![],
+            path: String::from("::phf"),
+        }
     }
 
     /// Set the path to the `phf` crate from the global namespace
-    pub fn phf_path(&mut self, path: &str) -> &mut Set<T> {
-        self.map.phf_path(path);
+    pub fn phf_path(&mut self, path: &str) -> &mut TrieMap<K> {
+        self.path = path.to_owned();
         self
     }
 
     /// Adds an entry to the builder.
-    pub fn entry(&mut self, entry: T) -> &mut Set<T> {
-        self.map.entry(entry, "()");
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: &str) -> &mut TrieMap<K> {
+        self.keys.push(key);
+        self.values.push(value.to_owned());
         self
     }
 
-    /// Calculate the hash parameters and return a struct implementing
-    /// [`Display`](::std::fmt::Display) which will print the constructed `phf::Set`.
+    /// Calculate the trie and return a struct implementing
+    /// [`Display`](::std::fmt::Display) which will print the constructed
+    /// `phf::TrieMap`.
     ///
     /// # Panics
     ///
     /// Panics if there are any duplicate keys.
-    pub fn build(&self) -> DisplaySet<'_, T> {
-        DisplaySet {
-            inner: self.map.build(),
+    pub fn build(&self) -> DisplayTrieMap<'_, K> {
+        let mut set = HashSet::new();
+        for key in &self.keys {
+            if !set.insert(key) {
+                panic!("duplicate key `{}`", Delegate(key));
+            }
+        }
+
+        DisplayTrieMap {
+            path: &self.path,
+            nodes: phf_generator::generate_trie(&self.keys),
+            keys: &self.keys,
+            values: &self.values,
         }
     }
 }
 
-/// An adapter for printing a [`Set`](Set).
-pub struct DisplaySet<'a, T> {
-    inner: DisplayMap<'a, T>,
+/// An adapter for printing a [`TrieMap`](TrieMap).
+pub struct DisplayTrieMap<'a, K> {
+    path: &'a str,
+    nodes: Vec<TrieNode>,
+    keys: &'a [K],
+    values: &'a [String],
 }
 
-impl<'a, T: FmtConst + 'a> fmt::Display for DisplaySet<'a, T> {
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayTrieMap<'a, K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}::Set {{ map: {} }}", self.inner.path, self.inner)
+        write!(f, "{}::TrieMap {{\n    nodes: &[", self.path)?;
+        for node in &self.nodes {
+            write!(
+                f,
+                "
+        {}::trie_map::Node {{ entry: {:?}, edges: &[",
+                self.path, node.entry
+            )?;
+            for &(byte, child) in &node.edges {
+                write!(f, "({}, {}), ", byte, child)?;
+            }
+            write!(f, "] }},")?;
+        }
+        write!(f, "\n    ],\n    entries: &[")?;
+        for (key, value) in self.keys.iter().zip(self.values) {
+            write!(
+                f,
+                "
+        ({}, {}),",
+                Delegate(key),
+                value
+            )?;
+        }
+        write!(f, "\n    ],\n}}")
     }
 }
 
-/// A builder for the `phf::OrderedMap` type.
-pub struct OrderedMap<K> {
-    keys: Vec<K>,
+/// A builder for the `phf::CharMap` type.
+pub struct CharMap {
+    keys: Vec<char>,
     values: Vec<String>,
     path: String,
 }
 
-impl<K: Hash + PhfHash + Eq + FmtConst> OrderedMap<K> {
-    /// Constructs a enw `phf::OrderedMap` builder.
-    pub fn new() -> OrderedMap<K> {
-        OrderedMap {
+impl CharMap {
+    /// Constructs a new `phf::CharMap` builder.
+    pub fn new() -> CharMap {
+        CharMap {
             keys: vec![],
             values: vec![],
             path: String::from("::phf"),
@@ -328,7 +2811,7 @@ impl<K: Hash + PhfHash + Eq + FmtConst> OrderedMap<K> {
     }
 
     /// Set the path to the `phf` crate from the global namespace
-    pub fn phf_path(&mut self, path: &str) -> &mut OrderedMap<K> {
+    pub fn phf_path(&mut self, path: &str) -> &mut CharMap {
         self.path = path.to_owned();
         self
     }
@@ -336,20 +2819,20 @@ impl<K: Hash + PhfHash + Eq + FmtConst> OrderedMap<K> {
     /// Adds an entry to the builder.
     ///
     /// `value` will be written exactly as provided in the constructed source.
-    pub fn entry(&mut self, key: K, value: &str) -> &mut OrderedMap<K> {
+    pub fn entry(&mut self, key: char, value: &str) -> &mut CharMap {
         self.keys.push(key);
         self.values.push(value.to_owned());
         self
     }
 
-    /// Calculate the hash parameters and return a struct implementing
+    /// Calculate the two-level table and return a struct implementing
     /// [`Display`](::std::fmt::Display) which will print the constructed
-    /// `phf::OrderedMap`.
+    /// `phf::CharMap`.
     ///
     /// # Panics
     ///
-    /// Panics if there are any duplicate keys.
-    pub fn build(&self) -> DisplayOrderedMap<'_, K> {
+    /// Panics if there are any duplicate keys, or more than 65534 entries.
+    pub fn build(&self) -> DisplayCharMap<'_> {
         let mut set = HashSet::new();
         for key in &self.keys {
             if !set.insert(key) {
@@ -357,63 +2840,40 @@ impl<K: Hash + PhfHash + Eq + FmtConst> OrderedMap<K> {
             }
         }
 
-        let state = phf_generator::generate_hash(&self.keys);
-
-        DisplayOrderedMap {
+        DisplayCharMap {
             path: &self.path,
-            state,
+            table: phf_generator::generate_char_table(&self.keys)
+                .expect("too many entries for a `phf::CharMap`"),
             keys: &self.keys,
             values: &self.values,
         }
     }
 }
 
-/// An adapter for printing a [`OrderedMap`](OrderedMap).
-pub struct DisplayOrderedMap<'a, K> {
+/// An adapter for printing a [`CharMap`](CharMap).
+pub struct DisplayCharMap<'a> {
     path: &'a str,
-    state: HashState,
-    keys: &'a [K],
+    table: CharTable,
+    keys: &'a [char],
     values: &'a [String],
 }
 
-impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
+impl<'a> fmt::Display for DisplayCharMap<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}::OrderedMap {{
-    key: {:?},
-    disps: &[",
-            self.path, self.state.key
-        )?;
-        for &(d1, d2) in &self.state.disps {
-            write!(
-                f,
-                "
-        ({}, {}),",
-                d1, d2
-            )?;
-        }
-        write!(
-            f,
-            "
-    ],
-    idxs: &[",
+            "{}::CharMap {{\n    pages: &{:?},",
+            self.path, self.table.pages
         )?;
-        for &idx in &self.state.map {
-            write!(
-                f,
-                "
-        {},",
-                idx
-            )?;
+        write!(f, "\n    blocks: &[")?;
+        for block in self.table.blocks.chunks(16) {
+            write!(f, "\n        ")?;
+            for slot in block {
+                write!(f, "{}, ", slot)?;
+            }
         }
-        write!(
-            f,
-            "
-    ],
-    entries: &[",
-        )?;
-        for (key, value) in self.keys.iter().zip(self.values.iter()) {
+        write!(f, "\n    ],\n    entries: &[")?;
+        for (key, value) in self.keys.iter().zip(self.values) {
             write!(
                 f,
                 "
@@ -422,65 +2882,231 @@ impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
                 value
             )?;
         }
-        write!(
-            f,
-            "
-    ],
-}}"
-        )
+        write!(f, "\n    ],\n}}")
     }
 }
 
-/// A builder for the `phf::OrderedSet` type.
-pub struct OrderedSet<T> {
-    map: OrderedMap<T>,
+/// A builder for the `phf::ShardedMap` type.
+pub struct ShardedMap<K> {
+    map: Map<K>,
+    shards: usize,
 }
 
-impl<T: Hash + PhfHash + Eq + FmtConst> OrderedSet<T> {
-    /// Constructs a new `phf::OrderedSet` builder.
-    pub fn new() -> OrderedSet<T> {
-        OrderedSet {
-            map: OrderedMap::new(),
+impl<K: Hash + PhfHash + Eq + FmtConst> ShardedMap<K> {
+    /// Constructs a new `phf::ShardedMap` builder which splits the entries
+    /// into `shards` shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn new(shards: usize) -> ShardedMap<K> {
+        assert!(shards > 0, "a sharded map needs at least one shard");
+        ShardedMap {
+            map: Map::new(),
+            shards,
         }
     }
 
     /// Set the path to the `phf` crate from the global namespace
-    pub fn phf_path(&mut self, path: &str) -> &mut OrderedSet<T> {
+    pub fn phf_path(&mut self, path: &str) -> &mut ShardedMap<K> {
         self.map.phf_path(path);
         self
     }
 
+    /// Set the layout of the generated entries.
+    pub fn layout(&mut self, layout: Layout) -> &mut ShardedMap<K> {
+        self.map.layout(layout);
+        self
+    }
+
+    /// Pad each entry to a multiple of `align` bytes, see [`Map::align`].
+    pub fn align(&mut self, align: usize) -> &mut ShardedMap<K> {
+        self.map.align(align);
+        self
+    }
+
+    /// Set the hash function used by the generated map and its shards.
+    pub fn hasher(&mut self, hasher: HashFn) -> &mut ShardedMap<K> {
+        self.map.hasher(hasher);
+        self
+    }
+
+    /// Derive the hash keys of the generated map and its shards from `key`.
+    ///
+    /// See [`Map::key`](Map::key).
+    pub fn key(&mut self, key: HashKey) -> &mut ShardedMap<K> {
+        self.map.key(key);
+        self
+    }
+
+    /// Store a 16-bit fingerprint of each key in the generated shards.
+    ///
+    /// See [`Map::fingerprints`](Map::fingerprints).
+    pub fn fingerprints(&mut self, fingerprints: bool) -> &mut ShardedMap<K> {
+        self.map.fingerprints(fingerprints);
+        self
+    }
+
+    /// Generate a small Bloom filter of the keys of each shard.
+    ///
+    /// See [`Map::filter`](Map::filter).
+    pub fn filter(&mut self, filter: bool) -> &mut ShardedMap<K> {
+        self.map.filter(filter);
+        self
+    }
+
     /// Adds an entry to the builder.
-    pub fn entry(&mut self, entry: T) -> &mut OrderedSet<T> {
-        self.map.entry(entry, "()");
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: &str) -> &mut ShardedMap<K> {
+        self.map.entry(key, value);
         self
     }
 
     /// Calculate the hash parameters and return a struct implementing
     /// [`Display`](::std::fmt::Display) which will print the constructed
-    /// `phf::OrderedSet`.
+    /// `phf::ShardedMap`, including all of its shards.
+    ///
+    /// To write each shard separately, print each of
+    /// [`DisplayShardedMap::shards`](DisplayShardedMap::shards) as the value
+    /// of a `static phf::Map`, and then
+    /// [`DisplayShardedMap::root`](DisplayShardedMap::root) with the paths
+    /// of those statics.
     ///
     /// # Panics
     ///
     /// Panics if there are any duplicate keys.
-    pub fn build(&self) -> DisplayOrderedSet<'_, T> {
-        DisplayOrderedSet {
-            inner: self.map.build(),
+    pub fn build(&self) -> DisplayShardedMap<'_, K> {
+        self.map.check_align();
+        self.map.check_duplicates();
+
+        let hasher = self
+            .map
+            .hasher
+            .unwrap_or_else(|| phf_generator::default_hash_fn(self.map.keys.len()));
+        let state = match self.map.key {
+            Some(key) => {
+                phf_generator::generate_shards_seeded(&self.map.keys, self.shards, hasher, key)
+            }
+            None => phf_generator::generate_shards(&self.map.keys, self.shards, hasher),
+        };
+        let values = self
+            .map
+            .values
+            .iter()
+            .map(|v| Cow::from(&**v))
+            .collect::<Vec<_>>();
+        let shards = state
+            .shards
+            .into_iter()
+            .map(|entries| DisplayMap {
+                path: &self.map.path,
+                layout: self.map.layout,
+                align: self.map.align,
+                min_len: None,
+                shared_values: None,
+                tables: vec![self.map.build_table(entries, &values)],
+            })
+            .collect();
+
+        DisplayShardedMap {
+            path: &self.map.path,
+            key: state.key,
+            hasher: state.hasher,
+            len: self.map.keys.len(),
+            shards,
         }
     }
 }
 
-/// An adapter for printing a [`OrderedSet`](OrderedSet).
-pub struct DisplayOrderedSet<'a, T> {
-    inner: DisplayOrderedMap<'a, T>,
+/// An adapter for printing a [`ShardedMap`](ShardedMap).
+pub struct DisplayShardedMap<'a, K> {
+    path: &'a str,
+    key: HashKey,
+    hasher: HashFn,
+    len: usize,
+    shards: Vec<DisplayMap<'a, K>>,
 }
 
-impl<'a, T: FmtConst + 'a> fmt::Display for DisplayOrderedSet<'a, T> {
+impl<'a, K: FmtConst + 'a> DisplayShardedMap<'a, K> {
+    /// Returns adapters printing each shard as a `phf::Map`.
+    pub fn shards(&self) -> &[DisplayMap<'a, K>] {
+        &self.shards
+    }
+
+    /// Returns an adapter printing the `phf::ShardedMap` itself, referring
+    /// to each shard by the path of a `static` written from
+    /// [`shards`](DisplayShardedMap::shards).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of paths doesn't match the number of shards.
+    pub fn root<'b, S: AsRef<str>>(&'b self, shard_paths: &'b [S]) -> DisplayShardedRoot<'b, S> {
+        assert_eq!(
+            shard_paths.len(),
+            self.shards.len(),
+            "expected a path for every shard"
+        );
+        DisplayShardedRoot {
+            path: self.path,
+            key: self.key,
+            hasher: self.hasher,
+            len: self.len,
+            shard_paths,
+        }
+    }
+}
+
+/// Writes the fields of a `phf::ShardedMap` up to its shards.
+fn fmt_sharded_header(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    key: HashKey,
+    hasher: HashFn,
+    len: usize,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}::ShardedMap {{
+    key: {:?},
+    hasher: {}::HashFn::{:?},
+    len: {},
+    shards: &[",
+        path, key, path, hasher, len
+    )
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayShardedMap<'a, K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}::OrderedSet {{ map: {} }}",
-            self.inner.path, self.inner
-        )
+        fmt_sharded_header(f, self.path, self.key, self.hasher, self.len)?;
+        for shard in &self.shards {
+            write!(f, "\n&{},", shard)?;
+        }
+        write!(f, "\n    ],\n}}")
+    }
+}
+
+/// An adapter for printing the root of a [`ShardedMap`](ShardedMap) whose
+/// shards are written separately.
+pub struct DisplayShardedRoot<'a, S> {
+    path: &'a str,
+    key: HashKey,
+    hasher: HashFn,
+    len: usize,
+    shard_paths: &'a [S],
+}
+
+impl<'a, S: AsRef<str>> fmt::Display for DisplayShardedRoot<'a, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_sharded_header(f, self.path, self.key, self.hasher, self.len)?;
+        for shard_path in self.shard_paths {
+            write!(
+                f,
+                "
+        &{},",
+                shard_path.as_ref()
+            )?;
+        }
+        write!(f, "\n    ],\n}}")
     }
 }
@@ -2,6 +2,7 @@ use std::env;
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
+use std::time::Duration;
 
 use uncased::UncasedStr;
 use unicase::UniCase;
@@ -20,6 +21,427 @@ fn main() -> io::Result<()> {
             .build()
     )?;
 
+    writeln!(
+        &mut file,
+        "static SPLIT_MAP: ::phf::Map<u32, &'static str> = \n{};",
+        phf_codegen::Map::new()
+            .layout(phf_codegen::Layout::Split)
+            .entry(1u32, "\"a\"")
+            .entry(2u32, "\"b\"")
+            .comment("a comment\n\nspanning lines")
+            .entry(3u32, "\"c\"")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static ALIGNED_MAP: ::phf::Map<u32, &'static str> = \n{};",
+        phf_codegen::Map::new()
+            .align(64)
+            .entry(1u32, "\"a\"")
+            .entry(2u32, "\"b\"")
+            .comment("a comment")
+            .entry(3u32, "\"c\"")
+            .build()
+    )?;
+
+    // large enough that the displacements don't fit in a `u8`
+    let mut large = phf_codegen::Map::new();
+    for i in 0..1000u32 {
+        large.entry(i, &format!("{}", i * 2));
+    }
+    writeln!(
+        &mut file,
+        "static LARGE_MAP: ::phf::Map<u32, u32> = \n{};",
+        large.build()
+    )?;
+
+    let mut short_keys = phf_codegen::Map::new();
+    short_keys.hasher(phf_codegen::HashFn::ShortKey);
+    let keys = (0..500u32)
+        .map(|i| format!("key{}", "x".repeat(i as usize % 20)) + &i.to_string())
+        .collect::<Vec<_>>();
+    for (i, key) in keys.iter().enumerate() {
+        short_keys.entry(&**key, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static SHORT_KEY_MAP: ::phf::Map<&'static str, usize> = \n{};",
+        short_keys.build()
+    )?;
+
+    let mut fingerprinted = phf_codegen::Map::new();
+    fingerprinted.fingerprints(true);
+    let keys = (0..50u32)
+        .map(|i| format!("a rather long key that shares a prefix {}", i))
+        .collect::<Vec<_>>();
+    for (i, key) in keys.iter().enumerate() {
+        fingerprinted.entry(&**key, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static FINGERPRINT_MAP: ::phf::Map<&'static str, usize> = \n{};",
+        fingerprinted.build()
+    )?;
+
+    let mut filtered = phf_codegen::Set::new();
+    filtered.filter(true);
+    for i in 0..100u32 {
+        filtered.entry(i * 2);
+    }
+    writeln!(
+        &mut file,
+        "static FILTERED_SET: ::phf::Set<u32> = \n{};",
+        filtered.build()
+    )?;
+
+    let mut routes = phf_codegen::TrieMap::new();
+    routes
+        .entry("/", "0")
+        .entry("/api/", "1")
+        .entry("/api/v1/", "2");
+    writeln!(
+        &mut file,
+        "static ROUTES: ::phf::TrieMap<&'static str, u32> = \n{};",
+        routes.build()
+    )?;
+
+    let mut headers = phf_codegen::AsciiCaseInsensitiveMap::new();
+    headers.filter(true);
+    for (i, header) in [
+        "Accept",
+        "Accept-Encoding",
+        "Authorization",
+        "Cache-Control",
+        "Content-Type",
+        "Content-Length",
+        "Host",
+        "User-Agent",
+    ]
+    .iter()
+    .enumerate()
+    {
+        headers.entry(header, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static HEADERS: ::phf::AsciiCaseInsensitiveMap<usize> = \n{};",
+        headers.build()
+    )?;
+
+    let mut streets = phf_codegen::CaselessMap::new();
+    streets
+        .entry("Hauptstraße", "1")
+        .entry("Ελληνικά", "2")
+        .entry("Main Street", "3");
+    writeln!(
+        &mut file,
+        "static STREETS: ::phf::CaselessMap<u32> = \n{};",
+        streets.build()
+    )?;
+
+    let mut planes = phf_codegen::IntervalMap::new();
+    planes
+        .entry(0x2_0000..=0x2_FFFF, "2")
+        .entry(0x0000..=0xFFFF, "0")
+        .entry(0x1_0000..=0x1_FFFF, "1")
+        .entry(0xE_0000..=0xE_FFFF, "14");
+    writeln!(
+        &mut file,
+        "static PLANES: ::phf::IntervalMap<u32, u8> = \n{};",
+        planes.build()
+    )?;
+
+    let mut interner = phf_codegen::Interner::new();
+    for i in 0..100 {
+        interner.entry(&format!("symbol{}", i));
+    }
+    writeln!(
+        &mut file,
+        "static INTERNER: ::phf::Interner = \n{};",
+        interner.build()
+    )?;
+
+    let mut array = phf_codegen::ArrayMap::new();
+    for i in 0..20u32 {
+        array.entry(i, &(i * 2).to_string());
+    }
+    writeln!(
+        &mut file,
+        "const ARRAY_MAP: ::phf::ArrayMap<u32, u32, 20> = \n{};",
+        array.build()
+    )?;
+
+    let mut chars = phf_codegen::CharMap::new();
+    for (i, c) in "abc\u{3b1}\u{3b2}\u{1f600}".chars().enumerate() {
+        chars.entry(c, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static CHAR_MAP: ::phf::CharMap<u32> = \n{};",
+        chars.build()
+    )?;
+
+    let mut keywords = phf_codegen::Enum::new("Keyword", "KEYWORD_ENUM");
+    keywords
+        .entry("loop")
+        .entry("continue")
+        .entry("type-of")
+        .variant("SelfType", "Self")
+        .as_str(true);
+    writeln!(&mut file, "{}", keywords.build())?;
+
+    writeln!(
+        &mut file,
+        "static SHARED_VALUES_MAP: ::phf::LengthMap<&'static [u32; 4]> = \n{};",
+        phf_codegen::Map::new()
+            .share_values("[u32; 4]")
+            .strategy(phf_codegen::Strategy::LengthBucketed)
+            .entry("zero", "[0, 0, 0, 0]")
+            .entry("one", "[1, 1, 1, 1]")
+            .entry("nil", "[0, 0, 0, 0]")
+            .entry("none", "[0, 0, 0, 0]")
+            .build()
+    )?;
+
+    let mut sorted = phf_codegen::Map::new();
+    sorted
+        .strategy(phf_codegen::Strategy::SortedSlice)
+        .fingerprints(true)
+        .filter(true);
+    for i in 0..100u32 {
+        sorted.entry(format!("key{}", i), &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static SORTED_MAP: ::phf::Map<&'static str, u32> = \n{};",
+        sorted.build()
+    )?;
+
+    let mut fallback = phf_codegen::Set::new();
+    fallback.fallback(true);
+    for i in 0..100u32 {
+        fallback.entry(i);
+    }
+    writeln!(
+        &mut file,
+        "static FALLBACK_SET: ::phf::Set<u32> = \n{};",
+        fallback.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "{}",
+        phf_codegen::CLookup::new("SORTED_MAP", "u32")
+            .name("phf_codegen_test_lookup")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static DURATION_MAP: ::phf::Map<::std::time::Duration, &'static str> = \n{};",
+        phf_codegen::Map::new()
+            .entry(Duration::from_secs(5), "\"short\"")
+            .entry(Duration::from_secs(60), "\"long\"")
+            .entry(Duration::from_millis(1500), "\"retry\"")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static OPTION_MAP: ::phf::Map<Option<&'static str>, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(None, "0")
+            .entry(Some("a"), "1")
+            .entry(Some(""), "2")
+            .build()
+    )?;
+
+    let mut definition_order = phf_codegen::Map::new();
+    definition_order.definition_order(true);
+    for i in (0..100).rev() {
+        definition_order.entry(i, &format!("{}", i * 2));
+    }
+    writeln!(
+        &mut file,
+        "static DEFINITION_ORDER_MAP: ::phf::Map<u32, u32> = \n{};",
+        definition_order.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static GENERIC_VALUE_MAP: ::phf::Map<u32, (::std::borrow::Cow<'static, str>, ::std::marker::PhantomData<u8>)> = \n{};",
+        phf_codegen::Map::new()
+            .entry(
+                1u32,
+                "(::std::borrow::Cow::<'static, str>::Borrowed(\"one\"), ::std::marker::PhantomData::<u8>)"
+            )
+            .entry(
+                2u32,
+                "(::std::borrow::Cow::Borrowed(\"two\"), ::std::marker::PhantomData)"
+            )
+            .build()
+    )?;
+
+    let mut nested = phf_codegen::Map::new();
+    for (name, offset) in &[("small", 0u32), ("large", 100)] {
+        let mut inner = phf_codegen::Map::new();
+        for i in 0..3u32 {
+            inner.entry(i, &(offset + i).to_string());
+        }
+        nested.entry_map(*name, &inner);
+    }
+    nested.entry_map("empty", &phf_codegen::Map::<u32>::new());
+    writeln!(
+        &mut file,
+        "static NESTED_MAP: ::phf::Map<&'static str, ::phf::Map<u32, u32>> = \n{};",
+        nested.build()
+    )?;
+
+    // defaults followed by overrides, as layered config files would give
+    let mut settings = phf_codegen::OrderedMap::new();
+    settings.duplicates(phf_codegen::Duplicates::Last);
+    for (key, value) in &[("colour", "auto"), ("jobs", "1"), ("colour", "never")] {
+        settings.entry(*key, &format!("{:?}", value));
+    }
+    writeln!(
+        &mut file,
+        "static SETTINGS: ::phf::OrderedMap<&'static str, &'static str> = \n{};",
+        settings.build()
+    )?;
+
+    let mut packed = phf_codegen::Map::new();
+    packed.pack_disps(true);
+    for i in 0..5000u32 {
+        packed.entry(i, &format!("{}", i * 3));
+    }
+    writeln!(
+        &mut file,
+        "static PACKED_MAP: ::phf::Map<u32, u32> = \n{};",
+        packed.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static PACKED_SET: ::phf::Set<&'static str> = \n{};",
+        phf_codegen::Set::new()
+            .pack_disps(true)
+            .entry("foo")
+            .entry("bar")
+            .build()
+    )?;
+
+    // builds a table the way a third-party generator would, without going
+    // through `phf_codegen`
+    let keys = (0..50u32).collect::<Vec<_>>();
+    let state = phf_generator::generate_hash(&keys);
+    write!(
+        &mut file,
+        "static RAW_MAP: ::phf::Map<u32, u32> = ::phf::Map::from_raw_parts({}, ::phf::HashFn::{:?}, &{:?}, &[",
+        state.key, state.hasher, state.disps
+    )?;
+    for &i in &state.map {
+        write!(&mut file, "({}, {}), ", keys[i], keys[i] + 1)?;
+    }
+    writeln!(&mut file, "]);")?;
+
+    let mut keyed = phf_codegen::Map::new();
+    keyed.key(0xdead_beef_0000_0000_0000_0000_cafe_f00d);
+    for i in 0..100u32 {
+        keyed.entry(i, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static KEYED_MAP: ::phf::Map<u32, u32> = \n{};",
+        keyed.build()
+    )?;
+
+    let mut keywords = phf_codegen::Map::new();
+    keywords.strategy(phf_codegen::Strategy::LengthBucketed);
+    for (i, keyword) in [
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "fn", "for", "if",
+        "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+        "static", "struct", "super", "trait", "type", "unsafe", "use", "where", "while",
+    ]
+    .iter()
+    .enumerate()
+    {
+        keywords.entry(*keyword, &i.to_string());
+    }
+    writeln!(
+        &mut file,
+        "static KEYWORDS: ::phf::LengthMap<usize> = \n{};",
+        keywords.build()
+    )?;
+
+    let mut sharded = phf_codegen::ShardedMap::new(4);
+    for i in 0..1000u32 {
+        sharded.entry(i, &format!("{}", i + 1));
+    }
+    let sharded = sharded.build();
+    writeln!(
+        &mut file,
+        "static SHARDED_MAP: ::phf::ShardedMap<u32, u32> = \n{};",
+        sharded
+    )?;
+
+    // the same map again, with every shard in its own file and module
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut shard_paths = vec![];
+    for (i, shard) in sharded.shards().iter().enumerate() {
+        let shard_file = Path::new(&out_dir).join(format!("shard_{}.rs", i));
+        let mut shard_file = BufWriter::new(File::create(&shard_file)?);
+        write!(&mut shard_file, "{}", shard)?;
+        writeln!(
+            &mut file,
+            "mod shard_{} {{ pub static SHARD: ::phf::Map<u32, u32> = \
+             include!(concat!(env!(\"OUT_DIR\"), \"/shard_{}.rs\")); }}",
+            i, i
+        )?;
+        shard_paths.push(format!("shard_{}::SHARD", i));
+    }
+    writeln!(
+        &mut file,
+        "static SPLIT_SHARDED_MAP: ::phf::ShardedMap<u32, u32> = \n{};",
+        sharded.root(&shard_paths)
+    )?;
+
+    // a map whose entries are written to several files, and another whose
+    // chunks refer to shared values
+    let mut chunked = phf_codegen::Map::new();
+    for i in 0..1000u32 {
+        chunked.entry(i, &format!("{}", i + 1));
+    }
+    let mut shared = phf_codegen::Map::new();
+    shared.share_values("[u32; 4]");
+    for i in 0..10u32 {
+        shared.entry(i, if i % 2 == 0 { "[0; 4]" } else { "[1; 4]" });
+    }
+    let maps = [
+        ("CHUNKED_MAP", "u32", chunked.build()),
+        ("CHUNKED_SHARED_MAP", "&'static [u32; 4]", shared.build()),
+    ];
+    for (name, ty, map) in &maps {
+        let mut chunk_paths = vec![];
+        for (i, chunk) in map.chunks(300).iter().enumerate() {
+            let chunk_name = format!("{}_{}.rs", name.to_lowercase(), i);
+            let chunk_file = Path::new(&out_dir).join(&chunk_name);
+            let mut chunk_file = BufWriter::new(File::create(&chunk_file)?);
+            write!(&mut chunk_file, "{}", chunk)?;
+            chunk_paths.push(format!(
+                "include!(concat!(env!(\"OUT_DIR\"), \"/{}\"))",
+                chunk_name
+            ));
+        }
+        writeln!(
+            &mut file,
+            "static {}: ::phf::Map<u32, {}> = \n{};",
+            name,
+            ty,
+            map.chunked_root(300, &chunk_paths)
+        )?;
+    }
+
     writeln!(
         &mut file,
         "static SET: ::phf::Set<u32> = \n{};",
@@ -35,11 +457,44 @@ fn main() -> io::Result<()> {
         "static ORDERED_MAP: ::phf::OrderedMap<u32, &'static str> = \n{};",
         phf_codegen::OrderedMap::new()
             .entry(1u32, "\"a\"")
+            .comment("the first entry")
             .entry(2u32, "\"b\"")
             .entry(3u32, "\"c\"")
             .build()
     )?;
 
+    let mut slot_ordered = phf_codegen::OrderedMap::new();
+    slot_ordered.slot_order(true);
+    for i in 0..100u32 {
+        slot_ordered.entry(i, &format!("{}", i * 3));
+    }
+    writeln!(
+        &mut file,
+        "static SLOT_ORDERED_MAP: ::phf::OrderedMap<u32, u32> = \n{};",
+        slot_ordered.build()
+    )?;
+
+    let mut bytes = phf_codegen::BitSet::new();
+    for &b in b"aeiouAEIOU" {
+        bytes.entry(b);
+    }
+    writeln!(
+        &mut file,
+        "static BIT_SET: ::phf::BitSet<u8> = \n{};",
+        bytes.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static SIGNED_BIT_SET: ::phf::BitSet<i16> = \n{};",
+        phf_codegen::BitSet::new()
+            .entry(-300i16)
+            .entry(-1i16)
+            .entry(0i16)
+            .entry(200i16)
+            .build()
+    )?;
+
     writeln!(
         &mut file,
         "static ORDERED_SET: ::phf::OrderedSet<u32> = \n{};",
@@ -13,6 +13,314 @@ mod test {
         assert!(!MAP.contains_key(&100));
     }
 
+    #[test]
+    fn split_map() {
+        assert_eq!("a", SPLIT_MAP[&1]);
+        assert_eq!("b", SPLIT_MAP[&2]);
+        assert_eq!("c", SPLIT_MAP[&3]);
+        assert!(!SPLIT_MAP.contains_key(&100));
+        assert_eq!(3, SPLIT_MAP.entries().count());
+    }
+
+    #[test]
+    fn aligned_map() {
+        assert_eq!("a", ALIGNED_MAP[&1]);
+        assert_eq!("b", ALIGNED_MAP[&2]);
+        assert_eq!("c", ALIGNED_MAP[&3]);
+        assert!(!ALIGNED_MAP.contains_key(&100));
+        assert_eq!(3, ALIGNED_MAP.entries().count());
+        match &ALIGNED_MAP.entries {
+            phf::map::Slots::Aligned(phf::map::AlignedSlots::A64(entries)) => {
+                assert_eq!(0, entries.as_ptr() as usize % 64);
+                assert_eq!(64, std::mem::size_of_val(&entries[0]));
+            }
+            _ => panic!("entries aren't aligned"),
+        }
+    }
+
+    #[test]
+    fn large_map() {
+        for i in 0..1000 {
+            assert_eq!(i * 2, LARGE_MAP[&i]);
+        }
+        assert!(!LARGE_MAP.contains_key(&1000));
+        assert_eq!(phf::HashFn::Sip64, LARGE_MAP.hasher);
+    }
+
+    #[test]
+    fn short_key_map() {
+        for i in 0..500 {
+            let key = format!("key{}", "x".repeat(i % 20)) + &i.to_string();
+            assert_eq!(i, SHORT_KEY_MAP[&*key]);
+        }
+        assert!(!SHORT_KEY_MAP.contains_key("key"));
+    }
+
+    #[test]
+    fn fingerprint_map() {
+        for i in 0..50 {
+            let key = format!("a rather long key that shares a prefix {}", i);
+            assert_eq!(i, FINGERPRINT_MAP[&*key]);
+        }
+        assert!(!FINGERPRINT_MAP.contains_key("a rather long key that shares a prefix 50"));
+        assert_eq!(50, FINGERPRINT_MAP.side.unwrap().fingerprints.len());
+    }
+
+    #[test]
+    fn filtered_set() {
+        for i in 0..200 {
+            assert_eq!(i % 2 == 0, FILTERED_SET.contains(&i));
+        }
+        assert!(!FILTERED_SET.map.side.unwrap().filter.is_empty());
+    }
+
+    #[test]
+    fn trie_map() {
+        assert_eq!(
+            Some((&"/api/v1/", &2)),
+            ROUTES.longest_prefix("/api/v1/users")
+        );
+        assert_eq!(Some((&"/api/", &1)), ROUTES.longest_prefix("/api/v2"));
+        assert_eq!(Some((&"/", &0)), ROUTES.longest_prefix("/index.html"));
+        assert_eq!(Some(&1), ROUTES.get("/api/"));
+        assert_eq!(None, ROUTES.get("/api"));
+    }
+
+    #[test]
+    fn ascii_case_insensitive_map() {
+        assert_eq!(Some(&0), HEADERS.get("accept"));
+        assert_eq!(Some(&7), HEADERS.get("USER-AGENT"));
+        assert_eq!(Some(&"Cache-Control"), HEADERS.get_key("cache-CONTROL"));
+        assert_eq!(None, HEADERS.get("Referer"));
+    }
+
+    #[test]
+    fn caseless_map() {
+        assert_eq!(Some(&1), STREETS.get("HAUPTSTRASSE"));
+        assert_eq!(Some(&2), STREETS.get("ΕΛΛΗΝΙΚΆ"));
+        assert_eq!(Some(&"Main Street"), STREETS.get_key("MAIN street"));
+        assert_eq!(None, STREETS.get("Hauptstrasse 2"));
+    }
+
+    #[test]
+    fn interval_map() {
+        assert_eq!(Some(&0), PLANES.get(&('a' as u32)));
+        assert_eq!(Some(&1), PLANES.get(&('🦀' as u32)));
+        assert_eq!(Some(&14), PLANES.get(&0xE_0001));
+        assert_eq!(None, PLANES.get(&0x3_0000));
+        assert_eq!(4, PLANES.len());
+    }
+
+    #[test]
+    fn interner() {
+        for i in 0..100 {
+            let s = format!("symbol{}", i);
+            let symbol = INTERNER.intern(&s).unwrap();
+            assert_eq!(i, symbol.as_u32());
+            assert_eq!(s, INTERNER.resolve(symbol));
+        }
+        assert_eq!(None, INTERNER.intern("symbol100"));
+    }
+
+    #[test]
+    fn array_map() {
+        assert_eq!(20, ARRAY_MAP.len());
+        for i in 0..20 {
+            assert_eq!(i * 2, ARRAY_MAP[&i]);
+        }
+        assert!(!ARRAY_MAP.contains_key(&20));
+    }
+
+    #[test]
+    fn char_map() {
+        for (i, c) in "abc\u{3b1}\u{3b2}\u{1f600}".chars().enumerate() {
+            assert_eq!(i as u32, CHAR_MAP[&c]);
+        }
+        assert!(!CHAR_MAP.contains_key(&'d'));
+        assert!(!CHAR_MAP.contains_key(&'\u{1f601}'));
+    }
+
+    #[test]
+    fn keyword_enum() {
+        assert_eq!(Some(&Keyword::Loop), KEYWORD_ENUM.get("loop"));
+        assert_eq!(Some(&Keyword::TypeOf), KEYWORD_ENUM.get("type-of"));
+        assert_eq!(Some(&Keyword::SelfType), KEYWORD_ENUM.get("Self"));
+        assert_eq!(None, KEYWORD_ENUM.get("self"));
+        for (s, keyword) in &KEYWORD_ENUM {
+            assert_eq!(*s, keyword.as_str());
+        }
+        assert_eq!("continue", Keyword::Continue.as_str());
+    }
+
+    #[test]
+    fn shared_values_map() {
+        assert_eq!(&[1; 4], SHARED_VALUES_MAP["one"]);
+        assert_eq!(&[0; 4], SHARED_VALUES_MAP["zero"]);
+        assert!(std::ptr::eq(
+            SHARED_VALUES_MAP["zero"],
+            SHARED_VALUES_MAP["none"]
+        ));
+        assert!(std::ptr::eq(
+            SHARED_VALUES_MAP["zero"],
+            SHARED_VALUES_MAP["nil"]
+        ));
+        assert!(!SHARED_VALUES_MAP.contains_key("two"));
+    }
+
+    #[test]
+    fn sorted_map() {
+        for i in 0..100 {
+            assert_eq!(i, SORTED_MAP[&*format!("key{}", i)]);
+        }
+        assert!(!SORTED_MAP.contains_key("key100"));
+        assert!(!SORTED_MAP.contains_key(""));
+        assert_eq!(100, SORTED_MAP.entries().count());
+    }
+
+    #[test]
+    fn fallback_set() {
+        for i in 0..100 {
+            assert!(FALLBACK_SET.contains(&i));
+        }
+        assert!(!FALLBACK_SET.contains(&100));
+    }
+
+    #[test]
+    fn c_lookup() {
+        let lookup = |key: &[u8]| unsafe {
+            let value = phf_codegen_test_lookup(key.as_ptr(), key.len());
+            value.as_ref().copied()
+        };
+        assert_eq!(Some(42), lookup(b"key42"));
+        assert_eq!(None, lookup(b"key100"));
+        assert_eq!(None, lookup(b"key\xff"));
+        assert!(unsafe { phf_codegen_test_lookup(std::ptr::null(), 0) }.is_null());
+    }
+
+    #[test]
+    fn duration_map() {
+        use std::time::Duration;
+
+        assert_eq!("short", DURATION_MAP[&Duration::from_secs(5)]);
+        assert_eq!("retry", DURATION_MAP[&Duration::from_millis(1500)]);
+        assert!(!DURATION_MAP.contains_key(&Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn option_map() {
+        let subtype = String::from("a");
+        assert_eq!(Some(&0), OPTION_MAP.get(&None));
+        assert_eq!(Some(&1), OPTION_MAP.get(&Some(&*subtype)));
+        assert_eq!(Some(&2), OPTION_MAP.get(&Some("")));
+        assert!(!OPTION_MAP.contains_key(&Some("b")));
+    }
+
+    #[test]
+    fn definition_order_map() {
+        assert!(DEFINITION_ORDER_MAP.keys().copied().eq((0..100).rev()));
+        assert!(DEFINITION_ORDER_MAP
+            .values()
+            .rev()
+            .copied()
+            .eq((0..100).map(|i| i * 2)));
+        assert_eq!(Some(&42), DEFINITION_ORDER_MAP.get(&21));
+    }
+
+    #[test]
+    fn generic_value_map() {
+        assert_eq!("one", GENERIC_VALUE_MAP[&1].0);
+        assert_eq!("two", GENERIC_VALUE_MAP[&2].0);
+    }
+
+    #[test]
+    fn nested_map() {
+        assert_eq!(Some(&1), NESTED_MAP["small"].get(&1));
+        assert_eq!(Some(&102), NESTED_MAP["large"].get(&2));
+        assert_eq!(None, NESTED_MAP["large"].get(&3));
+        assert!(NESTED_MAP["empty"].is_empty());
+    }
+
+    #[test]
+    fn duplicates_last() {
+        assert_eq!(
+            vec![(&"colour", &"never"), (&"jobs", &"1")],
+            SETTINGS.entries().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn packed_map() {
+        assert_eq!(5000, PACKED_MAP.len());
+        for i in 0..5000 {
+            assert_eq!(Some(&(i * 3)), PACKED_MAP.get(&i));
+        }
+        assert!(!PACKED_MAP.contains_key(&5000));
+        assert!(PACKED_SET.contains("foo"));
+        assert!(PACKED_SET.contains("bar"));
+        assert!(!PACKED_SET.contains("baz"));
+    }
+
+    #[test]
+    fn raw_map() {
+        assert_eq!(50, RAW_MAP.len());
+        for i in 0..50 {
+            assert_eq!(i + 1, RAW_MAP[&i]);
+        }
+        assert!(!RAW_MAP.contains_key(&50));
+    }
+
+    #[test]
+    fn keyed_map() {
+        for i in 0..100 {
+            assert_eq!(i, KEYED_MAP[&i]);
+        }
+        assert!(!KEYED_MAP.contains_key(&100));
+        assert_ne!(LARGE_MAP.key >> 64, KEYED_MAP.key >> 64);
+    }
+
+    #[test]
+    fn length_bucketed_map() {
+        assert_eq!(32, KEYWORDS.len());
+        assert_eq!(Some(&0), KEYWORDS.get("as"));
+        assert_eq!(Some(&3), KEYWORDS.get("continue"));
+        assert_eq!(Some(&31), KEYWORDS.get("while"));
+        assert_eq!(None, KEYWORDS.get(""));
+        assert_eq!(None, KEYWORDS.get("async"));
+        assert_eq!(None, KEYWORDS.get("continues"));
+        let lens = KEYWORDS.keys().map(|k| k.len()).collect::<Vec<_>>();
+        let mut sorted = lens.clone();
+        sorted.sort();
+        assert_eq!(sorted, lens);
+    }
+
+    #[test]
+    fn sharded_map() {
+        for map in &[&SHARDED_MAP, &SPLIT_SHARDED_MAP] {
+            assert_eq!(1000, map.len());
+            assert_eq!(4, map.shards.len());
+            for i in 0..1000 {
+                assert_eq!(i + 1, map[&i]);
+            }
+            assert!(!map.contains_key(&1000));
+            assert_eq!(1000, map.entries().count());
+        }
+    }
+
+    #[test]
+    fn chunked_map() {
+        assert_eq!(1000, CHUNKED_MAP.len());
+        for i in 0..1000 {
+            assert_eq!(i + 1, CHUNKED_MAP[&i]);
+        }
+        assert!(!CHUNKED_MAP.contains_key(&1000));
+        assert_eq!(1000, CHUNKED_MAP.entries().count());
+        assert_eq!(1000, CHUNKED_MAP.entries().rev().count());
+        assert_eq!(Ok(()), CHUNKED_MAP.verify());
+
+        assert_eq!(Some(&&[1; 4]), CHUNKED_SHARED_MAP.get(&3));
+        assert_eq!(Some(&&[0; 4]), CHUNKED_SHARED_MAP.get(&8));
+    }
+
     #[test]
     fn set() {
         assert!(SET.contains(&1));
@@ -33,6 +341,43 @@ mod test {
         );
     }
 
+    #[test]
+    fn slot_ordered_map() {
+        for i in 0..100 {
+            assert_eq!(i * 3, SLOT_ORDERED_MAP[&i]);
+            assert_eq!(Some(i as usize), SLOT_ORDERED_MAP.get_index(&i));
+            assert_eq!(Some((&i, &(i * 3))), SLOT_ORDERED_MAP.index(i as usize));
+        }
+        assert!(!SLOT_ORDERED_MAP.contains_key(&100));
+        assert!(SLOT_ORDERED_MAP.keys().cloned().eq(0..100));
+        assert!(SLOT_ORDERED_MAP.keys().rev().cloned().eq((0..100).rev()));
+        assert_eq!(100, SLOT_ORDERED_MAP.order.len());
+        assert_eq!(None, SLOT_ORDERED_MAP.entries_slice(..));
+    }
+
+    #[test]
+    fn bit_set() {
+        for b in 0..=255u8 {
+            assert_eq!(b"aeiouAEIOU".contains(&b), BIT_SET.contains(&b));
+        }
+        assert_eq!(10, BIT_SET.len());
+        assert_eq!(b"AEIOUaeiou".to_vec(), BIT_SET.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn signed_bit_set() {
+        assert!(SIGNED_BIT_SET.contains(&-300));
+        assert!(SIGNED_BIT_SET.contains(&-1));
+        assert!(SIGNED_BIT_SET.contains(&0));
+        assert!(SIGNED_BIT_SET.contains(&200));
+        assert!(!SIGNED_BIT_SET.contains(&1));
+        assert!(!SIGNED_BIT_SET.contains(&i16::MIN));
+        assert_eq!(
+            vec![200, 0, -1, -300],
+            SIGNED_BIT_SET.iter().rev().collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn ordered_set() {
         assert!(ORDERED_SET.contains(&1));
@@ -70,10 +415,10 @@ mod test {
 
     #[test]
     fn uncased_map() {
-        assert_eq!("a", UNCASED_MAP[&UncasedStr::new("AbC")]);
-        assert_eq!("a", UNCASED_MAP[&UncasedStr::new("abc")]);
-        assert_eq!("b", UNCASED_MAP[&UncasedStr::new("DEf")]);
-        assert!(!UNCASED_MAP.contains_key(&UncasedStr::new("XyZ")));
+        assert_eq!("a", UNCASED_MAP[UncasedStr::new("AbC")]);
+        assert_eq!("a", UNCASED_MAP[UncasedStr::new("abc")]);
+        assert_eq!("b", UNCASED_MAP[UncasedStr::new("DEf")]);
+        assert!(!UNCASED_MAP.contains_key(UncasedStr::new("XyZ")));
     }
 
     #[test]
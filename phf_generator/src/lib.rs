@@ -1,27 +1,142 @@
 #![doc(html_root_url = "https://docs.rs/phf_generator/0.10")]
-use phf_shared::{HashKey, PhfHash};
+use phf_shared::{HashFn, HashKey, Hashes, PhfHash, CHAR_PAGE_BITS};
 use rand::distributions::Standard;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::iter;
+#[cfg(feature = "strict")]
+use unicode_normalization::UnicodeNormalization;
 
 const DEFAULT_LAMBDA: usize = 5;
 
 const FIXED_SEED: u64 = 1234567890;
 
+/// The parameters of a generated perfect hash function.
+///
+/// Tables of at most `DEFAULT_LAMBDA` entries would only have a single
+/// bucket, so they are meant to be searched linearly instead: `disps` is
+/// empty and `map` is the identity in that case.
 pub struct HashState {
     pub key: HashKey,
+    pub hasher: HashFn,
     pub disps: Vec<(u32, u32)>,
     pub map: Vec<usize>,
 }
 
+/// The largest table for which `default_hash_fn` picks `HashFn::Sip64`.
+pub const SIP64_MAX_LEN: usize = 1 << 16;
+
+/// Returns the hash function to use for a table of `len` entries when none
+/// was chosen explicitly.
+///
+/// Tables of up to `SIP64_MAX_LEN` entries don't need the full 128-bit
+/// SipHash output.
+pub fn default_hash_fn(len: usize) -> HashFn {
+    if len <= SIP64_MAX_LEN {
+        HashFn::Sip64
+    } else {
+        HashFn::Sip
+    }
+}
+
+/// Generates a perfect hash function for `entries` using SipHash.
 pub fn generate_hash<H: PhfHash>(entries: &[H]) -> HashState {
+    generate_hash_with(entries, HashFn::Sip)
+}
+
+/// Generates a perfect hash function for `entries` using the given hash
+/// function.
+pub fn generate_hash_with<H: PhfHash>(entries: &[H], hasher: HashFn) -> HashState {
+    search_hash(entries, hasher, 0, fixed_keys()).expect("failed to solve PHF")
+}
+
+/// Like `generate_hash_with`, but derives the hash key from `key` rather
+/// than from a fixed, publicly known seed.
+///
+/// `key` itself is used if it yields a perfect hash function, otherwise keys
+/// generated from it are tried. Tables too small to be hashed keep `key`
+/// although it is never used.
+pub fn generate_hash_seeded<H: PhfHash>(entries: &[H], hasher: HashFn, key: HashKey) -> HashState {
+    search_hash(entries, hasher, key, seeded_keys(key)).expect("failed to solve PHF")
+}
+
+/// Like `generate_hash_with`, or `generate_hash_seeded` if `key` is given,
+/// but gives up after trying `attempts` hash keys.
+///
+/// The other functions keep trying keys until one works, which never ends
+/// for a pathological set of entries, e.g. with a weak hash function.
+pub fn try_generate_hash<H: PhfHash>(
+    entries: &[H],
+    hasher: HashFn,
+    key: Option<HashKey>,
+    attempts: usize,
+) -> Option<HashState> {
+    match key {
+        Some(key) => search_hash(entries, hasher, key, seeded_keys(key).take(attempts)),
+        None => search_hash(entries, hasher, 0, fixed_keys().take(attempts)),
+    }
+}
+
+/// Returns whether `state` is a perfect hash function for `entries` with the
+/// shape `generate_hash` gives it, e.g. to check one which was saved by an
+/// earlier build.
+pub fn verify_hash<H: PhfHash>(entries: &[H], state: &HashState) -> bool {
+    // `usize::div_ceil` is newer than our MSRV
+    #[allow(clippy::manual_div_ceil)]
+    let buckets_len = if entries.len() <= DEFAULT_LAMBDA {
+        0
+    } else {
+        (entries.len() + DEFAULT_LAMBDA - 1) / DEFAULT_LAMBDA
+    };
+    if state.map.len() != entries.len() || state.disps.len() != buckets_len {
+        return false;
+    }
+    if state.disps.is_empty() {
+        // small tables are searched linearly in definition order
+        return state.map.iter().enumerate().all(|(slot, &idx)| slot == idx);
+    }
+    entries.iter().enumerate().all(|(idx, entry)| {
+        let hashes = phf_shared::hash_with(entry, &state.key, state.hasher);
+        let slot = phf_shared::get_index(&hashes, &state.disps, entries.len()) as usize;
+        state.map[slot] == idx
+    })
+}
+
+fn fixed_keys() -> impl Iterator<Item = HashKey> {
     SmallRng::seed_from_u64(FIXED_SEED)
-        .sample_iter(Standard)
-        .find_map(|key| try_generate_hash(entries, key))
-        .expect("failed to solve PHF")
+        .sample_iter::<u64, _>(Standard)
+        .map(HashKey::from)
+}
+
+fn seeded_keys(key: HashKey) -> impl Iterator<Item = HashKey> {
+    let seed = (key >> 64) as u64 ^ key as u64;
+    iter::once(key).chain(SmallRng::seed_from_u64(seed).sample_iter(Standard))
+}
+
+fn search_hash<H: PhfHash>(
+    entries: &[H],
+    hasher: HashFn,
+    small_key: HashKey,
+    mut keys: impl Iterator<Item = HashKey>,
+) -> Option<HashState> {
+    if entries.len() <= DEFAULT_LAMBDA {
+        return Some(HashState {
+            key: small_key,
+            hasher,
+            disps: vec![],
+            map: (0..entries.len()).collect(),
+        });
+    }
+
+    keys.find_map(|key| try_generate_hash_key(entries, key, hasher))
 }
 
-fn try_generate_hash<H: PhfHash>(entries: &[H], key: HashKey) -> Option<HashState> {
+fn try_generate_hash_key<H: PhfHash>(
+    entries: &[H],
+    key: HashKey,
+    hasher: HashFn,
+) -> Option<HashState> {
     struct Bucket {
         idx: usize,
         keys: Vec<usize>,
@@ -29,9 +144,11 @@ fn try_generate_hash<H: PhfHash>(entries: &[H], key: HashKey) -> Option<HashStat
 
     let hashes: Vec<_> = entries
         .iter()
-        .map(|entry| phf_shared::hash(entry, &key))
+        .map(|entry| phf_shared::hash_with(entry, &key, hasher))
         .collect();
 
+    // `usize::div_ceil` is newer than our MSRV
+    #[allow(clippy::manual_div_ceil)]
     let buckets_len = (hashes.len() + DEFAULT_LAMBDA - 1) / DEFAULT_LAMBDA;
     let mut buckets = (0..buckets_len)
         .map(|i| Bucket {
@@ -46,6 +163,20 @@ fn try_generate_hash<H: PhfHash>(entries: &[H], key: HashKey) -> Option<HashStat
             .push(i);
     }
 
+    // No displacement separates keys whose `f1` and `f2` are equal, so give
+    // up on this key right away rather than trying all of them.
+    for bucket in &buckets {
+        let mut fs = bucket
+            .keys
+            .iter()
+            .map(|&i| (hashes[i].f1, hashes[i].f2))
+            .collect::<Vec<_>>();
+        fs.sort_unstable();
+        if fs.windows(2).any(|w| w[0] == w[1]) {
+            return None;
+        }
+    }
+
     // Sort descending
     buckets.sort_by(|a, b| a.keys.len().cmp(&b.keys.len()).reverse());
 
@@ -99,7 +230,464 @@ fn try_generate_hash<H: PhfHash>(entries: &[H], key: HashKey) -> Option<HashStat
 
     Some(HashState {
         key,
+        hasher,
         disps,
         map: map.into_iter().map(|i| i.unwrap()).collect(),
     })
 }
+
+/// Quality metrics of a generated hash function, see `analyze`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// The largest displacement of any bucket, the larger of its two
+    /// displacements.
+    pub max_displacement: u32,
+    /// The number of buckets with each displacement, indexed by
+    /// displacement.
+    pub displacement_histogram: Vec<usize>,
+    /// The number of entries in the fullest bucket.
+    pub max_bucket_len: usize,
+    /// The number of buckets with each number of entries, indexed by number
+    /// of entries.
+    pub bucket_len_histogram: Vec<usize>,
+    /// The fraction of buckets which hold at least one entry.
+    pub bucket_occupancy: f64,
+}
+
+/// Computes quality metrics of `state`, so that several generated hash
+/// functions for the same entries can be compared.
+///
+/// `hashes` are the hashes of the entries, from `phf_shared::hash_with`
+/// with the key and hash function of `state`. Lower displacements mean less
+/// searching during generation, but not faster lookups: every lookup
+/// computes a single index. Unevenly loaded buckets, with a high
+/// `max_bucket_len` and a low `bucket_occupancy`, are what make generation
+/// slow. Tables which are searched linearly have no buckets.
+///
+/// ```
+/// let keys = (0..1000u32).collect::<Vec<_>>();
+/// let state = phf_generator::generate_hash(&keys);
+/// let hashes = keys
+///     .iter()
+///     .map(|k| phf_shared::hash_with(k, &state.key, state.hasher))
+///     .collect::<Vec<_>>();
+/// let analysis = phf_generator::analyze(&state, &hashes);
+///
+/// let histogram = &analysis.bucket_len_histogram;
+/// assert_eq!(state.disps.len(), histogram.iter().sum::<usize>());
+/// assert_eq!(1000, histogram.iter().enumerate().map(|(len, n)| len * n).sum::<usize>());
+/// assert_eq!(analysis.max_bucket_len + 1, histogram.len());
+/// let empty = histogram[0] as f64 / state.disps.len() as f64;
+/// assert_eq!(1.0 - empty, analysis.bucket_occupancy);
+/// ```
+pub fn analyze(state: &HashState, hashes: &[Hashes]) -> Analysis {
+    if state.disps.is_empty() {
+        return Analysis {
+            max_displacement: 0,
+            displacement_histogram: vec![],
+            max_bucket_len: 0,
+            bucket_len_histogram: vec![],
+            bucket_occupancy: 1.0,
+        };
+    }
+
+    let displacements = state.disps.iter().map(|&(d1, d2)| d1.max(d2));
+    let max_displacement = displacements.clone().max().unwrap_or(0);
+    let mut displacement_histogram = vec![0; max_displacement as usize + 1];
+    for d in displacements {
+        displacement_histogram[d as usize] += 1;
+    }
+
+    let mut bucket_lens = vec![0; state.disps.len()];
+    for hashes in hashes {
+        bucket_lens[(hashes.g % state.disps.len() as u32) as usize] += 1;
+    }
+    let max_bucket_len = bucket_lens.iter().copied().max().unwrap_or(0);
+    let mut bucket_len_histogram = vec![0; max_bucket_len + 1];
+    for &len in &bucket_lens {
+        bucket_len_histogram[len] += 1;
+    }
+    let empty = bucket_len_histogram[0];
+
+    Analysis {
+        max_displacement,
+        displacement_histogram,
+        max_bucket_len,
+        bucket_len_histogram,
+        bucket_occupancy: 1.0 - empty as f64 / bucket_lens.len() as f64,
+    }
+}
+
+/// Displacements packed as varints, see `phf_shared::get_packed_disps`.
+pub struct PackedDisps {
+    pub offsets: Vec<u32>,
+    pub bytes: Vec<u8>,
+}
+
+/// Packs `disps` as varints.
+///
+/// Most displacements of a large table are far smaller than the table, so
+/// this takes much less space than pairs of the narrowest integer type which
+/// fits the largest displacement, at the cost of decoding a few pairs in
+/// each lookup.
+pub fn pack_disps(disps: &[(u32, u32)]) -> PackedDisps {
+    let mut offsets = vec![];
+    let mut bytes = vec![];
+    for (i, &(d1, d2)) in disps.iter().enumerate() {
+        if i % phf_shared::PACKED_DISPS_BLOCK == 0 {
+            offsets.push(bytes.len() as u32);
+        }
+        for &d in &[d1, d2] {
+            let mut d = d;
+            while d >= 0x80 {
+                bytes.push((d & 0x7f) as u8 | 0x80);
+                d >>= 7;
+            }
+            bytes.push(d as u8);
+        }
+    }
+    PackedDisps { offsets, bytes }
+}
+
+/// The parameters of a table sorted by hash, which is searched with a binary
+/// search rather than indexed by a perfect hash function.
+///
+/// Like `HashState`, tables of at most `DEFAULT_LAMBDA` entries are meant to
+/// be searched linearly: `hashes` is empty and `map` is the identity.
+pub struct SortedState {
+    pub key: HashKey,
+    pub hasher: HashFn,
+    /// The `phf_shared::sort_key` of each entry, in ascending order.
+    pub hashes: Vec<u64>,
+    /// The index of the entry of each hash.
+    pub map: Vec<usize>,
+}
+
+/// Sorts `entries` by hash using the given hash function, deriving the hash
+/// key from `key` if given.
+///
+/// This is the fallback for entries without a perfect hash function. Only
+/// distinct sort keys are needed, so the first hash key almost always works.
+pub fn generate_sorted<H: PhfHash>(
+    entries: &[H],
+    hasher: HashFn,
+    key: Option<HashKey>,
+) -> SortedState {
+    let small_key = key.unwrap_or(0);
+    if entries.len() <= DEFAULT_LAMBDA {
+        return SortedState {
+            key: small_key,
+            hasher,
+            hashes: vec![],
+            map: (0..entries.len()).collect(),
+        };
+    }
+
+    let keys: Box<dyn Iterator<Item = HashKey>> = match key {
+        Some(key) => Box::new(seeded_keys(key)),
+        None => Box::new(fixed_keys()),
+    };
+    keys.take(MAX_SORT_ATTEMPTS)
+        .find_map(|key| {
+            let mut sorted = entries
+                .iter()
+                .map(|entry| phf_shared::sort_key(&phf_shared::hash_with(entry, &key, hasher)))
+                .zip(0..)
+                .collect::<Vec<_>>();
+            sorted.sort_unstable();
+            if sorted.windows(2).any(|w| w[0].0 == w[1].0) {
+                return None;
+            }
+            let (hashes, map) = sorted.into_iter().unzip();
+            Some(SortedState {
+                key,
+                hasher,
+                hashes,
+                map,
+            })
+        })
+        .expect("failed to sort keys by hash")
+}
+
+// 64-bit hashes of distinct keys essentially never collide, so failing this
+// often means the hash function ignores the key
+const MAX_SORT_ATTEMPTS: usize = 64;
+
+/// The largest number of bits `generate_bit_set` will use.
+pub const MAX_BIT_SET_LEN: u64 = 1 << 16;
+
+/// The parameters of a generated bit set.
+///
+/// Bit `i` of `words[i / 64]` represents the value with bit index
+/// `offset + i`.
+pub struct BitSetState {
+    pub offset: u64,
+    pub words: Vec<u64>,
+}
+
+/// Generates a bit set of the given bit indices.
+///
+/// Returns `None` if the indices span more than `MAX_BIT_SET_LEN` bits.
+pub fn generate_bit_set(indices: &[u64]) -> Option<BitSetState> {
+    let offset = indices.iter().cloned().min().unwrap_or(0);
+    let max = indices.iter().cloned().max().unwrap_or(0);
+    if max - offset >= MAX_BIT_SET_LEN {
+        return None;
+    }
+
+    let mut words = vec![];
+    if !indices.is_empty() {
+        words.resize((max - offset) as usize / 64 + 1, 0u64);
+    }
+    for &index in indices {
+        let bit = index - offset;
+        words[(bit / 64) as usize] |= 1 << (bit % 64);
+    }
+
+    Some(BitSetState { offset, words })
+}
+
+/// Why a set of ranges can't form an interval map.
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntervalError {
+    /// The range at this index ends before it starts.
+    Empty(usize),
+    /// The ranges at these indices overlap.
+    Overlap(usize, usize),
+}
+
+/// Sorts the given inclusive ranges by their start.
+///
+/// Returns the indices of the ranges in ascending order.
+pub fn generate_intervals<K: Ord>(bounds: &[(K, K)]) -> Result<Vec<usize>, IntervalError> {
+    if let Some(i) = bounds.iter().position(|(start, end)| start > end) {
+        return Err(IntervalError::Empty(i));
+    }
+    let mut order = (0..bounds.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| bounds[a].0.cmp(&bounds[b].0));
+    for pair in order.windows(2) {
+        if bounds[pair[0]].1 >= bounds[pair[1]].0 {
+            return Err(IntervalError::Overlap(pair[0], pair[1]));
+        }
+    }
+    Ok(order)
+}
+
+/// Two keys which are distinct, but only differ by ASCII case or Unicode
+/// normalization.
+///
+/// Requires the `strict` feature.
+#[cfg(feature = "strict")]
+pub struct NearDuplicate {
+    /// The index of the key written first.
+    pub first: usize,
+    /// The index of the key written second.
+    pub second: usize,
+    /// Whether the NFC normal forms of the keys differ by ASCII case.
+    pub ascii_case: bool,
+    /// Whether the keys differ by more than ASCII case, i.e. by Unicode
+    /// normalization.
+    pub normalization: bool,
+}
+
+#[cfg(feature = "strict")]
+impl NearDuplicate {
+    /// Returns how the keys differ, to complete "differ only by".
+    pub fn reason(&self) -> &'static str {
+        match (self.ascii_case, self.normalization) {
+            (true, true) => "ASCII case and Unicode normalization",
+            (true, false) => "ASCII case",
+            _ => "Unicode normalization",
+        }
+    }
+}
+
+/// Returns the first pair of `keys` which only differ by ASCII case or
+/// Unicode normalization, which is almost always a mistake in the input.
+///
+/// Keys which are equal are not reported. Requires the `strict` feature.
+#[cfg(feature = "strict")]
+pub fn find_near_duplicate<K: AsRef<str>>(keys: &[K]) -> Option<NearDuplicate> {
+    let mut folded = HashMap::new();
+    for (second, key) in keys.iter().enumerate() {
+        let key = AsRef::<str>::as_ref(key);
+        let nfc = key.nfc().collect::<String>();
+        let fold = nfc.to_ascii_lowercase();
+        match folded.get(&fold) {
+            Some(&first) => {
+                let other = AsRef::<str>::as_ref(&keys[first]);
+                if other == key {
+                    continue;
+                }
+                return Some(NearDuplicate {
+                    first,
+                    second,
+                    ascii_case: !other.nfc().eq(nfc.chars()),
+                    normalization: !other.eq_ignore_ascii_case(key),
+                });
+            }
+            None => {
+                folded.insert(fold, second);
+            }
+        }
+    }
+    None
+}
+
+/// A node of a generated trie.
+pub struct TrieNode {
+    /// The index of the key which ends at this node.
+    pub entry: Option<usize>,
+    /// The children of this node, sorted by byte.
+    pub edges: Vec<(u8, usize)>,
+}
+
+/// Generates a byte-wise trie of the given keys, which must be distinct.
+///
+/// The root is the first node, and there is always a root.
+pub fn generate_trie<K: AsRef<[u8]>>(keys: &[K]) -> Vec<TrieNode> {
+    let mut nodes = vec![TrieNode {
+        entry: None,
+        edges: vec![],
+    }];
+    for (i, key) in keys.iter().enumerate() {
+        let mut node = 0;
+        for &byte in key.as_ref() {
+            node = match nodes[node].edges.binary_search_by_key(&byte, |&(b, _)| b) {
+                Ok(edge) => nodes[node].edges[edge].1,
+                Err(edge) => {
+                    let child = nodes.len();
+                    nodes[node].edges.insert(edge, (byte, child));
+                    nodes.push(TrieNode {
+                        entry: None,
+                        edges: vec![],
+                    });
+                    child
+                }
+            };
+        }
+        debug_assert!(nodes[node].entry.is_none(), "duplicate key");
+        nodes[node].entry = Some(i);
+    }
+    nodes
+}
+
+/// The tables of a generated two-level char map.
+///
+/// Char `c` is in page `c >> CHAR_PAGE_BITS`, and its slot is
+/// `blocks[pages[page] * page_len + (c & (page_len - 1))]`, which is one
+/// more than the index of its key, or zero if it isn't a key. Pages beyond
+/// the last key aren't stored, and pages without keys share a block.
+pub struct CharTable {
+    /// The block of each page.
+    pub pages: Vec<u16>,
+    /// The slots of each block, one per char of a page.
+    pub blocks: Vec<u16>,
+}
+
+/// Generates a two-level table of the given chars, which must be distinct.
+///
+/// Returns `None` if there are more than `u16::MAX - 1` chars.
+pub fn generate_char_table(keys: &[char]) -> Option<CharTable> {
+    if keys.len() >= usize::from(u16::MAX) {
+        return None;
+    }
+    let page_len = 1 << CHAR_PAGE_BITS;
+    let pages_len = keys
+        .iter()
+        .map(|&c| (c as usize >> CHAR_PAGE_BITS) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut slots = vec![vec![0u16; page_len]; pages_len];
+    for (i, &c) in keys.iter().enumerate() {
+        let c = c as usize;
+        slots[c >> CHAR_PAGE_BITS][c & (page_len - 1)] = i as u16 + 1;
+    }
+
+    // identical pages, in practice those without keys, share a block
+    let mut block_of = HashMap::new();
+    let mut blocks = vec![];
+    let pages = slots
+        .into_iter()
+        .map(|page| {
+            *block_of.entry(page).or_insert_with_key(|page| {
+                blocks.extend_from_slice(page);
+                (blocks.len() / page_len - 1) as u16
+            })
+        })
+        .collect();
+    Some(CharTable { pages, blocks })
+}
+
+/// The number of filter bits per key generated by `generate_filter`, before
+/// rounding up to a power of two.
+pub const FILTER_BITS_PER_KEY: usize = 16;
+
+/// Generates a companion filter of the given hashes for fast negative
+/// lookups, see `phf_shared::filter_contains`.
+///
+/// The filter is a two-probe Bloom filter of at least `FILTER_BITS_PER_KEY`
+/// bits per key, which rejects roughly 99% of missing keys.
+pub fn generate_filter(hashes: &[Hashes]) -> Vec<u64> {
+    let bits = (hashes.len() * FILTER_BITS_PER_KEY)
+        .next_power_of_two()
+        .max(64);
+    let mut filter = vec![0u64; bits / 64];
+    for hashes in hashes {
+        for &bit in &phf_shared::filter_bits(hashes, bits) {
+            filter[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+    filter
+}
+
+/// The `HashKey` used to pick the shard of each entry.
+///
+/// This is deliberately not a key `generate_hash` would try, so that the
+/// keys within a shard don't all land in the same few buckets.
+const SHARD_KEY: HashKey = 0x9e37_79b9_7f4a_7c15;
+
+/// The assignment of entries to the shards of a sharded map.
+pub struct ShardState {
+    pub key: HashKey,
+    pub hasher: HashFn,
+    /// The indices of the entries in each shard.
+    pub shards: Vec<Vec<usize>>,
+}
+
+/// Splits `entries` into `shards` shards using the given hash function.
+///
+/// # Panics
+///
+/// Panics if `shards` is zero.
+pub fn generate_shards<H: PhfHash>(entries: &[H], shards: usize, hasher: HashFn) -> ShardState {
+    generate_shards_seeded(entries, shards, hasher, 0)
+}
+
+/// Like `generate_shards`, but derives the hash key from `key`.
+///
+/// The shards themselves should be generated with `generate_hash_seeded`
+/// and the same `key`; this uses a different key derived from it.
+///
+/// # Panics
+///
+/// Panics if `shards` is zero.
+pub fn generate_shards_seeded<H: PhfHash>(
+    entries: &[H],
+    shards: usize,
+    hasher: HashFn,
+    key: HashKey,
+) -> ShardState {
+    assert!(shards > 0, "a sharded map needs at least one shard");
+
+    let mut state = ShardState {
+        key: key ^ SHARD_KEY,
+        hasher,
+        shards: vec![vec![]; shards],
+    };
+    for (i, entry) in entries.iter().enumerate() {
+        let hashes = phf_shared::hash_with(entry, &state.key, hasher);
+        state.shards[phf_shared::get_shard(&hashes, shards) as usize].push(i);
+    }
+    state
+}
@@ -1,11 +1,9 @@
-#![feature(test)]
-
-extern crate test;
+use criterion::{criterion_group, criterion_main, Criterion};
 
 mod map {
+    use criterion::{black_box, Criterion};
     use phf::phf_map;
     use std::collections::{BTreeMap, HashMap};
-    use test::Bencher;
 
     macro_rules! map_and_match {
         ($map:ident, $f:ident, $($key:expr => $value:expr,)+) => {
@@ -51,79 +49,150 @@ mod map {
         "zucchini" => 25,
     }
 
-    #[bench]
-    fn bench_match_some(b: &mut Bencher) {
-        b.iter(|| {
-            assert_eq!(match_get("zucchini").unwrap(), 25);
-        })
+    static SHORT_KEY_MAP: phf::Map<&'static str, usize> = phf_map! {
+        @hasher = short_key;
+        "apple" => 0,
+        "banana" => 1,
+        "carrot" => 2,
+        "doughnut" => 3,
+        "eggplant" => 4,
+        "frankincene" => 5,
+        "grapes" => 6,
+        "haggis" => 7,
+        "ice cream" => 8,
+        "jelly beans" => 9,
+        "kaffir lime leaves" => 10,
+        "lemonade" => 11,
+        "mashmallows" => 12,
+        "nectarines" => 13,
+        "oranges" => 14,
+        "pineapples" => 15,
+        "quinoa" => 16,
+        "rosemary" => 17,
+        "sourdough" => 18,
+        "tomatoes" => 19,
+        "unleavened bread" => 20,
+        "vanilla" => 21,
+        "watermelon" => 22,
+        "xinomavro grapes" => 23,
+        "yogurt" => 24,
+        "zucchini" => 25,
+    };
+
+    pub fn bench_match_some(c: &mut Criterion) {
+        c.bench_function("match_some", |b| {
+            b.iter(|| {
+                assert_eq!(match_get(black_box("zucchini")).unwrap(), 25);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_match_none(b: &mut Bencher) {
-        b.iter(|| {
-            assert_eq!(match_get("potato"), None);
-        })
+    pub fn bench_match_none(c: &mut Criterion) {
+        c.bench_function("match_none", |b| {
+            b.iter(|| {
+                assert_eq!(match_get(black_box("potato")), None);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_btreemap_some(b: &mut Bencher) {
+    pub fn bench_btreemap_some(c: &mut Criterion) {
         let mut map = BTreeMap::new();
         for (key, value) in MAP.entries() {
             map.insert(*key, *value);
         }
 
-        b.iter(|| {
-            assert_eq!(map.get("zucchini").unwrap(), &25);
-        })
+        c.bench_function("btreemap_some", |b| {
+            b.iter(|| {
+                assert_eq!(map.get(black_box("zucchini")).unwrap(), &25);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_hashmap_some(b: &mut Bencher) {
+    pub fn bench_hashmap_some(c: &mut Criterion) {
         let mut map = HashMap::new();
         for (key, value) in MAP.entries() {
             map.insert(*key, *value);
         }
 
-        b.iter(|| {
-            assert_eq!(map.get("zucchini").unwrap(), &25);
-        })
+        c.bench_function("hashmap_some", |b| {
+            b.iter(|| {
+                assert_eq!(map.get(black_box("zucchini")).unwrap(), &25);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_phf_some(b: &mut Bencher) {
-        b.iter(|| {
-            assert_eq!(MAP.get("zucchini").unwrap(), &25);
-        })
+    pub fn bench_phf_some(c: &mut Criterion) {
+        c.bench_function("phf_some", |b| {
+            b.iter(|| {
+                assert_eq!(MAP.get(black_box("zucchini")).unwrap(), &25);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_btreemap_none(b: &mut Bencher) {
+    pub fn bench_phf_short_key_some(c: &mut Criterion) {
+        c.bench_function("phf_short_key_some", |b| {
+            b.iter(|| {
+                assert_eq!(SHORT_KEY_MAP.get(black_box("zucchini")).unwrap(), &25);
+            })
+        });
+    }
+
+    pub fn bench_btreemap_none(c: &mut Criterion) {
         let mut map = BTreeMap::new();
         for (key, value) in MAP.entries() {
             map.insert(*key, *value);
         }
 
-        b.iter(|| {
-            assert_eq!(map.get("potato"), None);
-        })
+        c.bench_function("btreemap_none", |b| {
+            b.iter(|| {
+                assert_eq!(map.get(black_box("potato")), None);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_hashmap_none(b: &mut Bencher) {
+    pub fn bench_hashmap_none(c: &mut Criterion) {
         let mut map = HashMap::new();
         for (key, value) in MAP.entries() {
             map.insert(*key, *value);
         }
 
-        b.iter(|| {
-            assert_eq!(map.get("potato"), None);
-        })
+        c.bench_function("hashmap_none", |b| {
+            b.iter(|| {
+                assert_eq!(map.get(black_box("potato")), None);
+            })
+        });
+    }
+
+    pub fn bench_phf_short_key_none(c: &mut Criterion) {
+        c.bench_function("phf_short_key_none", |b| {
+            b.iter(|| {
+                assert_eq!(SHORT_KEY_MAP.get(black_box("potato")), None);
+            })
+        });
     }
 
-    #[bench]
-    fn bench_phf_none(b: &mut Bencher) {
-        b.iter(|| {
-            assert_eq!(MAP.get("potato"), None);
-        })
+    pub fn bench_phf_none(c: &mut Criterion) {
+        c.bench_function("phf_none", |b| {
+            b.iter(|| {
+                assert_eq!(MAP.get(black_box("potato")), None);
+            })
+        });
     }
 }
+
+fn map(c: &mut Criterion) {
+    map::bench_match_some(c);
+    map::bench_match_none(c);
+    map::bench_btreemap_some(c);
+    map::bench_hashmap_some(c);
+    map::bench_phf_some(c);
+    map::bench_btreemap_none(c);
+    map::bench_hashmap_none(c);
+    map::bench_phf_none(c);
+    map::bench_phf_short_key_some(c);
+    map::bench_phf_short_key_none(c);
+}
+
+criterion_group!(benches, map);
+
+criterion_main!(benches);
@@ -1,21 +1,29 @@
 // FIXME: Remove `extern crate` below when we bump MSRV to 1.42 or higher.
 extern crate proc_macro;
 
-use phf_generator::HashState;
-use phf_shared::PhfHash;
+use phf_generator::{HashState, IntervalError};
+use phf_shared::{HashFn, HashKey, PhfBitIndex, PhfHash};
 use proc_macro::TokenStream;
-use quote::quote;
-use std::collections::HashSet;
-use std::hash::Hasher;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use syn::ext::IdentExt;
 use syn::parse::{self, Parse, ParseStream};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 #[cfg(feature = "unicase")]
 use syn::ExprLit;
-use syn::{parse_macro_input, Error, Expr, Lit, Token, UnOp};
+use syn::{parse_macro_input, Error, Expr, Ident, Lit, Token, UnOp};
 #[cfg(feature = "unicase")]
 use unicase_::UniCase;
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization_::UnicodeNormalization;
 
-#[derive(Hash, PartialEq, Eq, Clone)]
+#[derive(Hash, PartialEq, Eq, PartialOrd, Clone)]
 enum ParsedKey {
     Str(String),
     Binary(Vec<u8>),
@@ -160,6 +168,26 @@ impl ParsedKey {
     }
 }
 
+impl ParsedKey {
+    /// Returns the index of the bit representing this key in a `phf::BitSet`.
+    fn bit_index(&self) -> Option<u64> {
+        match self {
+            ParsedKey::Char(s) => Some(s.bit_index()),
+            ParsedKey::I8(s) => Some(s.bit_index()),
+            ParsedKey::I16(s) => Some(s.bit_index()),
+            ParsedKey::I32(s) => Some(s.bit_index()),
+            ParsedKey::I64(s) => Some(s.bit_index()),
+            ParsedKey::U8(s) => Some(s.bit_index()),
+            ParsedKey::U16(s) => Some(s.bit_index()),
+            ParsedKey::U32(s) => Some(s.bit_index()),
+            ParsedKey::U64(s) => Some(s.bit_index()),
+            ParsedKey::Bool(s) => Some(s.bit_index()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
 struct Key {
     parsed: ParsedKey,
     expr: Expr,
@@ -178,12 +206,56 @@ impl Parse for Key {
     fn parse(input: ParseStream<'_>) -> parse::Result<Key> {
         let expr = input.parse()?;
         let parsed = ParsedKey::from_expr(&expr)
-            .ok_or_else(|| Error::new_spanned(&expr, "unsupported key expression"))?;
+            .ok_or_else(|| Error::new_spanned(&expr, unsupported_key_message(&expr)))?;
 
         Ok(Key { parsed, expr })
     }
 }
 
+/// Explains why `expr` can't be used as a key.
+fn unsupported_key_message(expr: &Expr) -> String {
+    let message = match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(s) if s.suffix().is_empty() => {
+                return format!(
+                    "integer keys need a type suffix, e.g. `{}u32`",
+                    s.base10_digits()
+                )
+            }
+            Lit::Int(s) => {
+                return format!(
+                    "`{}` keys are not supported, since their size depends on the target",
+                    s.suffix()
+                )
+            }
+            Lit::Float(_) => "float keys are not supported, since floats don't implement `Eq`",
+            _ => "unsupported literal key",
+        },
+        Expr::Group(group) => return unsupported_key_message(&group.expr),
+        Expr::Unary(unary) => match unary.op {
+            UnOp::Neg(_) => "only signed integer keys, with a type suffix, can be negative",
+            _ => "keys must be literals",
+        },
+        Expr::Array(_) => "array keys must consist of `u8` literals",
+        Expr::Path(_) => {
+            "keys must be literals, since the macro can't evaluate constants; \
+             use `phf_codegen` to build maps with computed keys"
+        }
+        Expr::Struct(_) | Expr::Tuple(_) => {
+            "struct and tuple keys are not supported; keys must be literals"
+        }
+        #[cfg(not(feature = "unicase"))]
+        Expr::Call(_) => "`UniCase` keys require the `unicase` feature",
+        #[cfg(feature = "unicase")]
+        Expr::Call(_) => {
+            "call keys must be `UniCase::ascii` or `UniCase::unicode` of a string literal"
+        }
+        _ => "keys must be literals",
+    };
+    message.to_string()
+}
+
+#[derive(Clone)]
 struct Entry {
     key: Key,
     value: Expr,
@@ -207,34 +279,645 @@ impl Parse for Entry {
     }
 }
 
-struct Map(Vec<Entry>);
+/// How the entries of a `phf::Map` are laid out in memory.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// A single array of `(key, value)` pairs.
+    Pairs,
+    /// Parallel arrays of keys and values.
+    Split,
+}
+
+/// Which data structure `phf_map!` generates.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    /// A single `phf::Map`.
+    Chd,
+    /// A `phf::LengthMap` of per-length `phf::Map`s.
+    LengthBucketed,
+    /// A `phf::CharMap`, a two-level table of `char` keys.
+    TwoLevel,
+}
+
+/// What to do with an entry whose key was already given.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Duplicates {
+    /// Reject the entry.
+    Error,
+    /// Keep the value of the last entry, at the position of the first.
+    Last,
+    /// Keep the first entry, with a warning about the ignored one.
+    First,
+}
+
+/// Generation options given as a prelude of `@name = value;` items, or
+/// `@name;` for flags.
+struct Options {
+    layout: Layout,
+    // the alignment of padded `(key, value)` pairs
+    align: Option<u32>,
+    // `None` picks a hash function by table size
+    hasher: Option<HashFn>,
+    // `None` uses the fixed default seed
+    key: Option<HashKey>,
+    strategy: Strategy,
+    shards: Option<usize>,
+    duplicates: Duplicates,
+    fingerprints: bool,
+    filter: bool,
+    slot_order: bool,
+    definition_order: bool,
+    normalize_nfc: bool,
+    strict: bool,
+    sorted: bool,
+    // the path to the `phf` crate in the emitted code
+    crate_path: syn::Path,
+    names: Vec<Ident>,
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream<'_>) -> parse::Result<Options> {
+        let mut options = Options {
+            layout: Layout::Pairs,
+            align: None,
+            hasher: None,
+            key: None,
+            strategy: Strategy::Chd,
+            shards: None,
+            duplicates: Duplicates::Error,
+            fingerprints: false,
+            filter: false,
+            slot_order: false,
+            definition_order: false,
+            normalize_nfc: false,
+            strict: false,
+            sorted: false,
+            crate_path: syn::parse_quote!(phf),
+            names: vec![],
+        };
+
+        while input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            // `crate` is a keyword
+            let name = input.call(Ident::parse_any)?;
+            if name == "crate" {
+                input.parse::<Token![=]>()?;
+                options.crate_path = input.call(syn::Path::parse_mod_style)?;
+            } else if name == "layout" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<Ident>()?;
+                options.layout = if value == "pairs" {
+                    Layout::Pairs
+                } else if value == "split" {
+                    Layout::Split
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unknown layout, expected `pairs` or `split`",
+                    ));
+                };
+            } else if name == "align" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::LitInt>()?;
+                let align = value.base10_parse::<u32>()?;
+                if ![16, 32, 64, 128].contains(&align) {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unsupported alignment, expected 16, 32, 64 or 128",
+                    ));
+                }
+                options.align = Some(align);
+            } else if name == "hasher" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<Ident>()?;
+                options.hasher = Some(if value == "sip" {
+                    HashFn::Sip
+                } else if value == "sip64" {
+                    HashFn::Sip64
+                } else if value == "short_key" {
+                    HashFn::ShortKey
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unknown hasher, expected `sip`, `sip64` or `short_key`",
+                    ));
+                });
+            } else if name == "key" {
+                input.parse::<Token![=]>()?;
+                options.key = Some(input.parse::<syn::LitInt>()?.base10_parse::<HashKey>()?);
+            } else if name == "strategy" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<Ident>()?;
+                options.strategy = if value == "chd" {
+                    Strategy::Chd
+                } else if value == "length_bucketed" {
+                    Strategy::LengthBucketed
+                } else if value == "two_level" {
+                    Strategy::TwoLevel
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unknown strategy, expected `chd`, `length_bucketed` or `two_level`",
+                    ));
+                };
+            } else if name == "shards" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::LitInt>()?;
+                let shards = value.base10_parse::<usize>()?;
+                if shards == 0 {
+                    return Err(Error::new_spanned(value, "expected at least one shard"));
+                }
+                options.shards = Some(shards);
+            } else if name == "duplicates" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<Ident>()?;
+                options.duplicates = if value == "error" {
+                    Duplicates::Error
+                } else if value == "last" {
+                    Duplicates::Last
+                } else if value == "first" {
+                    Duplicates::First
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unknown duplicate key policy, expected `error`, `last` or `first`",
+                    ));
+                };
+            } else if name == "fingerprints" {
+                options.fingerprints = true;
+            } else if name == "filter" {
+                options.filter = true;
+            } else if name == "slot_order" {
+                options.slot_order = true;
+            } else if name == "definition_order" {
+                options.definition_order = true;
+            } else if name == "sorted" {
+                options.sorted = true;
+            } else if name == "strict" {
+                if cfg!(not(feature = "unicode-normalization")) {
+                    return Err(Error::new_spanned(
+                        name,
+                        "`@strict` requires the `unicode-normalization` feature",
+                    ));
+                }
+                options.strict = true;
+            } else if name == "normalize" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<Ident>()?;
+                if value != "nfc" {
+                    return Err(Error::new_spanned(
+                        value,
+                        "unknown normalization form, expected `nfc`",
+                    ));
+                }
+                if cfg!(not(feature = "unicode-normalization")) {
+                    return Err(Error::new_spanned(
+                        name,
+                        "`@normalize` requires the `unicode-normalization` feature",
+                    ));
+                }
+                options.normalize_nfc = true;
+            } else {
+                return Err(Error::new_spanned(name, "unknown option"));
+            }
+            input.parse::<Token![;]>()?;
+            options.names.push(name);
+        }
+
+        if options.align.is_some() && options.layout == Layout::Split {
+            let name = options.names.iter().find(|name| *name == "align").unwrap();
+            return Err(Error::new_spanned(
+                name,
+                "`@align` pads `(key, value)` pairs and can't be combined with `@layout = split;`",
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+impl Options {
+    /// Returns the hash function to use for a table of `len` entries.
+    fn hasher_for(&self, len: usize) -> HashFn {
+        self.hasher
+            .unwrap_or_else(|| phf_generator::default_hash_fn(len))
+    }
+
+    /// Generates the hash of `entries` with the configured hasher and key.
+    ///
+    /// With `PHF_MACROS_CACHE_DIR` set, the result is saved in that directory
+    /// and reused by later expansions of the same entries.
+    fn generate_hash(&self, entries: &[Entry]) -> HashState {
+        let hasher = self.hasher_for(entries.len());
+        let cache = cache_path(entries, hasher, self.key);
+        if let Some(state) = cache
+            .as_ref()
+            .and_then(|path| read_cache(path, entries, hasher))
+        {
+            return state;
+        }
+        let state = match self.key {
+            Some(key) => phf_generator::generate_hash_seeded(entries, hasher, key),
+            None => phf_generator::generate_hash_with(entries, hasher),
+        };
+        // small tables take no search, so they aren't worth a file
+        if let (Some(path), false) = (cache, state.disps.is_empty()) {
+            write_cache(&path, &state);
+        }
+        state
+    }
+
+    /// Rejects any option not in `supported`, which is accepted by `macro_name`.
+    ///
+    /// `@crate` is supported by every macro.
+    fn check_supported(&self, supported: &[&str], macro_name: &str) -> parse::Result<()> {
+        for name in &self.names {
+            if name != "crate" && !supported.iter().any(|s| name == s) {
+                return Err(Error::new_spanned(
+                    name,
+                    format!("option `{}` is not supported by `{}!`", name, macro_name),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Map {
+    options: Options,
+    entries: Vec<Entry>,
+    // duplicate keys dropped by `@duplicates = first;`
+    ignored: Vec<Key>,
+    // a `_ => value` entry
+    default: Option<(Token![_], Expr)>,
+}
 
 impl Parse for Map {
     fn parse(input: ParseStream<'_>) -> parse::Result<Map> {
-        let parsed = Punctuated::<Entry, Token![,]>::parse_terminated(input)?;
-        let map = parsed.into_iter().collect::<Vec<_>>();
-        check_duplicates(&map)?;
-        Ok(Map(map))
+        let options = input.parse::<Options>()?;
+        let mut entries = vec![];
+        let mut default = None;
+        while !input.is_empty() {
+            if input.peek(Token![_]) {
+                let underscore = input.parse::<Token![_]>()?;
+                if default.is_some() {
+                    return Err(Error::new_spanned(underscore, "duplicate default entry"));
+                }
+                input.parse::<Token![=>]>()?;
+                default = Some((underscore, input.parse()?));
+            } else {
+                entries.push(input.parse()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if options.normalize_nfc {
+            normalize_nfc(&mut entries)?;
+        }
+        let ignored = resolve_duplicates(&mut entries, options.duplicates)?;
+        if options.strict {
+            check_near_duplicates(&entries)?;
+        }
+        Ok(Map {
+            options,
+            entries,
+            ignored,
+            default,
+        })
     }
 }
 
-struct Set(Vec<Entry>);
+impl Map {
+    /// Rejects a default entry, which only `phf_map!` accepts.
+    fn check_no_default(&self, macro_name: &str) -> parse::Result<()> {
+        match &self.default {
+            Some((underscore, _)) => Err(Error::new_spanned(
+                underscore,
+                format!("a default entry is not supported by `{}!`", macro_name),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Set {
+    options: Options,
+    entries: Vec<Entry>,
+    // duplicate keys dropped by `@duplicates = first;`
+    ignored: Vec<Key>,
+}
 
 impl Parse for Set {
     fn parse(input: ParseStream<'_>) -> parse::Result<Set> {
+        let options = input.parse::<Options>()?;
         let parsed = Punctuated::<Key, Token![,]>::parse_terminated(input)?;
-        let set = parsed
+        let mut entries = parsed
             .into_iter()
             .map(|key| Entry {
                 key,
                 value: syn::parse_str("()").unwrap(),
             })
             .collect::<Vec<_>>();
-        check_duplicates(&set)?;
-        Ok(Set(set))
+        if options.normalize_nfc {
+            normalize_nfc(&mut entries)?;
+        }
+        let ignored = resolve_duplicates(&mut entries, options.duplicates)?;
+        if options.strict {
+            check_near_duplicates(&entries)?;
+        }
+        Ok(Set {
+            options,
+            entries,
+            ignored,
+        })
+    }
+}
+
+/// A variant of a `phf_enum!` and its string.
+struct EnumVariant {
+    attrs: Vec<syn::Attribute>,
+    ident: Ident,
+    name: syn::LitStr,
+}
+
+impl Parse for EnumVariant {
+    fn parse(input: ParseStream<'_>) -> parse::Result<EnumVariant> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let ident = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let name = input.parse()?;
+        Ok(EnumVariant { attrs, ident, name })
+    }
+}
+
+struct Enum {
+    options: Options,
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: Ident,
+    variants: Vec<EnumVariant>,
+}
+
+impl Parse for Enum {
+    fn parse(input: ParseStream<'_>) -> parse::Result<Enum> {
+        let options = input.parse()?;
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let ident = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        let variants = Punctuated::<EnumVariant, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut names = HashSet::new();
+        for variant in &variants {
+            if !names.insert(variant.name.value()) {
+                return Err(Error::new_spanned(&variant.name, "duplicate key"));
+            }
+        }
+        Ok(Enum {
+            options,
+            attrs,
+            vis,
+            ident,
+            variants,
+        })
+    }
+}
+
+/// The type of the bounds of a `phf_interval_map!` key.
+#[derive(Clone)]
+enum BoundKind {
+    /// An integer literal with the given suffix, if any.
+    Int(String),
+    Char,
+}
+
+/// An entry of `phf_interval_map!`, whose key is a range.
+struct IntervalEntry {
+    range: Expr,
+    kind: BoundKind,
+    start: i128,
+    // inclusive, even for `start..end`
+    end: i128,
+    value: Expr,
+}
+
+/// Parses an integer or char literal bound of a range.
+fn parse_bound(expr: &Expr) -> Option<(i128, BoundKind)> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(s) => Some((
+                s.base10_parse::<i128>().ok()?,
+                BoundKind::Int(s.suffix().to_owned()),
+            )),
+            Lit::Byte(s) => Some((i128::from(s.value()), BoundKind::Int("u8".to_owned()))),
+            Lit::Char(s) => Some((i128::from(u32::from(s.value())), BoundKind::Char)),
+            _ => None,
+        },
+        Expr::Unary(unary) => match unary.op {
+            UnOp::Neg(_) => match parse_bound(&unary.expr)? {
+                (value, BoundKind::Int(suffix)) => Some((-value, BoundKind::Int(suffix))),
+                _ => None,
+            },
+            _ => None,
+        },
+        Expr::Group(group) => parse_bound(&group.expr),
+        _ => None,
+    }
+}
+
+/// Emits a bound parsed by `parse_bound`.
+fn build_bound(value: i128, kind: &BoundKind) -> proc_macro2::TokenStream {
+    let span = proc_macro2::Span::call_site();
+    match kind {
+        BoundKind::Int(suffix) => {
+            let abs =
+                syn::LitInt::new(&format!("{}{}", value.wrapping_abs() as u128, suffix), span);
+            if value < 0 {
+                quote!(-#abs)
+            } else {
+                quote!(#abs)
+            }
+        }
+        BoundKind::Char => {
+            let c = syn::LitChar::new(std::char::from_u32(value as u32).unwrap(), span);
+            quote!(#c)
+        }
+    }
+}
+
+impl Parse for IntervalEntry {
+    fn parse(input: ParseStream<'_>) -> parse::Result<IntervalEntry> {
+        let range = input.parse::<Expr>()?;
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+
+        let (from, limits, to) = match &range {
+            Expr::Range(syn::ExprRange {
+                from: Some(from),
+                limits,
+                to: Some(to),
+                ..
+            }) => (from, limits, to),
+            _ => {
+                return Err(Error::new_spanned(
+                    range,
+                    "expected a range `start..=end` or `start..end`",
+                ))
+            }
+        };
+        let unsupported = |expr| Error::new_spanned(expr, "unsupported range bound");
+        let (start, kind) = parse_bound(from).ok_or_else(|| unsupported(from))?;
+        let (mut end, _) = parse_bound(to).ok_or_else(|| unsupported(to))?;
+        if let syn::RangeLimits::HalfOpen(_) = limits {
+            end -= 1;
+            // skip back over the surrogates, which aren't chars
+            if let BoundKind::Char = kind {
+                if end == 0xDFFF {
+                    end = 0xD7FF;
+                }
+            }
+        }
+
+        Ok(IntervalEntry {
+            range,
+            kind,
+            start,
+            end,
+            value,
+        })
+    }
+}
+
+struct IntervalMap {
+    options: Options,
+    entries: Vec<IntervalEntry>,
+}
+
+impl Parse for IntervalMap {
+    fn parse(input: ParseStream<'_>) -> parse::Result<IntervalMap> {
+        let options = input.parse()?;
+        let parsed = Punctuated::<IntervalEntry, Token![,]>::parse_terminated(input)?;
+        let entries = parsed.into_iter().collect();
+        Ok(IntervalMap { options, entries })
     }
 }
 
+/// Returns the file caching the hash of `entries`, if `PHF_MACROS_CACHE_DIR`
+/// is set.
+///
+/// The name hashes everything the search depends on, including the version
+/// of this crate, since the search may change between versions.
+fn cache_path(entries: &[Entry], hasher: HashFn, key: Option<HashKey>) -> Option<PathBuf> {
+    let dir = env::var_os("PHF_MACROS_CACHE_DIR")?;
+    let mut state = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut state);
+    hasher.hash(&mut state);
+    key.hash(&mut state);
+    for entry in entries {
+        entry.key.parsed.hash(&mut state);
+    }
+    Some(PathBuf::from(dir).join(format!("{:016x}.phf", state.finish())))
+}
+
+/// Reads a hash saved by `write_cache`, if it is a perfect hash function of
+/// `entries`.
+///
+/// Anything unexpected, from a missing file to a hash collision of the file
+/// names, is a cache miss.
+fn read_cache(path: &Path, entries: &[Entry], hasher: HashFn) -> Option<HashState> {
+    let text = fs::read_to_string(path).ok()?;
+    let mut lines = text.lines();
+    let key = lines.next()?.parse().ok()?;
+    let disps = lines
+        .next()?
+        .split_whitespace()
+        .map(|pair| {
+            let mut split = pair.split(',');
+            let d1 = split.next()?.parse().ok()?;
+            let d2 = split.next()?.parse().ok()?;
+            Some((d1, d2))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let map = lines
+        .next()?
+        .split_whitespace()
+        .map(|idx| idx.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let state = HashState {
+        key,
+        hasher,
+        disps,
+        map,
+    };
+    if phf_generator::verify_hash(entries, &state) {
+        Some(state)
+    } else {
+        None
+    }
+}
+
+/// Saves `state` for `read_cache`.
+///
+/// This is best effort: the expansion is the same either way, so failures
+/// are ignored.
+fn write_cache(path: &Path, state: &HashState) {
+    let disps = state
+        .disps
+        .iter()
+        .map(|(d1, d2)| format!("{},{}", d1, d2))
+        .collect::<Vec<_>>();
+    let map = state
+        .map
+        .iter()
+        .map(|idx| idx.to_string())
+        .collect::<Vec<_>>();
+    let text = format!("{}\n{}\n{}\n", state.key, disps.join(" "), map.join(" "));
+    // concurrent builds may write the same file, so each writes its own and
+    // renames it into place
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    if fs::create_dir_all(path.parent().unwrap()).is_ok() && fs::write(&tmp, text).is_ok() {
+        let _ = fs::rename(&tmp, path);
+    }
+}
+
+/// Replaces each string key with its NFC normal form, both for hashing and
+/// in the emitted code, so that lookups with normalized strings find it.
+#[cfg(feature = "unicode-normalization")]
+fn normalize_nfc(entries: &mut [Entry]) -> parse::Result<()> {
+    for entry in entries {
+        let normalized = match &entry.key.parsed {
+            ParsedKey::Str(s) => s.nfc().collect::<String>(),
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "normalized keys must be string literals",
+                ))
+            }
+        };
+        let lit = syn::LitStr::new(&normalized, entry.key.expr.span());
+        entry.key.expr = syn::parse_quote!(#lit);
+        entry.key.parsed = ParsedKey::Str(normalized);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "unicode-normalization"))]
+fn normalize_nfc(_: &mut [Entry]) -> parse::Result<()> {
+    // `@normalize` is rejected while parsing the options
+    unreachable!()
+}
+
+#[cfg(not(feature = "unicode-normalization"))]
+fn check_near_duplicates(_: &[Entry]) -> parse::Result<()> {
+    // `@strict` is rejected while parsing the options
+    unreachable!()
+}
+
 fn check_duplicates(entries: &[Entry]) -> parse::Result<()> {
     let mut keys = HashSet::new();
     for entry in entries {
@@ -245,9 +928,278 @@ fn check_duplicates(entries: &[Entry]) -> parse::Result<()> {
     Ok(())
 }
 
-fn build_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
+/// Applies `duplicates` to entries whose key was already given, returning the
+/// keys of any entries dropped with a warning.
+fn resolve_duplicates(entries: &mut Vec<Entry>, duplicates: Duplicates) -> parse::Result<Vec<Key>> {
+    if duplicates == Duplicates::Error {
+        check_duplicates(entries)?;
+        return Ok(vec![]);
+    }
+    let mut ignored = vec![];
+    let mut resolved = Vec::<Entry>::with_capacity(entries.len());
+    let mut slots = HashMap::new();
+    for entry in entries.drain(..) {
+        match slots.get(&entry.key.parsed) {
+            None => {
+                slots.insert(entry.key.parsed.clone(), resolved.len());
+                resolved.push(entry);
+            }
+            // the key keeps its first position, so that iteration order
+            // doesn't depend on which entries were overridden
+            Some(&slot) if duplicates == Duplicates::Last => resolved[slot].value = entry.value,
+            Some(_) => ignored.push(entry.key),
+        }
+    }
+    *entries = resolved;
+    Ok(ignored)
+}
+
+/// Wraps `expr` in a block which warns about each of the `ignored` keys.
+///
+/// Proc macros can't emit warnings on stable, so this uses a deprecated
+/// constant spanned to each key instead.
+fn warn_ignored(ignored: &[Key], expr: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if ignored.is_empty() {
+        return expr;
+    }
+    let warnings = ignored.iter().map(|key| {
+        let note = format!(
+            "duplicate key `{}` is ignored, since `@duplicates = first;` keeps the first entry",
+            key.expr.to_token_stream()
+        );
+        let name = quote_spanned!(key.expr.span()=> DUPLICATE_KEY);
+        quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                const DUPLICATE_KEY: () = ();
+                #name
+            };
+        }
+    });
+    quote!({
+        #(#warnings)*
+        #expr
+    })
+}
+
+/// Rejects string keys which only differ by ASCII case or Unicode
+/// normalization.
+#[cfg(feature = "unicode-normalization")]
+fn check_near_duplicates(entries: &[Entry]) -> parse::Result<()> {
+    let strings = entries
+        .iter()
+        .filter_map(|entry| match &entry.key.parsed {
+            ParsedKey::Str(s) => Some((entry, s)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let keys = strings.iter().map(|&(_, s)| s).collect::<Vec<_>>();
+    let dup = match phf_generator::find_near_duplicate(&keys) {
+        Some(dup) => dup,
+        None => return Ok(()),
+    };
+    // a second error for the first key would be mangled by the expression
+    // position of the macro, so the message names both keys
+    let (_, first_key) = strings[dup.first];
+    let (second, second_key) = strings[dup.second];
+    Err(Error::new_spanned(
+        &second.key.expr,
+        format!(
+            "key {:?} differs from {:?} only by {}",
+            second_key,
+            first_key,
+            dup.reason()
+        ),
+    ))
+}
+
+/// Rejects entries whose keys aren't in ascending order.
+fn check_sorted(entries: &[Entry]) -> parse::Result<()> {
+    for pair in entries.windows(2) {
+        if pair[0].key.parsed >= pair[1].key.parsed {
+            return Err(Error::new_spanned(
+                &pair[1].key.expr,
+                "keys must be in ascending order",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn build_hasher(hasher: HashFn, phf: &syn::Path) -> proc_macro2::TokenStream {
+    match hasher {
+        HashFn::Sip => quote!(#phf::HashFn::Sip),
+        HashFn::Sip64 => quote!(#phf::HashFn::Sip64),
+        HashFn::ShortKey => quote!(#phf::HashFn::ShortKey),
+    }
+}
+
+/// Emits the displacements using the narrowest integer type which fits them.
+fn build_disps(disps: &[(u32, u32)], phf: &syn::Path) -> proc_macro2::TokenStream {
+    let max = disps.iter().map(|&(d1, d2)| d1.max(d2)).max().unwrap_or(0);
+    if max <= u32::from(u8::MAX) {
+        let disps = disps.iter().map(|&(d1, d2)| {
+            let (d1, d2) = (d1 as u8, d2 as u8);
+            quote!((#d1, #d2))
+        });
+        quote!(#phf::map::Disps::U8(&[#(#disps),*]))
+    } else if max <= u32::from(u16::MAX) {
+        let disps = disps.iter().map(|&(d1, d2)| {
+            let (d1, d2) = (d1 as u16, d2 as u16);
+            quote!((#d1, #d2))
+        });
+        quote!(#phf::map::Disps::U16(&[#(#disps),*]))
+    } else {
+        let disps = disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
+        quote!(#phf::map::Disps::U32(&[#(#disps),*]))
+    }
+}
+
+fn build_map(entries: &[Entry], state: HashState, options: &Options) -> proc_macro2::TokenStream {
+    let phf = &options.crate_path;
+    let key = state.key;
+    let hasher = build_hasher(state.hasher, phf);
+    let disps = build_disps(&state.disps, phf);
+    let slots = match (options.layout, options.align) {
+        (Layout::Pairs, Some(align)) => {
+            let variant = format_ident!("A{}", align);
+            let entries = state.map.iter().map(|&idx| {
+                let key = &entries[idx].key.expr;
+                let value = &entries[idx].value;
+                quote!(#phf::map::Aligned { align: [], value: (#key, #value) })
+            });
+            quote! {
+                #phf::map::Slots::Aligned(#phf::map::AlignedSlots::#variant(&[#(#entries),*]))
+            }
+        }
+        (Layout::Pairs, None) => {
+            let entries = state.map.iter().map(|&idx| {
+                let key = &entries[idx].key.expr;
+                let value = &entries[idx].value;
+                quote!((#key, #value))
+            });
+            quote!(#phf::map::Slots::Pairs(&[#(#entries),*]))
+        }
+        (Layout::Split, _) => {
+            let keys = state.map.iter().map(|&idx| &entries[idx].key.expr);
+            let values = state.map.iter().map(|&idx| &entries[idx].value);
+            quote!(#phf::map::Slots::Split(&[#(#keys),*], &[#(#values),*]))
+        }
+    };
+
+    // linearly searched maps never hash their keys, so fingerprints and
+    // filters are useless
+    let hashes = if (options.fingerprints || options.filter) && !state.disps.is_empty() {
+        state
+            .map
+            .iter()
+            .map(|&idx| phf_shared::hash_with(&entries[idx], &state.key, state.hasher))
+            .collect()
+    } else {
+        vec![]
+    };
+    let fingerprints = if options.fingerprints {
+        hashes.iter().map(phf_shared::fingerprint).collect()
+    } else {
+        vec![]
+    };
+    let filter = if options.filter && !hashes.is_empty() {
+        phf_generator::generate_filter(&hashes)
+    } else {
+        vec![]
+    };
+
+    // linearly searched maps are already in definition order
+    let mut order = vec![];
+    if options.definition_order && !state.disps.is_empty() {
+        order = vec![0; entries.len()];
+        for (slot, &idx) in state.map.iter().enumerate() {
+            order[idx] = slot;
+        }
+    }
+
+    let side = if fingerprints.is_empty() && filter.is_empty() && order.is_empty() {
+        quote!(None)
+    } else {
+        quote! {
+            Some(&#phf::map::SideTables {
+                fingerprints: &[#(#fingerprints),*],
+                filter: &[#(#filter),*],
+                order: &[#(#order),*],
+            })
+        }
+    };
+
+    quote! {
+        #phf::Map {
+            key: #key,
+            hasher: #hasher,
+            disps: #disps,
+            entries: #slots,
+            side: #side,
+        }
+    }
+}
+
+fn build_ordered_map(
+    entries: &[Entry],
+    state: HashState,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let phf = &options.crate_path;
+    let key = state.key;
+    let hasher = build_hasher(state.hasher, phf);
+    let disps = build_disps(&state.disps, phf);
+    let idxs = state.map.iter().map(|idx| quote!(#idx));
+    // linearly searched maps are never probed, so keep them in definition order
+    let (order, entries) = if options.slot_order && !state.disps.is_empty() {
+        let mut order = vec![0; entries.len()];
+        for (slot, &idx) in state.map.iter().enumerate() {
+            order[idx] = slot;
+        }
+        let entries = state
+            .map
+            .iter()
+            .map(|&idx| &entries[idx])
+            .collect::<Vec<_>>();
+        (order, entries)
+    } else {
+        (vec![], entries.iter().collect())
+    };
+    let entries = entries.iter().map(|entry| {
+        let key = &entry.key.expr;
+        let value = &entry.value;
+        quote!((#key, #value))
+    });
+
+    quote! {
+        #phf::OrderedMap {
+            key: #key,
+            hasher: #hasher,
+            disps: #disps,
+            idxs: &[#(#idxs),*],
+            entries: &[#(#entries),*],
+            order: &[#(#order),*],
+        }
+    }
+}
+
+fn build_array_map(
+    entries: &[Entry],
+    state: HashState,
+    phf: &syn::Path,
+) -> proc_macro2::TokenStream {
     let key = state.key;
-    let disps = state.disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
+    let hasher = build_hasher(state.hasher, phf);
+    let buckets = state.disps.len();
+    // the displacements are padded to the length of the entries
+    let disps = state
+        .disps
+        .iter()
+        .copied()
+        .chain(std::iter::repeat((0, 0)))
+        .take(entries.len())
+        .map(|(d1, d2)| quote!((#d1, #d2)));
     let entries = state.map.iter().map(|&idx| {
         let key = &entries[idx].key.expr;
         let value = &entries[idx].value;
@@ -255,30 +1207,363 @@ fn build_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
     });
 
     quote! {
-        phf::Map {
+        #phf::ArrayMap {
             key: #key,
-            disps: &[#(#disps),*],
+            hasher: #hasher,
+            buckets: #buckets,
+            disps: [#(#disps),*],
+            entries: [#(#entries),*],
+        }
+    }
+}
+
+fn build_enum(def: &Enum) -> proc_macro2::TokenStream {
+    let Enum {
+        attrs, vis, ident, ..
+    } = def;
+    // the table maps each string to the index of its variant
+    let entries = def
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(i, variant)| {
+            let name = &variant.name;
+            Entry {
+                key: Key {
+                    parsed: ParsedKey::Str(name.value()),
+                    expr: syn::parse_quote!(#name),
+                },
+                value: syn::parse_quote!(#i),
+            }
+        })
+        .collect::<Vec<_>>();
+    let state = def.options.generate_hash(&entries);
+    let table = build_map(&entries, state, &def.options);
+    let phf = &def.options.crate_path;
+
+    let variants = def.variants.iter().map(|variant| {
+        let attrs = &variant.attrs;
+        let ident = &variant.ident;
+        quote!(#(#attrs)* #ident)
+    });
+    let idents = def.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let names = def.variants.iter().map(|v| &v.name);
+    let idxs = 0..def.variants.len();
+    let from_idx = idents.iter().enumerate().map(|(i, variant)| {
+        quote!(::core::option::Option::Some(&#i) => ::core::result::Result::Ok(#ident::#variant))
+    });
+
+    quote! {
+        #(#attrs)*
+        #vis enum #ident {
+            #(#variants,)*
+        }
+
+        impl #ident {
+            /// The variants of the enum, in the order they are declared in.
+            #vis const VARIANTS: &'static [#ident] = &[#(#ident::#idents),*];
+
+            /// The strings of the variants, in the order they are declared in.
+            #vis const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            /// Returns the string of the variant.
+            #vis const fn as_str(&self) -> &'static str {
+                Self::NAMES[match self {
+                    #(#ident::#idents => #idxs,)*
+                }]
+            }
+
+            /// Returns an iterator over the variants of the enum, in the
+            /// order they are declared in.
+            #vis fn iter() -> ::core::slice::Iter<'static, #ident> {
+                Self::VARIANTS.iter()
+            }
+        }
+
+        impl ::core::str::FromStr for #ident {
+            type Err = #phf::ParseEnumError;
+
+            fn from_str(s: &str) -> ::core::result::Result<#ident, #phf::ParseEnumError> {
+                static TABLE: #phf::Map<&'static str, usize> = #table;
+                match TABLE.get(s) {
+                    #(#from_idx,)*
+                    _ => ::core::result::Result::Err(#phf::ParseEnumError),
+                }
+            }
+        }
+    }
+}
+
+/// Replaces each string key with its ASCII lowercase form for hashing. The
+/// emitted keys are unchanged.
+fn fold_ascii_case(entries: &[Entry]) -> parse::Result<Vec<Entry>> {
+    let mut folded = entries.to_vec();
+    for entry in &mut folded {
+        entry.key.parsed = match &entry.key.parsed {
+            ParsedKey::Str(s) => ParsedKey::Str(s.to_ascii_lowercase()),
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "case insensitive map keys must be string literals",
+                ))
+            }
+        };
+    }
+    check_duplicates(&folded)?;
+    Ok(folded)
+}
+
+/// Replaces each string key with the bytes `UniCase::unicode` hashes it as,
+/// its full Unicode case folding, for hashing. The emitted keys are unchanged.
+#[cfg(feature = "caseless")]
+fn fold_unicode_case(entries: &[Entry]) -> parse::Result<Vec<Entry>> {
+    // collects the bytes written to it
+    struct Bytes(Vec<u8>);
+
+    impl Hasher for Bytes {
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+
+        fn finish(&self) -> u64 {
+            unreachable!()
+        }
+    }
+
+    let mut folded = entries.to_vec();
+    for entry in &mut folded {
+        let mut bytes = Bytes(vec![]);
+        match &entry.key.parsed {
+            ParsedKey::Str(s) => unicase_::UniCase::unicode(s).hash(&mut bytes),
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "caseless map keys must be string literals",
+                ))
+            }
+        }
+        // all hashers are streaming, so these bytes hash the same as the key
+        entry.key.parsed = ParsedKey::Binary(bytes.0);
+    }
+    check_duplicates(&folded)?;
+    Ok(folded)
+}
+
+fn build_interval_map(
+    entries: &[IntervalEntry],
+    phf: &syn::Path,
+) -> parse::Result<proc_macro2::TokenStream> {
+    let bounds = entries
+        .iter()
+        .map(|entry| (entry.start, entry.end))
+        .collect::<Vec<_>>();
+    let order = phf_generator::generate_intervals(&bounds).map_err(|err| match err {
+        IntervalError::Empty(i) => Error::new_spanned(&entries[i].range, "empty range"),
+        IntervalError::Overlap(_, i) => {
+            Error::new_spanned(&entries[i].range, "range overlaps another range")
+        }
+    })?;
+
+    let bounds = order.iter().map(|&i| {
+        let entry = &entries[i];
+        let start = build_bound(entry.start, &entry.kind);
+        let end = build_bound(entry.end, &entry.kind);
+        quote!((#start, #end))
+    });
+    let values = order.iter().map(|&i| &entries[i].value);
+
+    Ok(quote! {
+        #phf::IntervalMap {
+            bounds: &[#(#bounds),*],
+            values: &[#(#values),*],
+        }
+    })
+}
+
+fn build_trie_map(entries: &[Entry], phf: &syn::Path) -> parse::Result<proc_macro2::TokenStream> {
+    let mut keys = vec![];
+    for entry in entries {
+        let key = match (&entry.key.parsed, &entries[0].key.parsed) {
+            (ParsedKey::Str(s), ParsedKey::Str(_)) => s.as_bytes(),
+            (ParsedKey::Binary(b), ParsedKey::Binary(_)) => &b[..],
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "trie map keys must all be string literals or all be byte string literals",
+                ))
+            }
+        };
+        keys.push(key);
+    }
+
+    let nodes = phf_generator::generate_trie(&keys).into_iter().map(|node| {
+        let entry = match node.entry {
+            Some(i) => quote!(::core::option::Option::Some(#i)),
+            None => quote!(::core::option::Option::None),
+        };
+        let edges = node
+            .edges
+            .iter()
+            .map(|&(byte, child)| quote!((#byte, #child)));
+        quote!(#phf::trie_map::Node {
+            entry: #entry,
+            edges: &[#(#edges),*],
+        })
+    });
+    let entries = entries.iter().map(|entry| {
+        let key = &entry.key.expr;
+        let value = &entry.value;
+        quote!((#key, #value))
+    });
+
+    Ok(quote! {
+        #phf::TrieMap {
+            nodes: &[#(#nodes),*],
             entries: &[#(#entries),*],
         }
+    })
+}
+
+fn build_bit_set(entries: &[Entry], phf: &syn::Path) -> parse::Result<proc_macro2::TokenStream> {
+    let mut indices = vec![];
+    for entry in entries {
+        let index = entry.key.parsed.bit_index().ok_or_else(|| {
+            Error::new_spanned(&entry.key.expr, "unsupported key expression for a bit set")
+        })?;
+        indices.push(index);
     }
+    let state = phf_generator::generate_bit_set(&indices).ok_or_else(|| {
+        Error::new(
+            proc_macro2::Span::call_site(),
+            "keys span too many values for a bit set, use `phf_set!` instead",
+        )
+    })?;
+
+    let offset = state.offset;
+    let len = entries.len();
+    let words = &state.words;
+    // the keys themselves aren't stored, but they pin down the element type
+    let keys = entries.iter().map(|entry| &entry.key.expr);
+
+    Ok(quote! {
+        #phf::BitSet {
+            offset: #offset,
+            len: #len,
+            words: &[#(#words),*],
+            marker: #phf::bit_set::marker(&[#(#keys),*]),
+        }
+    })
 }
 
-fn build_ordered_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
-    let key = state.key;
-    let disps = state.disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
-    let idxs = state.map.iter().map(|idx| quote!(#idx));
+fn build_length_map(
+    entries: &[Entry],
+    options: &Options,
+) -> parse::Result<proc_macro2::TokenStream> {
+    let phf = &options.crate_path;
+    let mut lens = vec![];
+    for entry in entries {
+        match &entry.key.parsed {
+            ParsedKey::Str(s) => lens.push(s.len()),
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "the `length_bucketed` strategy requires string keys",
+                ))
+            }
+        }
+    }
+
+    let min_len = lens.iter().cloned().min().unwrap_or(0);
+    let max_len = lens.iter().cloned().max().unwrap_or(0);
+    let mut buckets = vec![
+        vec![];
+        if entries.is_empty() {
+            0
+        } else {
+            max_len - min_len + 1
+        }
+    ];
+    for (entry, len) in entries.iter().zip(lens) {
+        buckets[len - min_len].push(entry.clone());
+    }
+    let maps = buckets.iter().map(|entries| {
+        let state = options.generate_hash(entries);
+        build_map(entries, state, options)
+    });
+    let len = entries.len();
+
+    Ok(quote! {
+        #phf::LengthMap {
+            min_len: #min_len,
+            len: #len,
+            maps: &[#(#maps),*],
+        }
+    })
+}
+
+fn build_char_map(entries: &[Entry], phf: &syn::Path) -> parse::Result<proc_macro2::TokenStream> {
+    let mut keys = vec![];
+    for entry in entries {
+        match entry.key.parsed {
+            ParsedKey::Char(c) => keys.push(c),
+            _ => {
+                return Err(Error::new_spanned(
+                    &entry.key.expr,
+                    "the `two_level` strategy requires char keys",
+                ))
+            }
+        }
+    }
+    let table = phf_generator::generate_char_table(&keys).ok_or_else(|| {
+        Error::new(
+            proc_macro2::Span::call_site(),
+            "the `two_level` strategy supports at most 65534 entries",
+        )
+    })?;
+
+    let pages = &table.pages;
+    let blocks = &table.blocks;
     let entries = entries.iter().map(|entry| {
         let key = &entry.key.expr;
         let value = &entry.value;
         quote!((#key, #value))
     });
 
+    Ok(quote! {
+        #phf::CharMap {
+            pages: &[#(#pages),*],
+            blocks: &[#(#blocks),*],
+            entries: &[#(#entries),*],
+        }
+    })
+}
+
+fn build_sharded_map(
+    entries: &[Entry],
+    shards: usize,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let phf = &options.crate_path;
+    let hasher = options.hasher_for(entries.len());
+    let state = match options.key {
+        Some(key) => phf_generator::generate_shards_seeded(entries, shards, hasher, key),
+        None => phf_generator::generate_shards(entries, shards, hasher),
+    };
+    let key = state.key;
+    let hasher = build_hasher(state.hasher, phf);
+    let len = entries.len();
+    let shards = state.shards.iter().map(|idxs| {
+        let entries = idxs.iter().map(|&i| entries[i].clone()).collect::<Vec<_>>();
+        let state = options.generate_hash(&entries);
+        build_map(&entries, state, options)
+    });
+
     quote! {
-        phf::OrderedMap {
+        #phf::ShardedMap {
             key: #key,
-            disps: &[#(#disps),*],
-            idxs: &[#(#idxs),*],
-            entries: &[#(#entries),*],
+            hasher: #hasher,
+            len: #len,
+            shards: &[#(&#shards),*],
         }
     }
 }
@@ -286,33 +1571,340 @@ fn build_ordered_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenS
 #[::proc_macro_hack::proc_macro_hack]
 pub fn phf_map(input: TokenStream) -> TokenStream {
     let map = parse_macro_input!(input as Map);
-    let state = phf_generator::generate_hash(&map.0);
+    warn_ignored(&map.ignored, expand_map(&map)).into()
+}
 
-    build_map(&map.0, state).into()
+/// Builds the data structure picked by the options of a `phf_map!`.
+fn expand_map(map: &Map) -> proc_macro2::TokenStream {
+    // the `two_level` table has no hash function, and its keys are chars
+    let mut supported = if map.options.strategy == Strategy::TwoLevel {
+        vec!["strategy", "duplicates"]
+    } else {
+        vec![
+            "layout",
+            "align",
+            "hasher",
+            "key",
+            "strategy",
+            "shards",
+            "fingerprints",
+            "filter",
+            "definition_order",
+            "normalize",
+            "strict",
+            "duplicates",
+        ]
+    };
+    // `LengthMap` and `ShardedMap` iterate table by table, so only a single
+    // `Map` can keep the definition order
+    if map.options.strategy != Strategy::Chd || map.options.shards.is_some() {
+        supported.retain(|&name| name != "definition_order");
+    }
+    if let Err(err) = map.options.check_supported(&supported, "phf_map") {
+        return err.to_compile_error();
+    }
+    if let Some((underscore, default)) = &map.default {
+        if map.options.shards.is_some() || map.options.strategy != Strategy::Chd {
+            return Error::new_spanned(
+                underscore,
+                "a default entry can't be combined with `@strategy` or `@shards`",
+            )
+            .to_compile_error();
+        }
+        let state = map.options.generate_hash(&map.entries);
+        let phf = &map.options.crate_path;
+        let map = build_map(&map.entries, state, &map.options);
+        return quote!(#phf::DefaultMap {
+            map: #map,
+            default: #default,
+        });
+    }
+    if let Some(shards) = map.options.shards {
+        if map.options.strategy != Strategy::Chd {
+            return Error::new(
+                proc_macro2::Span::call_site(),
+                "`@shards` can't be combined with `@strategy`",
+            )
+            .to_compile_error();
+        }
+        return build_sharded_map(&map.entries, shards, &map.options);
+    }
+    if map.options.strategy == Strategy::LengthBucketed {
+        return match build_length_map(&map.entries, &map.options) {
+            Ok(map) => map,
+            Err(err) => err.to_compile_error(),
+        };
+    }
+    if map.options.strategy == Strategy::TwoLevel {
+        return match build_char_map(&map.entries, &map.options.crate_path) {
+            Ok(map) => map,
+            Err(err) => err.to_compile_error(),
+        };
+    }
+    let state = map.options.generate_hash(&map.entries);
+
+    build_map(&map.entries, state, &map.options)
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_array_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as Map);
+    if let Err(err) = map
+        .options
+        .check_supported(&["hasher", "key"], "phf_array_map")
+    {
+        return err.to_compile_error().into();
+    }
+    if let Err(err) = map.check_no_default("phf_array_map") {
+        return err.to_compile_error().into();
+    }
+    let state = map.options.generate_hash(&map.entries);
+
+    build_array_map(&map.entries, state, &map.options.crate_path).into()
 }
 
 #[::proc_macro_hack::proc_macro_hack]
 pub fn phf_set(input: TokenStream) -> TokenStream {
     let set = parse_macro_input!(input as Set);
-    let state = phf_generator::generate_hash(&set.0);
+    if let Err(err) = set.options.check_supported(
+        &[
+            "layout",
+            "align",
+            "hasher",
+            "key",
+            "fingerprints",
+            "filter",
+            "definition_order",
+            "normalize",
+            "strict",
+            "duplicates",
+        ],
+        "phf_set",
+    ) {
+        return err.to_compile_error().into();
+    }
+    let state = set.options.generate_hash(&set.entries);
 
-    let map = build_map(&set.0, state);
-    quote!(phf::Set { map: #map }).into()
+    let map = build_map(&set.entries, state, &set.options);
+    let phf = &set.options.crate_path;
+    warn_ignored(&set.ignored, quote!(#phf::Set { map: #map })).into()
 }
 
 #[::proc_macro_hack::proc_macro_hack]
 pub fn phf_ordered_map(input: TokenStream) -> TokenStream {
     let map = parse_macro_input!(input as Map);
-    let state = phf_generator::generate_hash(&map.0);
+    if let Err(err) = map.options.check_supported(
+        &[
+            "hasher",
+            "key",
+            "slot_order",
+            "normalize",
+            "strict",
+            "sorted",
+            "duplicates",
+        ],
+        "phf_ordered_map",
+    ) {
+        return err.to_compile_error().into();
+    }
+    if let Err(err) = map.check_no_default("phf_ordered_map") {
+        return err.to_compile_error().into();
+    }
+    if map.options.sorted {
+        if let Err(err) = check_sorted(&map.entries) {
+            return err.to_compile_error().into();
+        }
+    }
+    let state = map.options.generate_hash(&map.entries);
 
-    build_ordered_map(&map.0, state).into()
+    warn_ignored(
+        &map.ignored,
+        build_ordered_map(&map.entries, state, &map.options),
+    )
+    .into()
 }
 
 #[::proc_macro_hack::proc_macro_hack]
 pub fn phf_ordered_set(input: TokenStream) -> TokenStream {
     let set = parse_macro_input!(input as Set);
-    let state = phf_generator::generate_hash(&set.0);
+    if let Err(err) = set.options.check_supported(
+        &[
+            "hasher",
+            "key",
+            "slot_order",
+            "normalize",
+            "strict",
+            "sorted",
+            "duplicates",
+        ],
+        "phf_ordered_set",
+    ) {
+        return err.to_compile_error().into();
+    }
+    if set.options.sorted {
+        if let Err(err) = check_sorted(&set.entries) {
+            return err.to_compile_error().into();
+        }
+    }
+    let state = set.options.generate_hash(&set.entries);
+
+    let map = build_ordered_map(&set.entries, state, &set.options);
+    let phf = &set.options.crate_path;
+    warn_ignored(&set.ignored, quote!(#phf::OrderedSet { map: #map })).into()
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_ascii_case_insensitive_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as Map);
+    if let Err(err) = map.options.check_supported(
+        &["layout", "align", "hasher", "key", "fingerprints", "filter"],
+        "phf_ascii_case_insensitive_map",
+    ) {
+        return err.to_compile_error().into();
+    }
+    if let Err(err) = map.check_no_default("phf_ascii_case_insensitive_map") {
+        return err.to_compile_error().into();
+    }
+    let entries = match fold_ascii_case(&map.entries) {
+        Ok(entries) => entries,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let state = map.options.generate_hash(&entries);
+
+    let phf = &map.options.crate_path;
+    let map = build_map(&entries, state, &map.options);
+    quote!(#phf::AsciiCaseInsensitiveMap { map: #map }).into()
+}
+
+#[cfg(feature = "caseless")]
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_caseless_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as Map);
+    if let Err(err) = map.options.check_supported(
+        &["layout", "align", "hasher", "key", "fingerprints", "filter"],
+        "phf_caseless_map",
+    ) {
+        return err.to_compile_error().into();
+    }
+    if let Err(err) = map.check_no_default("phf_caseless_map") {
+        return err.to_compile_error().into();
+    }
+    let entries = match fold_unicode_case(&map.entries) {
+        Ok(entries) => entries,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let state = map.options.generate_hash(&entries);
+
+    let phf = &map.options.crate_path;
+    let map = build_map(&entries, state, &map.options);
+    quote!(#phf::CaselessMap { map: #map }).into()
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_dispatch_map(input: TokenStream) -> TokenStream {
+    let mut map = parse_macro_input!(input as Map);
+    if let Err(err) = map
+        .options
+        .check_supported(
+            &["layout", "align", "hasher", "key", "fingerprints", "filter"],
+            "phf_dispatch_map",
+        )
+        .and_then(|()| map.check_no_default("phf_dispatch_map"))
+    {
+        return err.to_compile_error().into();
+    }
+    // coercing each handler on its own puts a signature mismatch on the
+    // handler, rather than on the whole table
+    for entry in &mut map.entries {
+        let value = &entry.value;
+        entry.value = syn::parse_quote_spanned!(value.span()=> {
+            let handler: fn(_) -> _ = #value;
+            handler
+        });
+    }
+    let state = map.options.generate_hash(&map.entries);
+
+    let phf = &map.options.crate_path;
+    let map = build_map(&map.entries, state, &map.options);
+    quote!(#phf::DispatchMap { map: #map }).into()
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_interval_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as IntervalMap);
+    if let Err(err) = map.options.check_supported(&[], "phf_interval_map") {
+        return err.to_compile_error().into();
+    }
+
+    match build_interval_map(&map.entries, &map.options.crate_path) {
+        Ok(map) => map.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_trie_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as Map);
+    if let Err(err) = map.options.check_supported(&[], "phf_trie_map") {
+        return err.to_compile_error().into();
+    }
+    if let Err(err) = map.check_no_default("phf_trie_map") {
+        return err.to_compile_error().into();
+    }
+
+    match build_trie_map(&map.entries, &map.options.crate_path) {
+        Ok(map) => map.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_interner(input: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(input as Set);
+    if let Err(err) = set
+        .options
+        .check_supported(&["hasher", "key", "slot_order"], "phf_interner")
+    {
+        return err.to_compile_error().into();
+    }
+    if let Some(entry) = set
+        .entries
+        .iter()
+        .find(|entry| !matches!(entry.key.parsed, ParsedKey::Str(_)))
+    {
+        return Error::new_spanned(&entry.key.expr, "interned strings must be string literals")
+            .to_compile_error()
+            .into();
+    }
+    let state = set.options.generate_hash(&set.entries);
+
+    let map = build_ordered_map(&set.entries, state, &set.options);
+    let phf = &set.options.crate_path;
+    quote!(#phf::Interner {
+        set: #phf::OrderedSet { map: #map },
+    })
+    .into()
+}
+
+#[::proc_macro_hack::proc_macro_hack]
+pub fn phf_bit_set(input: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(input as Set);
+    if let Err(err) = set.options.check_supported(&[], "phf_bit_set") {
+        return err.to_compile_error().into();
+    }
+
+    match build_bit_set(&set.entries, &set.options.crate_path) {
+        Ok(set) => set.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn phf_enum(input: TokenStream) -> TokenStream {
+    let def = parse_macro_input!(input as Enum);
+    if let Err(err) = def.options.check_supported(&["hasher", "key"], "phf_enum") {
+        return err.to_compile_error().into();
+    }
 
-    let map = build_ordered_map(&set.0, state);
-    quote!(phf::OrderedSet { map: #map }).into()
+    build_enum(&def).into()
 }
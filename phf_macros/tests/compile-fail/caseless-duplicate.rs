@@ -0,0 +1,8 @@
+use phf::phf_caseless_map;
+
+static MAP: phf::CaselessMap<u32> = phf_caseless_map! {
+    "Straße" => 0,
+    "STRASSE" => 1, //~ ERROR duplicate key
+};
+
+fn main() {}
@@ -0,0 +1,9 @@
+use phf::phf_map;
+
+static MAP: phf::Map<&'static str, isize> = phf_map!(
+    @strict;
+    "Content-Type" => 1,
+    "content-type" => 2, //~ ERROR differs from "Content-Type" only by ASCII case
+);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use phf::phf_map;
+
+static MAP: phf::Map<&'static str, isize> = phf_map!(
+    @normalize = nfc;
+    "caf\u{e9}" => 1,
+    "cafe\u{301}" => 2, //~ ERROR duplicate key
+);
+
+fn main() {}
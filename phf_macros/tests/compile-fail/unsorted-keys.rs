@@ -0,0 +1,10 @@
+use phf::phf_ordered_set;
+
+static SET: phf::OrderedSet<u32> = phf_ordered_set!(
+    @sorted;
+    1u32,
+    3u32,
+    2u32, //~ ERROR keys must be in ascending order
+);
+
+fn main() {}
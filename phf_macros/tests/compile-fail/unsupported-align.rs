@@ -0,0 +1,9 @@
+use phf::phf_map;
+
+static MAP: phf::Map<&'static str, u32> = phf_map! {
+    @layout = split;
+    @align = 64; //~ ERROR `@align` pads `(key, value)` pairs and can't be combined with `@layout = split;`
+    "a" => 0,
+};
+
+fn main() {}
@@ -0,0 +1,8 @@
+use phf::phf_ordered_map;
+
+static MAP: phf::OrderedMap<&'static str, u32> = phf_ordered_map! {
+    "a" => 0,
+    _ => 1, //~ ERROR a default entry is not supported by `phf_ordered_map!`
+};
+
+fn main() {}
@@ -0,0 +1,9 @@
+use phf::phf_map;
+
+static MAP: phf::LengthMap<u32> = phf_map! {
+    @strategy = length_bucketed;
+    @definition_order; //~ ERROR option `definition_order` is not supported by `phf_map!`
+    "a" => 0,
+};
+
+fn main() {}
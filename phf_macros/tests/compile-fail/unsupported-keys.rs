@@ -0,0 +1,25 @@
+use phf::phf_map;
+
+const KEY: u32 = 1;
+
+static FLOAT: phf::Map<f64, u32> = phf_map! {
+    1.5 => 0, //~ ERROR float keys are not supported
+};
+
+static UNSUFFIXED: phf::Map<u32, u32> = phf_map! {
+    1 => 0, //~ ERROR integer keys need a type suffix
+};
+
+static USIZE: phf::Map<usize, u32> = phf_map! {
+    1usize => 0, //~ ERROR `usize` keys are not supported
+};
+
+static PATH: phf::Map<u32, u32> = phf_map! {
+    KEY => 0, //~ ERROR keys must be literals
+};
+
+static STRUCT: phf::Map<u32, u32> = phf_map! {
+    Foo { x: 1 } => 0, //~ ERROR struct and tuple keys are not supported
+};
+
+fn main() {}
@@ -0,0 +1,8 @@
+use phf::phf_map;
+
+static MAP: phf::Map<&'static str, u32> = phf_map! {
+    @sorted; //~ ERROR option `sorted` is not supported by `phf_map!`
+    "a" => 0,
+};
+
+fn main() {}
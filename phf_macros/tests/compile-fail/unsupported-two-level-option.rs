@@ -0,0 +1,9 @@
+use phf::phf_map;
+
+static MAP: phf::CharMap<u32> = phf_map! {
+    @strategy = two_level;
+    @fingerprints; //~ ERROR option `fingerprints` is not supported by `phf_map!`
+    'a' => 0,
+};
+
+fn main() {}
@@ -1,3 +1,6 @@
+// The tests spell out borrows and indexing expressions on purpose.
+#![allow(clippy::needless_borrow, clippy::map_clone, clippy::no_effect)]
+
 mod map {
     use phf::phf_map;
     use std::collections::{HashMap, HashSet};
@@ -56,6 +59,414 @@ mod map {
         assert_eq!(2, hash.len());
     }
 
+    #[test]
+    fn test_debug_sorted() {
+        static MAP: phf::Map<u32, char> = phf_map! {
+            5u32 => 'e', 3u32 => 'c', 8u32 => 'h', 1u32 => 'a',
+            4u32 => 'd', 2u32 => 'b', 7u32 => 'g', 6u32 => 'f',
+        };
+        let expected = (1..=8u8)
+            .map(|i| format!("    {}: '{}',\n", i, (b'a' + i - 1) as char))
+            .collect::<String>();
+        assert_eq!(
+            format!("{{\n{}}}", expected),
+            format!("{:#?}", MAP.debug_sorted())
+        );
+
+        let entries = MAP
+            .entries()
+            .map(|(k, v)| format!("{}: '{}'", k, v))
+            .collect::<Vec<_>>();
+        assert_eq!(format!("{{{}}}", entries.join(", ")), format!("{:?}", MAP));
+    }
+
+    #[test]
+    fn test_debug_unordered_keys() {
+        #[derive(Debug)]
+        struct Unordered;
+
+        static MAP: phf::Map<Unordered, u8> =
+            phf::Map::from_raw_parts(0, phf::HashFn::Sip, &[], &[(Unordered, 2)]);
+        assert_eq!("{Unordered: 2}", format!("{:?}", MAP));
+    }
+
+    #[test]
+    fn test_intersect_difference_keys() {
+        static OLD: phf::Map<u32, &'static str> = phf_map! {
+            1u32 => "a", 2u32 => "b", 3u32 => "c", 4u32 => "d", 5u32 => "e", 6u32 => "f", 7u32 => "g",
+        };
+        static NEW: phf::Map<u32, char> = phf_map! {
+            6u32 => 'F', 7u32 => 'G', 8u32 => 'H',
+        };
+        let mut both = OLD.intersect_keys(&NEW).collect::<Vec<_>>();
+        both.sort();
+        assert_eq!(vec![(&6, &"f", &'F'), (&7, &"g", &'G')], both);
+        let mut both = NEW.intersect_keys(&OLD).collect::<Vec<_>>();
+        both.sort();
+        assert_eq!(vec![(&6, &'F', &"f"), (&7, &'G', &"g")], both);
+
+        let mut removed = OLD.difference_keys(&NEW).map(|e| *e.0).collect::<Vec<_>>();
+        removed.sort();
+        assert_eq!(vec![1, 2, 3, 4, 5], removed);
+        assert_eq!(
+            vec![(&8, &'H')],
+            NEW.difference_keys(&OLD).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        static MAP: phf::Map<u32, u32> = phf_map! {
+            0u32 => 0, 1u32 => 1, 2u32 => 2, 3u32 => 3, 4u32 => 4,
+            5u32 => 5, 6u32 => 6, 7u32 => 7, 8u32 => 8, 9u32 => 9,
+        };
+        static SMALL: phf::Map<u32, u32> = phf_map!(0u32 => 0);
+        assert_eq!(10, MAP.capacity());
+        assert_eq!(2, MAP.buckets());
+        assert_eq!(5., MAP.load_factor());
+        assert!(MAP.max_displacement() < 10);
+
+        assert_eq!(1, SMALL.capacity());
+        assert_eq!(0, SMALL.buckets());
+        assert_eq!(0., SMALL.load_factor());
+        assert_eq!(0, SMALL.max_displacement());
+    }
+
+    #[test]
+    fn test_normalize_nfc() {
+        static MAP: phf::Map<&'static str, u32> = phf_map! {
+            @normalize = nfc;
+            "caf\u{e9}" => 0,
+            "nai\u{308}ve" => 1,
+            "A\u{30a}ngstro\u{308}m" => 2,
+            "plain" => 3,
+            "e\u{301}clair" => 4,
+            "pi\u{f1}ata" => 5,
+            "Zu\u{308}rich" => 6,
+        };
+        assert_eq!(Some(&1), MAP.get("na\u{ef}ve"));
+        assert_eq!(None, MAP.get("nai\u{308}ve"));
+        assert_eq!(Some(&1), MAP.get_nfc("nai\u{308}ve"));
+        assert_eq!(Some(&0), MAP.get_nfc("cafe\u{301}"));
+        assert_eq!(Some(&2), MAP.get_nfc("\u{c5}ngstr\u{f6}m"));
+        assert_eq!(Some(&3), MAP.get_nfc("plain"));
+        assert_eq!(None, MAP.get_nfc("cafe"));
+        assert!(MAP.keys().all(|k| MAP.get(k).is_some()));
+    }
+
+    #[test]
+    fn test_get_full() {
+        static MAP: phf::Map<&'static str, u32> = phf_map!(
+            "one" => 1,
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "six" => 6,
+            "seven" => 7,
+        );
+        for (i, (k, v)) in MAP.entries().enumerate() {
+            assert_eq!(Some((i, k, v)), MAP.get_full(k));
+        }
+        assert_eq!(None, MAP.get_full("eight"));
+    }
+
+    #[test]
+    fn test_verify() {
+        static MAP: phf::Map<u32, u32> = phf_map!(
+            @fingerprints;
+            @filter;
+            1u32 => 1,
+            2u32 => 2,
+            3u32 => 3,
+            4u32 => 4,
+            5u32 => 5,
+            6u32 => 6,
+            7u32 => 7,
+        );
+        assert_eq!(Ok(()), MAP.verify());
+
+        // displacements which weren't generated for these keys
+        static BROKEN: phf::Map<u32, u32> = phf::Map::from_raw_parts(
+            0,
+            phf::HashFn::Sip,
+            &[(0, 0), (0, 0)],
+            &[(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (7, 7)],
+        );
+        assert!(BROKEN.verify().is_err());
+    }
+
+    #[test]
+    fn test_find_key_by_value() {
+        static MAP: phf::Map<&'static str, u32> = phf_map!(
+            "one" => 1,
+            "uno" => 1,
+            "two" => 2,
+        );
+        assert_eq!(Some(&"two"), MAP.find_key_by_value(&2));
+        assert!(matches!(
+            MAP.find_key_by_value(&1),
+            Some(&"one") | Some(&"uno")
+        ));
+        assert_eq!(None, MAP.find_key_by_value(&3));
+
+        let inverted = MAP.invert();
+        assert_eq!(2, inverted.len());
+        assert_eq!(Some(&&"two"), inverted.get(&2));
+        assert_eq!(MAP.find_key_by_value(&1), inverted.get(&1).copied());
+    }
+
+    #[test]
+    fn test_inconsistent_lookup() {
+        // lookups must not panic, even though no key can be stored here
+        static NO_ENTRIES: phf::Map<u32, u32> =
+            phf::Map::from_raw_parts(0, phf::HashFn::Sip, &[(0, 0)], &[]);
+        assert_eq!(None, NO_ENTRIES.get(&1));
+        assert!(!NO_ENTRIES.contains_key(&1));
+
+        // packed displacements whose bytes are missing
+        static PACKED: phf::Map<u32, u32> = phf::Map {
+            key: 0,
+            hasher: phf::HashFn::Sip,
+            disps: phf::map::Disps::Packed(&phf::map::PackedDisps {
+                buckets: 1,
+                offsets: &[0],
+                bytes: &[],
+            }),
+            entries: phf::map::Slots::Pairs(&[(1, 1), (2, 2)]),
+            side: None,
+        };
+        assert_eq!(None, PACKED.get(&3));
+        assert!(!PACKED.contains_key(&1));
+        assert_eq!(Err(&1), PACKED.verify());
+
+        static NO_SHARDS: phf::ShardedMap<u32, u32> = phf::ShardedMap {
+            key: 0,
+            hasher: phf::HashFn::Sip,
+            len: 0,
+            shards: &[],
+        };
+        assert_eq!(None, NO_SHARDS.get(&1));
+
+        // a trie whose node points past the entries and the other nodes
+        static TRIE: phf::TrieMap<&'static str, u32> = phf::TrieMap {
+            nodes: &[phf::trie_map::Node {
+                entry: Some(1),
+                edges: &[(b'a', 1)],
+            }],
+            entries: &[("", 0)],
+        };
+        assert_eq!(None, TRIE.get(""));
+        assert_eq!(None, TRIE.get("a"));
+        assert_eq!(None, TRIE.longest_prefix("ab"));
+    }
+
+    #[test]
+    fn test_get_key_static() {
+        fn intern(map: &phf::Map<&'static str, u32>, s: &str) -> Option<&'static str> {
+            map.get_key(s).copied()
+        }
+
+        let map = phf_map! {
+            "foo" => 0,
+            "bar" => 1,
+        };
+        let probe = String::from("bar");
+        assert_eq!(Some("bar"), intern(&map, &probe));
+        assert_eq!(None, intern(&map, "baz"));
+    }
+
+    #[test]
+    fn test_get_key_static_containers() {
+        // the maps are borrowed for less than `'static`, but their tables aren't
+        fn intern(
+            length_map: &phf::LengthMap<'static, u32>,
+            sharded_map: &phf::ShardedMap<'static, &'static str, u32>,
+            trie_map: &phf::TrieMap<'static, &'static str, u32>,
+            ascii_map: &phf::AsciiCaseInsensitiveMap<'static, u32>,
+            s: &str,
+        ) -> [Option<&'static &'static str>; 4] {
+            [
+                length_map.get_key(s),
+                sharded_map.get_key(s),
+                trie_map.get_key(s),
+                ascii_map.get_key(s),
+            ]
+        }
+
+        let length_map = phf_map! {
+            @strategy = length_bucketed;
+            "foo" => 0,
+            "quux" => 1,
+        };
+        let sharded_map = phf_map! {
+            @shards = 2;
+            "foo" => 0,
+            "quux" => 1,
+        };
+        let trie_map = phf::phf_trie_map! {
+            "foo" => 0,
+            "quux" => 1,
+        };
+        let ascii_map = phf::phf_ascii_case_insensitive_map! {
+            "Foo" => 0,
+            "quux" => 1,
+        };
+
+        let probe = String::from("QUUX");
+        assert_eq!(
+            [None, None, None, Some(&"quux")],
+            intern(&length_map, &sharded_map, &trie_map, &ascii_map, &probe)
+        );
+        let probe = probe.to_lowercase();
+        assert_eq!(
+            [Some(&"quux"); 4],
+            intern(&length_map, &sharded_map, &trie_map, &ascii_map, &probe)
+        );
+        assert_eq!(
+            [None; 4],
+            intern(&length_map, &sharded_map, &trie_map, &ascii_map, "baz")
+        );
+    }
+
+    #[test]
+    fn test_get_copied() {
+        static MAP: phf::Map<&'static str, u8> = phf_map!(
+            "a" => 1,
+            "b" => 2,
+        );
+        assert_eq!(Some(1), MAP.get_copied("a"));
+        assert_eq!(None, MAP.get_copied("c"));
+    }
+
+    #[test]
+    fn test_get_cloned() {
+        static MAP: phf::Map<u8, &'static [u8]> = phf_map!(
+            1u8 => b"one",
+        );
+        assert_eq!(Some(&b"one"[..]), MAP.get_cloned(&1));
+        assert_eq!(None, MAP.get_cloned(&2));
+    }
+
+    #[test]
+    fn test_iter_sorted() {
+        static MAP: phf::Map<u32, char> = phf_map!(
+            5u32 => 'e',
+            3u32 => 'c',
+            1u32 => 'a',
+            6u32 => 'f',
+            2u32 => 'b',
+            4u32 => 'd',
+        );
+        assert_eq!(
+            vec![(&1, &'a'), (&2, &'b'), (&3, &'c')],
+            MAP.iter_sorted().take(3).collect::<Vec<_>>()
+        );
+        assert_eq!(Some((&6, &'f')), MAP.iter_sorted().next_back());
+        assert_eq!(6, MAP.iter_sorted().len());
+    }
+
+    #[test]
+    fn test_get_or_suggest() {
+        static MAP: phf::Map<&'static str, u32> = phf_map!(
+            "install" => 0,
+            "uninstall" => 1,
+            "update" => 2,
+            "upgrade" => 3,
+        );
+        assert_eq!(Ok(&2), MAP.get_or_suggest("update"));
+        assert_eq!(
+            Some("upgrade"),
+            MAP.get_or_suggest("upgarde").unwrap_err().closest()
+        );
+        assert_eq!(
+            Some("update"),
+            MAP.get_or_suggest("up").unwrap_err().closest()
+        );
+        assert_eq!(None, MAP.get_or_suggest("remove").unwrap_err().closest());
+        assert_eq!(None, MAP.get_or_suggest("").unwrap_err().closest());
+    }
+
+    #[test]
+    fn test_keys_display() {
+        static MAP: phf::Map<u32, ()> = phf_map!(
+            3u32 => (),
+            1u32 => (),
+            2u32 => (),
+        );
+        assert_eq!("`1`, `2`, `3`", MAP.keys_display().sorted().to_string());
+        assert_eq!(
+            "`1`, and 2 more",
+            MAP.keys_display().sorted().truncate(1).to_string()
+        );
+        assert_eq!("3 more", MAP.keys_display().truncate(0).to_string());
+        assert_eq!(
+            MAP.keys()
+                .map(|k| format!("`{}`", k))
+                .collect::<Vec<_>>()
+                .join(", "),
+            MAP.keys_display().truncate(5).to_string()
+        );
+    }
+
+    #[test]
+    fn test_shared_keys() {
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let entries = [(Arc::<str>::from("a"), 0), (Arc::from("b"), 1)];
+        let map = phf::Map::from_raw_parts(0, phf::HashFn::Sip, &[], &entries);
+        assert_eq!(Some(&1), map.get("b"));
+        assert_eq!(None, map.get("c"));
+
+        let entries = [(Rc::<[u8]>::from(&b"a"[..]), 0)];
+        let map = phf::Map::from_raw_parts(0, phf::HashFn::Sip, &[], &entries);
+        assert_eq!(Some(&0), map.get(&b"a"[..]));
+        assert!(map.verify().is_ok());
+    }
+
+    #[test]
+    fn test_borrowed_entries() {
+        fn build<'a, T>(keys: &'a [String], values: &'a [T]) -> Vec<(&'a str, &'a T)> {
+            keys.iter().map(String::as_str).zip(values).collect()
+        }
+
+        let keys = vec!["a".to_string(), "b".to_string()];
+        let values = [10, 11];
+        let entries = build(&keys, &values);
+        let map: phf::Map<&str, &i32> =
+            phf::Map::from_raw_parts(0, phf::HashFn::Sip, &[], &entries);
+        assert_eq!(Some(&&11), map.get("b"));
+        assert_eq!(Some(&"a"), map.get_key("a"));
+        assert_eq!(None, map.get("c"));
+        assert!(map.verify().is_ok());
+
+        assert!(map.as_set().contains("a"));
+    }
+
+    #[test]
+    fn test_as_set() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "foo" => 10,
+            "bar" => 11,
+            "baz" => 12,
+            "qux" => 13,
+            "quux" => 14,
+            "corge" => 15,
+        );
+        fn contains(set: phf::map::SetView<'_, &'static str, isize>, key: &str) -> bool {
+            set.contains(key)
+        }
+        let set = MAP.as_set();
+        assert_eq!(6, set.len());
+        assert!(contains(set, "foo"));
+        assert!(contains(set, "corge"));
+        assert!(!contains(set, "grault"));
+        assert_eq!(Some(&"baz"), set.get_key("baz"));
+        let keys = set.into_iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(MAP.keys().cloned().collect::<HashSet<_>>(), keys);
+    }
+
     #[test]
     fn test_values() {
         static MAP: phf::Map<&'static str, isize> = phf_map!(
@@ -101,6 +512,116 @@ mod map {
         assert!(MAP.get(&("a")) == Some(&0));
     }
 
+    #[test]
+    fn test_small_and_hashed_sizes() {
+        // Up to five entries are searched linearly, more are hashed.
+        static SMALL: phf::Map<&'static str, isize> = phf_map!(
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+        );
+        static HASHED: phf::Map<&'static str, isize> = phf_map!(
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+            "f" => 5,
+        );
+        for (i, key) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+            assert_eq!(Some(&(i as isize)), SMALL.get(key));
+            assert_eq!(Some(&(i as isize)), HASHED.get(key));
+        }
+        assert_eq!(Some(&5), HASHED.get("f"));
+        assert_eq!(None, SMALL.get("f"));
+        assert_eq!(None, HASHED.get("g"));
+    }
+
+    #[test]
+    fn test_length_bucketed() {
+        static MAP: phf::LengthMap<isize> = phf_map!(
+            @strategy = length_bucketed;
+            "fn" => 0,
+            "if" => 1,
+            "let" => 2,
+            "loop" => 3,
+            "while" => 4,
+            "return" => 5,
+            "continue" => 6,
+        );
+        assert_eq!(7, MAP.len());
+        assert_eq!(Some(&0), MAP.get("fn"));
+        assert_eq!(Some(&1), MAP.get("if"));
+        assert_eq!(Some(&6), MAP.get("continue"));
+        assert_eq!(None, MAP.get("f"));
+        assert_eq!(None, MAP.get("else"));
+        assert_eq!(None, MAP.get("continues"));
+        assert_eq!(
+            vec![2, 2, 3, 4, 5, 6, 8],
+            MAP.keys().map(|k| k.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(&4, &MAP["while"]);
+    }
+
+    #[test]
+    fn test_length_bucketed_empty() {
+        static MAP: phf::LengthMap<isize> = phf_map!(
+            @strategy = length_bucketed;
+        );
+        assert!(MAP.is_empty());
+        assert_eq!(None, MAP.get(""));
+        assert_eq!(None, MAP.entries().next());
+    }
+
+    #[test]
+    fn test_sharded() {
+        static MAP: phf::ShardedMap<&'static str, isize> = phf_map!(
+            @shards = 3;
+            @layout = split;
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+            "f" => 5,
+            "g" => 6,
+            "h" => 7,
+        );
+        assert_eq!(8, MAP.len());
+        assert_eq!(3, MAP.shards.len());
+        for (i, key) in ["a", "b", "c", "d", "e", "f", "g", "h"].iter().enumerate() {
+            assert_eq!(Some(&(i as isize)), MAP.get(key));
+        }
+        assert_eq!(None, MAP.get("i"));
+        let mut keys = MAP.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(vec!["a", "b", "c", "d", "e", "f", "g", "h"], keys);
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        static SMALL: phf::Map<&'static str, isize> = phf_map!(
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+        );
+        static HASHED: phf::Map<&'static str, isize> = phf_map!(
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+            "f" => 5,
+        );
+        for map in &[&SMALL, &HASHED] {
+            for (key, value) in map.entries() {
+                assert_eq!(value, unsafe { map.get_unchecked(key) });
+            }
+        }
+    }
+
     #[test]
     fn test_non_static_str_key() {
         static MAP: phf::Map<&'static str, isize> = phf_map!(
@@ -240,6 +761,196 @@ mod map {
         }
     }
 
+    #[test]
+    fn test_split_layout() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            @layout = split;
+            "foo" => 10,
+            "bar" => 11,
+        );
+        assert_eq!(Some(&10), MAP.get("foo"));
+        assert_eq!(Some(&11), MAP.get("bar"));
+        assert_eq!(None, MAP.get("baz"));
+        let mut entries = MAP.entries().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, vec![("bar", 11), ("foo", 10)]);
+    }
+
+    #[test]
+    fn test_align() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            @align = 32;
+            @definition_order;
+            "foo" => 10,
+            "bar" => 11,
+            "baz" => 12,
+        );
+        assert_eq!(Some(&10), MAP.get("foo"));
+        assert_eq!(Some(&12), MAP.get("baz"));
+        assert_eq!(None, MAP.get("qux"));
+        assert_eq!(
+            vec!["foo", "bar", "baz"],
+            MAP.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(Some((&"baz", &12)), MAP.entries().next_back());
+        match &MAP.entries {
+            phf::map::Slots::Aligned(phf::map::AlignedSlots::A32(entries)) => {
+                assert_eq!(0, entries.as_ptr() as usize % 32);
+                assert_eq!(32, std::mem::size_of_val(&entries[0]));
+            }
+            _ => panic!("entries aren't aligned"),
+        }
+
+        static UNORDERED: phf::Map<u8, [u8; 100]> = phf_map!(
+            @align = 128;
+            1u8 => [1; 100],
+            2u8 => [2; 100],
+        );
+        assert_eq!(Some(&[2; 100]), UNORDERED.get(&2));
+        assert_eq!(2, UNORDERED.values().count());
+    }
+
+    #[test]
+    fn test_short_key_hasher() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            @hasher = short_key;
+            "a" => 0,
+            "bb" => 1,
+            "ccc" => 2,
+            "sixteen bytes!!!" => 3,
+            "seventeen bytes!!" => 4,
+            "a much longer key which is hashed with SipHash" => 5,
+            "" => 6,
+        );
+        assert_eq!(Some(&0), MAP.get("a"));
+        assert_eq!(Some(&1), MAP.get("bb"));
+        assert_eq!(Some(&2), MAP.get("ccc"));
+        assert_eq!(Some(&3), MAP.get("sixteen bytes!!!"));
+        assert_eq!(Some(&4), MAP.get("seventeen bytes!!"));
+        assert_eq!(
+            Some(&5),
+            MAP.get("a much longer key which is hashed with SipHash")
+        );
+        assert_eq!(Some(&6), MAP.get(""));
+        assert_eq!(None, MAP.get("b"));
+        assert_eq!(None, MAP.get("a much longer key which isn't in the map"));
+    }
+
+    #[test]
+    fn test_hash_width() {
+        static DEFAULT: phf::Map<u32, isize> = phf_map!(
+            1u32 => 10,
+            2u32 => 11,
+            3u32 => 12,
+            4u32 => 13,
+            5u32 => 14,
+            6u32 => 15,
+        );
+        static FULL: phf::Map<u32, isize> = phf_map!(
+            @hasher = sip;
+            1u32 => 10,
+            2u32 => 11,
+            3u32 => 12,
+            4u32 => 13,
+            5u32 => 14,
+            6u32 => 15,
+        );
+        assert_eq!(phf::HashFn::Sip64, DEFAULT.hasher);
+        assert_eq!(phf::HashFn::Sip, FULL.hasher);
+        for i in 1..=6u32 {
+            assert_eq!(Some(&(i as isize + 9)), DEFAULT.get(&i));
+            assert_eq!(Some(&(i as isize + 9)), FULL.get(&i));
+        }
+        assert_eq!(None, DEFAULT.get(&7));
+        assert_eq!(None, FULL.get(&7));
+    }
+
+    #[test]
+    fn test_key() {
+        static MAP: phf::Map<u32, isize> = phf_map!(
+            @key = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+            1u32 => 10,
+            2u32 => 11,
+            3u32 => 12,
+            4u32 => 13,
+            5u32 => 14,
+            6u32 => 15,
+        );
+        assert_eq!(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210, MAP.key);
+        for i in 1..=6u32 {
+            assert_eq!(Some(&(i as isize + 9)), MAP.get(&i));
+        }
+        assert_eq!(None, MAP.get(&7));
+    }
+
+    #[test]
+    fn test_fingerprints() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            @fingerprints;
+            @hasher = short_key;
+            "the first rather long key" => 0,
+            "the second rather long key" => 1,
+            "the third rather long key" => 2,
+            "the fourth rather long key" => 3,
+            "the fifth rather long key" => 4,
+            "the sixth rather long key" => 5,
+        );
+        assert_eq!(6, MAP.side.unwrap().fingerprints.len());
+        assert_eq!(Some(&0), MAP.get("the first rather long key"));
+        assert_eq!(Some(&3), MAP.get("the fourth rather long key"));
+        assert_eq!(Some(&5), MAP.get("the sixth rather long key"));
+        assert_eq!(None, MAP.get("the seventh rather long key"));
+    }
+
+    #[test]
+    fn test_size() {
+        use std::mem::size_of;
+
+        // every variant is at most a tag and a slice
+        assert_eq!(3 * size_of::<usize>(), size_of::<phf::map::Disps>());
+        // the key, then the tagged displacements and slots, the side tables
+        // and the hasher, which is 96 bytes on 64-bit targets
+        assert!(size_of::<phf::Map<&str, u32>>() <= 16 + 10 * size_of::<usize>());
+    }
+
+    #[test]
+    fn test_no_side_tables() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "the first rather long key" => 0,
+            "the second rather long key" => 1,
+            "the third rather long key" => 2,
+            "the fourth rather long key" => 3,
+            "the fifth rather long key" => 4,
+            "the sixth rather long key" => 5,
+        );
+        assert!(MAP.side.is_none());
+        assert_eq!(Some(&3), MAP.get("the fourth rather long key"));
+    }
+
+    #[test]
+    fn test_definition_order() {
+        static MAP: phf::Map<u32, u32> = phf_map!(
+            @definition_order;
+            @layout = split;
+            10u32 => 0,
+            9u32 => 1,
+            8u32 => 2,
+            7u32 => 3,
+            6u32 => 4,
+            5u32 => 5,
+            4u32 => 6,
+        );
+        assert_eq!(
+            vec![10, 9, 8, 7, 6, 5, 4],
+            MAP.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![6, 5, 4, 3, 2, 1, 0],
+            MAP.values().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&3), MAP.get(&7));
+    }
+
     #[cfg(feature = "unicase")]
     #[test]
     fn test_unicase() {
@@ -252,53 +963,255 @@ mod map {
         assert!(Some(&11) == MAP.get(&UniCase::new("bar")));
         assert_eq!(None, MAP.get(&UniCase::new("asdf")));
     }
-}
-
-mod set {
-    use phf::phf_set;
-    use std::collections::HashSet;
-
-    #[allow(dead_code)]
-    static TRAILING_COMMA: phf::Set<&'static str> = phf_set! {
-        "foo",
-    };
-
-    #[allow(dead_code)]
-    static NO_TRAILING_COMMA: phf::Set<&'static str> = phf_set! {
-        "foo"
-    };
 
     #[test]
-    fn test_two() {
-        static SET: phf::Set<&'static str> = phf_set! {
-            "hello",
-            "world",
+    fn test_default() {
+        static MAP: phf::DefaultMap<&'static str, isize> = phf_map! {
+            "foo" => 10,
+            _ => -1,
+            "bar" => 11,
         };
-        assert!(SET.contains(&"hello"));
-        assert!(SET.contains(&"world"));
-        assert!(!SET.contains(&"foo"));
-        assert_eq!(2, SET.len());
+        assert_eq!(2, MAP.len());
+        assert_eq!(&10, MAP.get_or_default("foo"));
+        assert_eq!(&-1, MAP.get_or_default("baz"));
+        assert_eq!(-1, MAP["baz"]);
+        assert_eq!(None, MAP.get("baz"));
+        assert!(!MAP.contains_key("baz"));
+        assert_eq!(&-1, MAP.default_value());
+        let mut values = MAP.values().copied().collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(vec![10, 11], values);
     }
 
     #[test]
-    fn test_iter() {
-        static SET: phf::Set<&'static str> = phf_set! {
-            "hello",
-            "world",
+    fn test_default_only() {
+        static MAP: phf::DefaultMap<u32, &'static str> = phf_map! {
+            @fingerprints;
+            _ => "none",
         };
-        let set = SET.iter().map(|e| *e).collect::<HashSet<_>>();
-        assert!(set.contains(&"hello"));
-        assert!(set.contains(&"world"));
-        assert_eq!(2, set.len());
+        assert!(MAP.is_empty());
+        assert_eq!(&"none", MAP.get_or_default(&0));
     }
 
     #[test]
-    fn test_non_static_str_contains() {
-        static SET: phf::Set<&'static str> = phf_set! {
-            "hello",
-            "world",
+    fn test_duplicates_last() {
+        static MAP: phf::Map<&'static str, isize> = phf_map! {
+            @duplicates = last;
+            "colour" => 0,
+            "jobs" => 1,
+            "colour" => 2,
+            "colour" => 3,
         };
-        assert!(SET.contains(&*"hello".to_string()));
+        assert_eq!(2, MAP.len());
+        assert_eq!(Some(&3), MAP.get("colour"));
+        assert_eq!(Some(&1), MAP.get("jobs"));
+    }
+
+    #[test]
+    // keeping the first entry warns about the others
+    #[allow(deprecated)]
+    fn test_duplicates_first() {
+        static MAP: phf::Map<&'static str, isize> = phf_map! {
+            @duplicates = first;
+            "colour" => 0,
+            "jobs" => 1,
+            "colour" => 2,
+        };
+        assert_eq!(2, MAP.len());
+        assert_eq!(Some(&0), MAP.get("colour"));
+    }
+}
+
+mod set {
+    use phf::phf_set;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_iter_sorted() {
+        static SET: phf::Set<&'static str> = phf_set!("b", "c", "a", "e", "d", "f");
+        assert_eq!(
+            vec!["a", "b", "c", "d", "e", "f"],
+            SET.iter_sorted().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_definition_order() {
+        static SET: phf::Set<&'static str> = phf_set!(
+            @definition_order;
+            "zeta", "eta", "theta", "iota", "kappa", "lambda",
+        );
+        assert_eq!(
+            vec!["zeta", "eta", "theta", "iota", "kappa", "lambda"],
+            SET.iter().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        static SET: phf::Set<&'static str> = phf_set!(
+            @strict;
+            "Accept", "Accept-Encoding", "caf\u{e9}", "cafe",
+        );
+        assert!(SET.contains("Accept"));
+        assert!(!SET.contains("accept"));
+    }
+
+    #[test]
+    fn test_verify() {
+        static SET: phf::Set<u32> = phf_set!(1u32, 2u32, 3u32, 4u32, 5u32, 6u32, 7u32);
+        assert_eq!(Ok(()), SET.verify());
+    }
+
+    #[test]
+    fn test_normalize_nfc() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            @normalize = nfc;
+            "cafe\u{301}",
+            "plain",
+        };
+        assert!(SET.contains("caf\u{e9}"));
+        assert!(SET.contains_nfc("cafe\u{301}"));
+        assert!(!SET.contains_nfc("cafe"));
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        static A: phf::Set<u32> = phf_set!(1u32, 2u32, 3u32, 4u32, 5u32, 6u32, 7u32);
+        static B: phf::Set<u32> = phf_set!(5u32, 6u32, 7u32, 8u32, 9u32);
+        let diff = A.symmetric_difference(&B).copied().collect::<HashSet<_>>();
+        assert_eq!(
+            [1, 2, 3, 4, 8, 9].iter().copied().collect::<HashSet<_>>(),
+            diff
+        );
+        assert_eq!(0, A.symmetric_difference(&A).count());
+
+        let b = B.iter().copied().collect::<HashSet<_>>();
+        let diff = A
+            .symmetric_difference_hash_set(&b)
+            .copied()
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            [1, 2, 3, 4, 8, 9].iter().copied().collect::<HashSet<_>>(),
+            diff
+        );
+    }
+
+    #[test]
+    fn test_contains_all_any() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            "fn", "let", "match", "struct", "enum", "impl", "trait",
+        };
+        let probes = ["let", "x", "match"];
+        assert!(SET.contains_any(probes.iter().copied()));
+        assert!(!SET.contains_all(probes.iter().copied()));
+        assert!(SET.contains_all(probes.iter().copied().filter(|&p| p != "x")));
+        assert!(!SET.contains_any(["x", "y"].iter().copied()));
+        assert!(SET.contains_all(None::<&str>));
+        assert!(!SET.contains_any(None::<&str>));
+    }
+
+    #[test]
+    fn test_debug_sorted() {
+        static SET: phf::Set<u32> = phf_set! {
+            5u32, 3u32, 8u32, 1u32, 4u32, 2u32, 7u32, 6u32,
+        };
+        let expected = (1..=8).map(|i| format!("    {},\n", i)).collect::<String>();
+        assert_eq!(
+            format!("{{\n{}}}", expected),
+            format!("{:#?}", SET.debug_sorted())
+        );
+    }
+
+    #[allow(dead_code)]
+    static TRAILING_COMMA: phf::Set<&'static str> = phf_set! {
+        "foo",
+    };
+
+    #[allow(dead_code)]
+    static NO_TRAILING_COMMA: phf::Set<&'static str> = phf_set! {
+        "foo"
+    };
+
+    #[test]
+    fn test_two() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            "hello",
+            "world",
+        };
+        assert!(SET.contains(&"hello"));
+        assert!(SET.contains(&"world"));
+        assert!(!SET.contains(&"foo"));
+        assert_eq!(2, SET.len());
+    }
+
+    #[test]
+    fn test_filter() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            @filter;
+            "as",
+            "break",
+            "const",
+            "continue",
+            "crate",
+            "else",
+            "enum",
+            "extern",
+        };
+        assert!(!SET.map.side.unwrap().filter.is_empty());
+        for key in &[
+            "as", "break", "const", "continue", "crate", "else", "enum", "extern",
+        ] {
+            assert!(SET.contains(key));
+        }
+        for i in 0..100 {
+            assert!(!SET.contains(&*format!("ident{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            "hello",
+            "world",
+        };
+        let set = SET.iter().map(|e| *e).collect::<HashSet<_>>();
+        assert!(set.contains(&"hello"));
+        assert!(set.contains(&"world"));
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn test_split_layout() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            @layout = split;
+            "hello",
+            "world",
+        };
+        assert!(SET.contains("hello"));
+        assert!(SET.contains("world"));
+        assert!(!SET.contains("foo"));
+    }
+
+    #[test]
+    fn test_align() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            @align = 16;
+            "hello",
+            "world",
+        };
+        assert!(SET.contains("hello"));
+        assert!(!SET.contains("foo"));
+        assert_eq!(2, SET.iter().count());
+    }
+
+    #[test]
+    fn test_non_static_str_contains() {
+        static SET: phf::Set<&'static str> = phf_set! {
+            "hello",
+            "world",
+        };
+        assert!(SET.contains(&*"hello".to_string()));
     }
 
     #[test]
@@ -338,6 +1251,47 @@ mod ordered_map {
         assert_eq!(2, MAP.len());
     }
 
+    #[test]
+    fn test_duplicates_last() {
+        static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map! {
+            @duplicates = last;
+            "colour" => 0,
+            "jobs" => 1,
+            "colour" => 2,
+        };
+        assert_eq!(
+            vec![(&"colour", &2), (&"jobs", &1)],
+            MAP.entries().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_short_key_hasher() {
+        static MAP: phf::OrderedMap<u32, isize> = phf_ordered_map!(
+            @hasher = short_key;
+            1u32 => 10,
+            2u32 => 11,
+            3u32 => 12,
+            4u32 => 13,
+            5u32 => 14,
+            6u32 => 15,
+        );
+        assert_eq!(Some(&10), MAP.get(&1));
+        assert_eq!(Some(&15), MAP.get(&6));
+        assert_eq!(Some(5), MAP.get_index(&6));
+        assert_eq!(None, MAP.get(&7));
+    }
+
+    #[test]
+    fn test_get_copied() {
+        static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+            "foo" => 10,
+        );
+        assert_eq!(Some(10), MAP.get_copied("foo"));
+        assert_eq!(Some(10), MAP.get_cloned("foo"));
+        assert_eq!(None, MAP.get_copied("bar"));
+    }
+
     #[test]
     fn test_get_index() {
         static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
@@ -354,6 +1308,78 @@ mod ordered_map {
         assert_eq!(None, MAP.get_index(&*"xyz".to_string()));
     }
 
+    #[test]
+    fn test_slot_order() {
+        static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+            @slot_order;
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+            "f" => 5,
+            "g" => 6,
+        );
+        assert_eq!(7, MAP.order.len());
+        for (i, key) in ["a", "b", "c", "d", "e", "f", "g"].iter().enumerate() {
+            assert_eq!(Some(&(i as isize)), MAP.get(key));
+            assert_eq!(Some(i), MAP.get_index(key));
+            assert_eq!(i, MAP.get_index_unchecked(key));
+            assert_eq!(&(i as isize), unsafe { MAP.get_unchecked(key) });
+            assert_eq!(Some((key, &(i as isize))), MAP.index(i));
+        }
+        assert_eq!(None, MAP.get("h"));
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 5, 6],
+            MAP.values().cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_entries_range() {
+        static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+            "a" => 0,
+            "b" => 1,
+            "c" => 2,
+            "d" => 3,
+            "e" => 4,
+            "f" => 5,
+        );
+        assert_eq!(Some(3), MAP.get_index_of("d"));
+        assert_eq!(
+            vec![(&"b", &1), (&"c", &2)],
+            MAP.entries_range(1..3).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&"e", &"f"], MAP.keys_range(4..).collect::<Vec<_>>());
+        assert_eq!(vec![&0], MAP.values_range(..1).collect::<Vec<_>>());
+        assert_eq!(Some(&[("b", 1), ("c", 2)][..]), MAP.entries_slice(1..3));
+        assert_eq!(Some(&[][..]), MAP.entries_slice(6..));
+    }
+
+    #[test]
+    fn test_get_index_unchecked() {
+        static SMALL: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+            "foo" => 5,
+            "bar" => 6,
+            "baz" => 7,
+        );
+        static HASHED: phf::OrderedMap<u32, isize> = phf_ordered_map!(
+            1u32 => 10,
+            2u32 => 11,
+            3u32 => 12,
+            4u32 => 13,
+            5u32 => 14,
+            6u32 => 15,
+        );
+        assert_eq!(0, SMALL.get_index_unchecked("foo"));
+        assert_eq!(2, SMALL.get_index_unchecked("baz"));
+        assert_eq!(&6, unsafe { SMALL.get_unchecked("bar") });
+        for (i, key) in HASHED.keys().enumerate() {
+            assert_eq!(i, HASHED.get_index_unchecked(key));
+            assert_eq!(&HASHED.entries[i].1, unsafe { HASHED.get_unchecked(key) });
+        }
+    }
+
     #[test]
     fn test_index() {
         static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
@@ -434,11 +1460,134 @@ mod ordered_map {
             assert_eq!(&10, v)
         }
     }
+
+    #[test]
+    fn test_get_full() {
+        static MAP: phf::OrderedMap<&'static str, u32> = phf_ordered_map!(
+            @slot_order;
+            "one" => 1,
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "six" => 6,
+        );
+        assert_eq!(Some((0, &"one", &1)), MAP.get_full("one"));
+        assert_eq!(Some((4, &"five", &5)), MAP.get_full("five"));
+        assert_eq!(None, MAP.get_full("seven"));
+    }
+
+    #[test]
+    fn test_find_key_by_value() {
+        static MAP: phf::OrderedMap<&'static str, u32> = phf_ordered_map!(
+            "one" => 1,
+            "uno" => 1,
+            "two" => 2,
+        );
+        assert_eq!(Some(&"one"), MAP.find_key_by_value(&1));
+        assert_eq!(None, MAP.find_key_by_value(&3));
+
+        let inverted = MAP.invert();
+        assert_eq!(Some(&&"one"), inverted.get(&1));
+        assert_eq!(Some(&&"two"), inverted.get(&2));
+    }
+
+    #[test]
+    fn test_verify() {
+        static MAP: phf::OrderedMap<u32, u32> = phf_ordered_map!(
+            1u32 => 1,
+            2u32 => 2,
+            3u32 => 3,
+            4u32 => 4,
+            5u32 => 5,
+            6u32 => 6,
+            7u32 => 7,
+        );
+        static SLOT_ORDER: phf::OrderedMap<u32, u32> = phf_ordered_map!(
+            @slot_order;
+            1u32 => 1,
+            2u32 => 2,
+            3u32 => 3,
+            4u32 => 4,
+            5u32 => 5,
+            6u32 => 6,
+            7u32 => 7,
+        );
+        assert_eq!(Ok(()), MAP.verify());
+        assert_eq!(Ok(()), SLOT_ORDER.verify());
+    }
+
+    #[test]
+    fn test_sorted_search() {
+        static MAP: phf::OrderedMap<i32, &'static str> = phf_ordered_map!(
+            @sorted;
+            @slot_order;
+            -20i32 => "a",
+            -3i32 => "b",
+            0i32 => "c",
+            7i32 => "d",
+            12i32 => "e",
+            40i32 => "f",
+            41i32 => "g",
+        );
+        assert_eq!(Ok(3), MAP.binary_search_by_key(&7, |&k, _| k));
+        assert_eq!(Ok(0), MAP.binary_search_by_key(&-20, |&k, _| k));
+        assert_eq!(Err(0), MAP.binary_search_by_key(&-21, |&k, _| k));
+        assert_eq!(Err(5), MAP.binary_search_by_key(&13, |&k, _| k));
+        assert_eq!(Err(7), MAP.binary_search_by_key(&42, |&k, _| k));
+        assert_eq!(Ok(6), MAP.binary_search_by_key(&"g", |_, &v| v));
+        assert_eq!(5, MAP.partition_point(|&k, _| k <= 12));
+        assert_eq!(0, MAP.partition_point(|&k, _| k < -20));
+        assert_eq!(7, MAP.partition_point(|_, _| true));
+    }
 }
 
 mod ordered_set {
     use phf::phf_ordered_set;
 
+    #[test]
+    fn test_get_full() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set! {
+            "hello",
+            "world",
+        };
+        assert_eq!(Some((1, &"world")), SET.get_full("world"));
+        assert_eq!(None, SET.get_full("foo"));
+    }
+
+    #[test]
+    fn test_iter_with_prefix() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set! {
+            @sorted;
+            "a",
+            "ab",
+            "abc",
+            "abd",
+            "b",
+            "ba",
+        };
+        let with_prefix = |prefix| SET.iter_with_prefix(prefix).copied().collect::<Vec<_>>();
+        assert_eq!(vec!["ab", "abc", "abd"], with_prefix("ab"));
+        assert_eq!(vec!["abc"], with_prefix("abc"));
+        assert_eq!(vec!["b", "ba"], with_prefix("b"));
+        assert_eq!(SET.iter().count(), with_prefix("").len());
+        assert!(with_prefix("c").is_empty());
+        assert!(with_prefix("aa").is_empty());
+    }
+
+    #[test]
+    fn test_sorted_search() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set! {
+            @sorted;
+            "apple",
+            "banana",
+            "cherry",
+        };
+        assert_eq!(Ok(1), SET.binary_search_by_key(&"banana", |&s| s));
+        assert_eq!(Err(2), SET.binary_search_by_key(&"blueberry", |&s| s));
+        assert_eq!(1, SET.partition_point(|s| s.len() < 6));
+    }
+
     #[allow(dead_code)]
     static TRAILING_COMMA: phf::OrderedSet<&'static str> = phf_ordered_set! {
         "foo",
@@ -479,6 +1628,53 @@ mod ordered_set {
         assert_eq!(None, SET.get_index(&*"xyz".to_string()));
     }
 
+    #[test]
+    fn test_get_index_unchecked() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set! {
+            "foo",
+            "bar",
+            "baz",
+        };
+        assert_eq!(0, SET.get_index_unchecked("foo"));
+        assert_eq!(2, SET.get_index_unchecked("baz"));
+    }
+
+    #[test]
+    fn test_get_index_of() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set! {
+            "foo",
+            "bar",
+            "baz",
+        };
+        assert_eq!(Some(1), SET.get_index_of("bar"));
+        assert_eq!(None, SET.get_index_of("xyz"));
+    }
+
+    #[test]
+    fn test_iter_range() {
+        static SET: phf::OrderedSet<u32> = phf_ordered_set! {
+            @slot_order;
+            0u32, 1u32, 2u32, 3u32, 4u32, 5u32, 6u32, 7u32, 8u32, 9u32, 10u32, 11u32,
+        };
+        let range = SET.iter_range(2..10).cloned().collect::<Vec<_>>();
+        assert_eq!((2..10).collect::<Vec<_>>(), range);
+        assert_eq!(vec![11], SET.iter_range(11..).cloned().collect::<Vec<_>>());
+        assert_eq!(
+            vec![0, 1],
+            SET.iter_range(..=1).cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(12, SET.iter_range(..).len());
+        assert_eq!(Some(&8), SET.iter_range(5..9).next_back());
+        assert_eq!(0, SET.iter_range(3..3).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_range_out_of_bounds() {
+        static SET: phf::OrderedSet<&'static str> = phf_ordered_set!("foo", "bar");
+        SET.iter_range(1..3);
+    }
+
     #[test]
     fn test_index() {
         static MAP: phf::OrderedSet<&'static str> = phf_ordered_set!("foo", "bar",);
@@ -515,4 +1711,877 @@ mod ordered_set {
             assert_eq!(&"foo", e);
         }
     }
+
+    #[test]
+    fn test_set_operations() {
+        static LEFT: phf::OrderedSet<u32> = phf_ordered_set! {
+            9u32, 2u32, 7u32, 4u32, 5u32, 1u32,
+        };
+        static RIGHT: phf::OrderedSet<u32> = phf_ordered_set! {
+            1u32, 3u32, 5u32, 7u32, 9u32,
+        };
+        assert_eq!(
+            vec![9, 7, 5, 1],
+            LEFT.intersection(&RIGHT).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![2, 4],
+            LEFT.difference(&RIGHT).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![9, 2, 7, 4, 5, 1, 3],
+            LEFT.union(&RIGHT).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 5, 7, 9],
+            RIGHT.intersection(&LEFT).copied().collect::<Vec<_>>()
+        );
+    }
+}
+
+mod bit_set {
+    use phf::phf_bit_set;
+
+    #[allow(dead_code)]
+    static TRAILING_COMMA: phf::BitSet<u8> = phf_bit_set! {
+        1u8,
+    };
+
+    #[allow(dead_code)]
+    static NO_TRAILING_COMMA: phf::BitSet<u8> = phf_bit_set! {
+        1u8
+    };
+
+    #[test]
+    fn test_bytes() {
+        static SET: phf::BitSet<u8> = phf_bit_set! {
+            b'\t', b'\n', b'\r', b' ', 0u8, 255u8,
+        };
+        for b in 0..=255u8 {
+            assert_eq!(
+                [b'\t', b'\n', b'\r', b' ', 0, 255].contains(&b),
+                SET.contains(&b)
+            );
+        }
+        assert_eq!(6, SET.len());
+        assert_eq!(4, SET.words.len());
+    }
+
+    #[test]
+    fn test_iter() {
+        static SET: phf::BitSet<i32> = phf_bit_set! {
+            70i32, -5i32, 3i32, 64i32, 63i32,
+        };
+        assert_eq!(vec![-5, 3, 63, 64, 70], SET.iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![70, 64, 63, 3, -5],
+            SET.iter().rev().collect::<Vec<_>>()
+        );
+        let mut iter = SET.iter();
+        assert_eq!(Some(-5), iter.next());
+        assert_eq!(Some(70), iter.next_back());
+        assert_eq!(3, iter.len());
+        assert_eq!(vec![3, 63, 64], iter.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_chars_and_bools() {
+        static CHARS: phf::BitSet<char> = phf_bit_set! {
+            'a', 'z', 'é',
+        };
+        static BOOLS: phf::BitSet<bool> = phf_bit_set! {
+            true,
+        };
+        assert!(CHARS.contains(&'é'));
+        assert!(!CHARS.contains(&'b'));
+        assert_eq!(vec!['a', 'z', 'é'], CHARS.iter().collect::<Vec<_>>());
+        assert!(BOOLS.contains(&true));
+        assert!(!BOOLS.contains(&false));
+    }
+
+    #[test]
+    fn test_empty() {
+        static SET: phf::BitSet<u16> = phf_bit_set! {};
+        assert!(SET.is_empty());
+        assert!(!SET.contains(&0));
+        assert_eq!(None, SET.iter().next());
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        static SET: phf::BitSet<u8> = phf_bit_set!(7u8,);
+
+        for e in &SET {
+            assert_eq!(7, e);
+        }
+    }
+}
+
+mod trie_map {
+    use phf::phf_trie_map;
+
+    #[allow(dead_code)]
+    static TRAILING_COMMA: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+        "foo" => 10,
+    };
+
+    #[allow(dead_code)]
+    static NO_TRAILING_COMMA: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+        "foo" => 10
+    };
+
+    #[test]
+    fn test_get() {
+        static MAP: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+            "foo" => 10,
+            "foobar" => 11,
+            "bar" => 12,
+        };
+        assert_eq!(3, MAP.len());
+        assert_eq!(Some(&10), MAP.get("foo"));
+        assert_eq!(Some(&11), MAP.get("foobar"));
+        assert_eq!(Some(&12), MAP.get("bar"));
+        assert_eq!(None, MAP.get("foob"));
+        assert_eq!(None, MAP.get("fo"));
+        assert_eq!(None, MAP.get(""));
+        assert_eq!(Some(&"foobar"), MAP.get_key("foobar"));
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        static ROUTES: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+            "/" => 0,
+            "/api" => 1,
+            "/api/v1/" => 2,
+            "/static/" => 3,
+        };
+        assert_eq!(
+            Some((&"/api/v1/", &2)),
+            ROUTES.longest_prefix("/api/v1/users")
+        );
+        assert_eq!(Some((&"/api", &1)), ROUTES.longest_prefix("/api/v2/users"));
+        assert_eq!(Some((&"/", &0)), ROUTES.longest_prefix("/stat"));
+        assert_eq!(Some((&"/static/", &3)), ROUTES.longest_prefix("/static/"));
+        assert_eq!(None, ROUTES.longest_prefix("api"));
+        assert_eq!(None, ROUTES.longest_prefix(""));
+    }
+
+    #[test]
+    fn test_prefix_matches() {
+        static MAP: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+            "" => 0,
+            "a" => 1,
+            "abc" => 3,
+            "abd" => 4,
+        };
+        let matches = MAP
+            .get_prefix_matches("abcd")
+            .map(|(k, _)| *k)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["", "a", "abc"], matches);
+        assert_eq!(1, MAP.get_prefix_matches("b").count());
+    }
+
+    #[test]
+    fn test_bytes() {
+        static MAGIC: phf::TrieMap<&'static [u8], &'static str> = phf_trie_map! {
+            b"\x89PNG" => "png",
+            b"\xff\xd8\xff" => "jpeg",
+        };
+        assert_eq!(
+            Some(&"png"),
+            MAGIC.longest_prefix(b"\x89PNG\r\n").map(|e| e.1)
+        );
+        assert_eq!(
+            Some(&"jpeg"),
+            MAGIC.longest_prefix(&b"\xff\xd8\xff\xe0"[..]).map(|e| e.1)
+        );
+        assert_eq!(None, MAGIC.longest_prefix(b"GIF89a"));
+    }
+
+    #[test]
+    fn test_entries() {
+        static MAP: phf::TrieMap<&'static str, isize> = phf_trie_map! {
+            "foo" => 10,
+            "bar" => 11,
+            "baz" => 12,
+        };
+        let keys = MAP.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(vec!["foo", "bar", "baz"], keys);
+        let values = MAP.values().cloned().collect::<Vec<_>>();
+        assert_eq!(vec![10, 11, 12], values);
+    }
+
+    #[test]
+    fn test_empty() {
+        static MAP: phf::TrieMap<&'static str, isize> = phf_trie_map! {};
+        assert!(MAP.is_empty());
+        assert_eq!(None, MAP.get(""));
+        assert_eq!(None, MAP.longest_prefix("foo"));
+    }
+}
+
+mod ascii_case_insensitive_map {
+    use phf::phf_ascii_case_insensitive_map;
+
+    #[test]
+    fn test_get() {
+        static MAP: phf::AsciiCaseInsensitiveMap<isize> = phf_ascii_case_insensitive_map! {
+            "Content-Type" => 0,
+            "Content-Length" => 1,
+        };
+        assert_eq!(Some(&0), MAP.get("content-type"));
+        assert_eq!(Some(&1), MAP.get("CONTENT-LENGTH"));
+        assert_eq!(Some(&"Content-Type"), MAP.get_key("cOnTeNt-TyPe"));
+        assert_eq!(None, MAP.get("content_type"));
+    }
+
+    #[test]
+    fn test_large() {
+        static MAP: phf::AsciiCaseInsensitiveMap<isize> = phf_ascii_case_insensitive_map! {
+            @fingerprints;
+            @filter;
+            "Accept" => 0,
+            "Accept-Encoding" => 1,
+            "Authorization" => 2,
+            "Cache-Control" => 3,
+            "Content-Type" => 4,
+            "Content-Length" => 5,
+            "A-Rather-Long-Header-Name-Which-Spans-More-Than-One-Chunk" => 6,
+        };
+        assert_eq!(Some(&0), MAP.get("ACCEPT"));
+        assert_eq!(Some(&3), MAP.get("cache-control"));
+        assert_eq!(
+            Some(&6),
+            MAP.get("a-rather-long-header-name-which-spans-more-than-one-chunk")
+        );
+        assert_eq!(None, MAP.get("Accept-Language"));
+        for (key, value) in &MAP {
+            assert_eq!(Some(value), MAP.get(&key.to_ascii_uppercase()));
+        }
+    }
+}
+
+mod caseless_map {
+    use phf::phf_caseless_map;
+
+    #[test]
+    fn test_get() {
+        static MAP: phf::CaselessMap<isize> = phf_caseless_map! {
+            "Hauptstraße" => 0,
+            "Ελληνικά" => 1,
+            "Main Street" => 2,
+        };
+        assert_eq!(Some(&0), MAP.get("HAUPTSTRASSE"));
+        assert_eq!(Some(&0), MAP.get("hauptstrasse"));
+        assert_eq!(Some(&1), MAP.get("ΕΛΛΗΝΙΚΆ"));
+        assert_eq!(Some(&"Main Street"), MAP.get_key("MAIN street"));
+        assert_eq!(None, MAP.get("Main-Street"));
+    }
+
+    #[test]
+    fn test_hasher() {
+        static MAP: phf::CaselessMap<isize> = phf_caseless_map! {
+            @hasher = short_key;
+            @fingerprints;
+            @filter;
+            "Straße" => 0,
+            "Ärger" => 1,
+            "ΣΊΣΥΦΟΣ" => 2,
+        };
+        assert_eq!(Some(&0), MAP.get("STRASSE"));
+        assert_eq!(Some(&1), MAP.get("ärger"));
+        assert_eq!(Some(&2), MAP.get("σίσυφοσ"));
+        assert_eq!(None, MAP.get("Strase"));
+        for (key, value) in &MAP {
+            assert_eq!(Some(value), MAP.get(&key.to_uppercase()));
+        }
+    }
+}
+
+mod dispatch_map {
+    use phf::phf_dispatch_map;
+
+    fn len(args: &'static [&'static str]) -> usize {
+        args.len()
+    }
+
+    fn first(args: &'static [&'static str]) -> usize {
+        args.first().map_or(0, |arg| arg.len())
+    }
+
+    #[test]
+    fn test_call() {
+        static COMMANDS: phf::DispatchMap<&'static str, &'static [&'static str], usize> = phf_dispatch_map! {
+            "len" => len,
+            "first" => first,
+            "none" => |_| 0,
+        };
+        assert_eq!(3, COMMANDS.len());
+        assert_eq!(Some(2), COMMANDS.call("len", &["a", "bcd"]));
+        assert_eq!(Some(1), COMMANDS.call("first", &["a", "bcd"]));
+        assert_eq!(Some(0), COMMANDS.call("none", &["a"]));
+        assert_eq!(None, COMMANDS.call("last", &["a"]));
+        assert!(COMMANDS.contains_key("none"));
+        assert_eq!(Some(0), COMMANDS.get("first").map(|handler| handler(&[])));
+    }
+
+    #[test]
+    fn test_options() {
+        static OPS: phf::DispatchMap<u8, (u32, u32), u32> = phf_dispatch_map! {
+            @layout = split;
+            @filter;
+            b'+' => |(a, b)| a + b,
+            b'*' => |(a, b)| a * b,
+        };
+        assert_eq!(Some(12), OPS.call(&b'*', (3, 4)));
+        assert_eq!(None, OPS.call(&b'-', (3, 4)));
+        let mut keys = OPS.keys().copied().collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(vec![b'*', b'+'], keys);
+    }
+}
+
+mod interval_map {
+    use phf::phf_interval_map;
+
+    #[test]
+    fn test_get() {
+        static MAP: phf::IntervalMap<u32, &'static str> = phf_interval_map! {
+            0x0370..=0x03FF => "greek",
+            0x0000..=0x007F => "latin",
+            0x0400..0x0500 => "cyrillic",
+        };
+        assert_eq!(3, MAP.len());
+        assert_eq!(Some(&"latin"), MAP.get(&0));
+        assert_eq!(Some(&"latin"), MAP.get(&0x7F));
+        assert_eq!(None, MAP.get(&0x80));
+        assert_eq!(Some(&"greek"), MAP.get(&0x03BB));
+        assert_eq!(Some(&"cyrillic"), MAP.get(&0x04FF));
+        assert_eq!(None, MAP.get(&0x0500));
+        assert_eq!(
+            Some((&0x0400, &0x04FF, &"cyrillic")),
+            MAP.get_entry(&0x0410)
+        );
+    }
+
+    #[test]
+    fn test_entries() {
+        static MAP: phf::IntervalMap<i8, isize> = phf_interval_map! {
+            10..=20 => 1,
+            -128..=-1 => 0,
+            21..127 => 2,
+        };
+        let starts = MAP.entries().map(|e| *e.0).collect::<Vec<_>>();
+        assert_eq!(vec![-128, 10, 21], starts);
+        assert_eq!(Some(&0), MAP.get(&-5));
+        assert_eq!(None, MAP.get(&5));
+        assert_eq!(Some(&2), MAP.get(&126));
+        assert_eq!(None, MAP.get(&127));
+    }
+
+    #[test]
+    fn test_chars() {
+        static MAP: phf::IntervalMap<char, u8> = phf_interval_map! {
+            'a'..='z' => 0,
+            'A'..='Z' => 1,
+            '0'..':' => 2,
+        };
+        assert_eq!(Some(&0), MAP.get(&'q'));
+        assert_eq!(Some(&1), MAP.get(&'Q'));
+        assert_eq!(Some(&2), MAP.get(&'9'));
+        assert_eq!(None, MAP.get(&':'));
+    }
+
+    #[test]
+    fn test_empty() {
+        static MAP: phf::IntervalMap<u32, u8> = phf_interval_map! {};
+        assert!(MAP.is_empty());
+        assert_eq!(None, MAP.get(&0));
+    }
+}
+
+mod interner {
+    use phf::phf_interner;
+
+    #[test]
+    fn test_intern() {
+        static INTERNER: phf::Interner = phf_interner! {
+            "fn",
+            "let",
+            "match",
+            "struct",
+            "enum",
+            "impl",
+            "trait",
+        };
+        assert_eq!(7, INTERNER.len());
+        for (i, s) in INTERNER.iter().enumerate() {
+            let symbol = INTERNER.intern(s).unwrap();
+            assert_eq!(i as u32, symbol.as_u32());
+            assert_eq!(*s, INTERNER.resolve(symbol));
+        }
+        assert_eq!(None, INTERNER.intern("loop"));
+        assert!(INTERNER.intern("fn") < INTERNER.intern("trait"));
+    }
+
+    #[test]
+    fn test_slot_order() {
+        static INTERNER: phf::Interner = phf_interner! {
+            @slot_order;
+            "a", "b", "c", "d", "e", "f", "g", "h",
+        };
+        for (i, s) in ["a", "b", "c", "d", "e", "f", "g", "h"].iter().enumerate() {
+            let symbol = INTERNER.intern(s).unwrap();
+            assert_eq!(i as u32, symbol.as_u32());
+            assert_eq!(*s, INTERNER.resolve(symbol));
+        }
+    }
+}
+
+mod array_map {
+    use phf::phf_array_map;
+
+    #[test]
+    fn test_array_map() {
+        const MAP: phf::ArrayMap<&'static str, isize, 8> = phf_array_map! {
+            "a" => 0, "b" => 1, "c" => 2, "d" => 3, "e" => 4, "f" => 5, "g" => 6, "h" => 7,
+        };
+        assert_eq!(8, MAP.len());
+        for (i, key) in ["a", "b", "c", "d", "e", "f", "g", "h"].iter().enumerate() {
+            assert_eq!(Some(&(i as isize)), MAP.get(key));
+        }
+        assert_eq!(None, MAP.get("i"));
+        assert_eq!(8, MAP.entries().count());
+    }
+
+    #[test]
+    fn test_small() {
+        static MAP: phf::ArrayMap<u32, &'static str, 2> = phf_array_map! {
+            @hasher = short_key;
+            1u32 => "one",
+            2u32 => "two",
+        };
+        assert_eq!("two", MAP[&2]);
+        assert!(!MAP.contains_key(&3));
+    }
+
+    #[test]
+    fn test_borrowed() {
+        let names = [String::from("zero"), String::from("one")];
+        let map: phf::ArrayMap<&str, &String, 2> = phf_array_map! {
+            "zero" => &names[0],
+            "one" => &names[1],
+        };
+        assert_eq!("one", map["one"]);
+    }
+
+    #[test]
+    fn test_empty() {
+        const MAP: phf::ArrayMap<u32, u32, 0> = phf_array_map!();
+        assert!(MAP.is_empty());
+        assert_eq!(None, MAP.get(&0));
+    }
+}
+
+mod phf_enum {
+    use phf::phf_enum;
+
+    phf_enum! {
+        @hasher = short_key;
+        #[derive(Debug, PartialEq)]
+        enum Keyword {
+            Fn => "fn",
+            Let => "let",
+            Match => "match",
+            /// Not `Struct`, to check that the strings are used.
+            Record => "struct",
+            Enum => "enum",
+            Impl => "impl",
+            Trait => "trait",
+        }
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!(7, Keyword::VARIANTS.len());
+        assert_eq!("struct", Keyword::Record.as_str());
+        for (variant, name) in Keyword::iter().zip(Keyword::NAMES) {
+            assert_eq!(*name, variant.as_str());
+            assert_eq!(Ok(variant), name.parse().as_ref());
+        }
+        assert_eq!(Err(phf::ParseEnumError), "Record".parse::<Keyword>());
+        assert_eq!("unknown variant", phf::ParseEnumError.to_string());
+    }
+
+    #[test]
+    fn test_const_as_str() {
+        const NAME: &str = Keyword::Trait.as_str();
+        assert_eq!("trait", NAME);
+    }
+}
+
+mod char_map {
+    use phf::phf_map;
+
+    static MAP: phf::CharMap<u32> = phf_map! {
+        @strategy = two_level;
+        'a' => 0,
+        'z' => 1,
+        '\u{e9}' => 2,
+        '\u{3b1}' => 3,
+        '\u{3c9}' => 4,
+        '\u{1f600}' => 5,
+        '\u{10ffff}' => 6,
+    };
+
+    #[test]
+    fn test_get() {
+        for (i, c) in "az\u{e9}\u{3b1}\u{3c9}\u{1f600}\u{10ffff}"
+            .chars()
+            .enumerate()
+        {
+            assert_eq!(Some(&(i as u32)), MAP.get(&c));
+            assert_eq!(i as u32, MAP[&c]);
+        }
+        assert_eq!(None, MAP.get(&'b'));
+        assert_eq!(None, MAP.get(&'\u{3b2}'));
+        assert_eq!(None, MAP.get(&'\u{10fffe}'));
+        assert!(!MAP.contains_key(&'\0'));
+    }
+
+    #[test]
+    fn test_entries() {
+        assert_eq!(7, MAP.len());
+        assert_eq!(
+            vec![
+                'a',
+                'z',
+                '\u{e9}',
+                '\u{3b1}',
+                '\u{3c9}',
+                '\u{1f600}',
+                '\u{10ffff}'
+            ],
+            MAP.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (0..7).collect::<Vec<_>>(),
+            MAP.values().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_shared_blocks() {
+        // pages 0, 3, 0x1f6 and 0x10ff have keys, and all others share one
+        assert_eq!(0x1100, MAP.pages.len());
+        assert_eq!(5 * 256, MAP.blocks.len());
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: phf::CharMap<u32> = phf_map! {
+            @strategy = two_level;
+        };
+        assert!(EMPTY.is_empty());
+        assert_eq!(None, EMPTY.get(&'a'));
+    }
+}
+
+mod assert_key {
+    use phf::{phf_assert_key, phf_map, phf_set};
+
+    const SMALL: phf::Map<&'static str, u32> = phf_map! {
+        "foo" => 0,
+        "bar" => 1,
+    };
+
+    const LARGE: phf::Map<&'static str, u32> = phf_map! {
+        @layout = split;
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+    };
+
+    const SET: phf::Set<&'static str> = phf_set!("hello", "world");
+
+    phf_assert_key!(SMALL, "bar");
+    phf_assert_key!(LARGE, "seven");
+    phf_assert_key!(SET, "world");
+
+    #[test]
+    fn test_const_contains() {
+        assert!(LARGE.__contains_str_const("one"));
+        assert!(!LARGE.__contains_str_const("eight"));
+        assert!(!LARGE.__contains_str_const("on"));
+        assert!(!SET.__contains_str_const("hell"));
+    }
+}
+
+mod into_iterator {
+    use phf::{phf_map, phf_ordered_map, phf_ordered_set, phf_set};
+
+    fn count<I: IntoIterator>(iter: I) -> usize {
+        iter.into_iter().count()
+    }
+
+    fn assert_iterator<I: Iterator + Clone + Send + Sync + std::fmt::Debug>(_: I) {}
+
+    #[test]
+    fn test_iterator_traits() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!("foo" => 0);
+        static SET: phf::Set<&'static str> = phf_set!("foo");
+        static ORDERED_MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!("foo" => 0);
+        static ORDERED_SET: phf::OrderedSet<&'static str> = phf_ordered_set!("foo");
+
+        assert_iterator(MAP.entries());
+        assert_iterator(MAP.keys());
+        assert_iterator(MAP.values());
+        assert_iterator(SET.iter());
+        assert_iterator(ORDERED_MAP.entries());
+        assert_iterator(ORDERED_MAP.keys());
+        assert_iterator(ORDERED_MAP.values());
+        assert_iterator(ORDERED_SET.iter());
+
+        let mut entries = ORDERED_MAP.entries();
+        let copy = entries.clone();
+        assert_eq!(entries.next(), copy.clone().next());
+        assert_eq!(r#"[("foo", 0)]"#, format!("{:?}", copy));
+        std::thread::spawn(move || copy.count()).join().unwrap();
+    }
+
+    #[test]
+    fn test_generic() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!("foo" => 0, "bar" => 1);
+        static SET: phf::Set<&'static str> = phf_set!("foo", "bar");
+        static ORDERED_MAP: phf::OrderedMap<&'static str, isize> =
+            phf_ordered_map!("foo" => 0, "bar" => 1);
+        static ORDERED_SET: phf::OrderedSet<&'static str> = phf_ordered_set!("foo", "bar");
+
+        assert_eq!(2, count(&MAP));
+        assert_eq!(2, count(&SET));
+        assert_eq!(2, count(&ORDERED_MAP));
+        assert_eq!(2, count(&ORDERED_SET));
+        assert_eq!(2, count::<&phf::map::SetView<_, _>>(&MAP.as_set()));
+        assert_eq!(
+            vec![(&"foo", &"foo"), (&"bar", &"bar")],
+            ORDERED_SET.iter().zip(&ORDERED_SET).collect::<Vec<_>>()
+        );
+    }
+}
+
+mod const_items {
+    use phf::{phf_map, phf_ordered_map, phf_ordered_set, phf_set};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Tok {
+        Fn,
+        Let,
+        Other,
+    }
+
+    struct Lang;
+
+    impl Lang {
+        const KEYWORDS: phf::Map<'static, &'static str, Tok> = phf_map! {
+            "fn" => Tok::Fn,
+            "let" => Tok::Let,
+        };
+    }
+
+    trait Dialect {
+        const KEYWORDS: phf::Set<'static, &'static str>;
+
+        fn is_keyword(s: &str) -> bool {
+            Self::KEYWORDS.contains(s)
+        }
+    }
+
+    impl Dialect for Lang {
+        const KEYWORDS: phf::Set<'static, &'static str> = phf_set!("fn", "let", "loop");
+    }
+
+    const LARGE: phf::Map<u32, Tok> = phf_map! {
+        @layout = split;
+        @fingerprints;
+        @filter;
+        @definition_order;
+        1u32 => Tok::Fn,
+        2u32 => Tok::Let,
+        3u32 => Tok::Other,
+        4u32 => Tok::Other,
+        5u32 => Tok::Other,
+        6u32 => Tok::Other,
+        7u32 => Tok::Other,
+    };
+
+    const ORDERED_MAP: phf::OrderedMap<&'static str, u32> = phf_ordered_map! {
+        @slot_order;
+        "a" => 0,
+        "b" => 1,
+        "c" => 2,
+        "d" => 3,
+        "e" => 4,
+        "f" => 5,
+    };
+
+    const ORDERED_SET: phf::OrderedSet<&'static str> = phf_ordered_set!("a", "b");
+
+    const LENGTH_MAP: phf::LengthMap<u32> = phf_map! {
+        @strategy = length_bucketed;
+        "a" => 0,
+        "bb" => 1,
+    };
+
+    const CHAR_MAP: phf::CharMap<u32> = phf_map! {
+        @strategy = two_level;
+        'a' => 0,
+    };
+
+    const SHARDED_MAP: phf::ShardedMap<u32, u32> = phf_map! {
+        @shards = 2;
+        1u32 => 10,
+        2u32 => 20,
+    };
+
+    #[test]
+    fn test_associated_consts() {
+        assert_eq!(Some(&Tok::Let), Lang::KEYWORDS.get("let"));
+        assert!(<Lang as Dialect>::is_keyword("loop"));
+        assert!(!<Lang as Dialect>::is_keyword("while"));
+    }
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(Some(&Tok::Fn), LARGE.get(&1));
+        assert_eq!(Some(&3), LARGE.keys().nth(2));
+        assert_eq!(Some(&5), ORDERED_MAP.get("f"));
+        assert_eq!(Some(1), ORDERED_SET.get_index("b"));
+        assert_eq!(Some(&1), LENGTH_MAP.get("bb"));
+        assert_eq!(Some(&0), CHAR_MAP.get(&'a'));
+        assert_eq!(Some(&20), SHARDED_MAP.get(&2));
+    }
+}
+
+mod sip_hash {
+    use phf_shared::{HashFn, PhfHash};
+    use siphasher::sip128::{Hasher128, SipHasher13};
+    use std::hash::Hasher;
+
+    // feeds the hasher in uneven pieces, as composite keys do
+    struct Pieces<'a>(&'a [u8]);
+
+    impl PhfHash for Pieces<'_> {
+        fn phf_hash<H: Hasher>(&self, state: &mut H) {
+            for (i, piece) in self.0.chunks(5).enumerate() {
+                match i % 4 {
+                    0 => state.write(piece),
+                    1 => state.write_u8(piece[0]),
+                    2 => state.write_u32(i as u32),
+                    _ => state.write_u64(u64::from(piece[0]) << 40),
+                }
+            }
+            state.write_usize(self.0.len());
+        }
+    }
+
+    // with the `simd` feature, the vectorized SipHash must agree with the
+    // scalar one tables are generated with
+    #[test]
+    fn test_matches_siphasher() {
+        let key = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        let bytes = (0..100u8).map(|b| b.wrapping_mul(151)).collect::<Vec<_>>();
+        for len in 0..bytes.len() {
+            let pieces = Pieces(&bytes[..len]);
+            let mut sip = SipHasher13::new_with_keys((key >> 64) as u64, key as u64);
+            pieces.phf_hash(&mut sip);
+            let hash = sip.finish128();
+
+            let hashes = phf_shared::hash_with(&pieces, &key, HashFn::Sip);
+            assert_eq!((hash.h1 >> 32) as u32, hashes.g);
+            assert_eq!(hash.h1 as u32, hashes.f1);
+            assert_eq!(hash.h2 as u32, hashes.f2);
+
+            let mut sip =
+                siphasher::sip::SipHasher13::new_with_keys((key >> 64) as u64, key as u64);
+            pieces.phf_hash(&mut sip);
+            let hash = sip.finish();
+
+            let hashes = phf_shared::hash_with(&pieces, &key, HashFn::Sip64);
+            assert_eq!((hash >> 32) as u32, hashes.g);
+            assert_eq!(hash as u32, hashes.f1);
+        }
+    }
+}
+
+mod crate_path {
+    use ::phf::{phf_enum, phf_interval_map, phf_map, phf_ordered_set, phf_set, phf_trie_map};
+
+    // shadows the crate, so that any path the macros emit without `@crate`
+    // fails to resolve
+    mod phf {}
+
+    mod facade {
+        pub use ::phf as inner;
+    }
+
+    macro_rules! facade_map {
+        ($($entries:tt)*) => {
+            ::phf::phf_map!(@crate = $crate::crate_path::facade::inner; $($entries)*)
+        };
+    }
+
+    static MAP: ::phf::Map<u32, u32> = phf_map! {
+        @crate = ::phf;
+        @fingerprints;
+        1u32 => 10,
+        2u32 => 20,
+        3u32 => 30,
+        4u32 => 40,
+        5u32 => 50,
+        6u32 => 60,
+    };
+
+    static SET: ::phf::Set<&'static str> = phf_set!(@crate = ::phf; "a", "b");
+
+    static ORDERED_SET: ::phf::OrderedSet<&'static str> =
+        phf_ordered_set!(@crate = ::phf; "x", "y");
+
+    static INTERVALS: ::phf::IntervalMap<u32, u32> = phf_interval_map! {
+        @crate = ::phf;
+        0u32..=9 => 0,
+    };
+
+    static TRIE: ::phf::TrieMap<&'static str, u32> = phf_trie_map! {
+        @crate = ::phf;
+        "ab" => 1,
+    };
+
+    static FACADE: ::phf::Map<&'static str, u32> = facade_map! {
+        "one" => 1,
+        "two" => 2,
+    };
+
+    phf_enum! {
+        @crate = ::phf;
+        enum Color {
+            Red => "red",
+            Blue => "blue",
+        }
+    }
+
+    #[test]
+    fn test_crate_path() {
+        assert_eq!(Some(&60), MAP.get(&6));
+        assert!(SET.contains("b"));
+        assert_eq!(Some(1), ORDERED_SET.get_index("y"));
+        assert_eq!(Some(&0), INTERVALS.get(&5));
+        assert_eq!(Some(&1), TRIE.get("ab"));
+        assert_eq!(Some(&2), FACADE.get("two"));
+        assert!(matches!("blue".parse(), Ok(Color::Blue)));
+    }
 }
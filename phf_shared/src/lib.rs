@@ -1,13 +1,25 @@
 #![doc(html_root_url = "https://docs.rs/phf_shared/0.10")]
-#![cfg_attr(not(feature = "std"), no_std)]
+#![no_std]
 
-#[cfg(feature = "std")]
-extern crate std as core;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, string::String, vec::Vec};
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::num::Wrapping;
-use siphasher::sip128::{Hash128, Hasher128, SipHasher13};
+use core::time::Duration;
+#[cfg(feature = "simd")]
+use simd::{Sip64Hasher13, SipHasher13};
+use siphasher::sip128::{Hash128, Hasher128};
+#[cfg(not(feature = "simd"))]
+use siphasher::{sip::SipHasher13 as Sip64Hasher13, sip128::SipHasher13};
+
+#[cfg(feature = "simd")]
+mod simd;
 
 #[non_exhaustive]
 pub struct Hashes {
@@ -19,23 +31,76 @@ pub struct Hashes {
 /// A central typedef for hash keys
 ///
 /// Makes experimentation easier by only needing to be updated here.
-pub type HashKey = u64;
+///
+/// The upper and lower 64 bits are SipHash's two keys.
+pub type HashKey = u128;
+
+#[inline]
+fn sip_hasher(key: HashKey) -> SipHasher13 {
+    SipHasher13::new_with_keys((key >> 64) as u64, key as u64)
+}
+
+#[inline]
+fn sip64_hasher(key: HashKey) -> Sip64Hasher13 {
+    Sip64Hasher13::new_with_keys((key >> 64) as u64, key as u64)
+}
 
 #[inline]
 pub fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
     (Wrapping(d2) + Wrapping(f1) * Wrapping(d1) + Wrapping(f2)).0
 }
 
+/// The hash function used to index a PHF data structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashFn {
+    /// SipHash-1-3 with 128-bit output. This is the default for large
+    /// tables.
+    Sip,
+    /// SipHash-1-3 with 64-bit output, which takes three finalization rounds
+    /// instead of six. This is the default for tables of at most 65536
+    /// entries, where the extra bits of `Sip` buy nothing.
+    Sip64,
+    /// A cheap multiply-and-fold mix of the key's bytes, for keys which feed
+    /// at most 16 bytes to the hasher. Longer keys fall back to SipHash-1-3.
+    ///
+    /// Setting up SipHash dominates the cost of looking up short keys such
+    /// as identifiers or keywords, which this avoids.
+    ShortKey,
+}
+
 /// `key` is from `phf_generator::HashState`.
 #[inline]
 pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> Hashes {
-    let mut hasher = SipHasher13::new_with_keys(0, *key);
+    let mut hasher = sip_hasher(*key);
     x.phf_hash(&mut hasher);
+    sip_hashes(hasher.finish128())
+}
+
+/// Like `hash`, but using the given hash function.
+///
+/// `key` and `hasher` are from `phf_generator::HashState`.
+#[inline]
+pub fn hash_with<T: ?Sized + PhfHash>(x: &T, key: &HashKey, hasher: HashFn) -> Hashes {
+    match hasher {
+        HashFn::Sip => hash(x, key),
+        HashFn::Sip64 => {
+            let mut hasher = sip64_hasher(*key);
+            x.phf_hash(&mut hasher);
+            sip64_hashes(hasher.finish())
+        }
+        HashFn::ShortKey => {
+            let mut hasher = ShortKeyHasher::new(*key);
+            x.phf_hash(&mut hasher);
+            hasher.finish_hashes()
+        }
+    }
+}
 
+fn sip_hashes(hash: Hash128) -> Hashes {
     let Hash128 {
         h1: lower,
         h2: upper,
-    } = hasher.finish128();
+    } = hash;
 
     Hashes {
         g: (lower >> 32) as u32,
@@ -44,15 +109,243 @@ pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> Hashes {
     }
 }
 
+fn sip64_hashes(hash: u64) -> Hashes {
+    Hashes {
+        g: (hash >> 32) as u32,
+        f1: hash as u32,
+        // mix all of the bits into the last hash rather than reusing any
+        f2: (hash.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32) as u32,
+    }
+}
+
+const SHORT_KEY_LEN: usize = 16;
+
+/// Buffers up to `SHORT_KEY_LEN` bytes to be mixed inline, switching over to
+/// SipHash once a key turns out to be longer.
+struct ShortKeyHasher {
+    key: HashKey,
+    buf: [u8; SHORT_KEY_LEN],
+    len: usize,
+    sip: Option<SipHasher13>,
+}
+
+impl ShortKeyHasher {
+    fn new(key: HashKey) -> ShortKeyHasher {
+        ShortKeyHasher {
+            key,
+            buf: [0; SHORT_KEY_LEN],
+            len: 0,
+            sip: None,
+        }
+    }
+
+    fn finish_hashes(&self) -> Hashes {
+        if let Some(sip) = &self.sip {
+            return sip_hashes(sip.finish128());
+        }
+
+        let mut a = [0; 8];
+        let mut b = [0; 8];
+        a.copy_from_slice(&self.buf[..8]);
+        b.copy_from_slice(&self.buf[8..]);
+        let (a, b) = (u64::from_le_bytes(a), u64::from_le_bytes(b));
+
+        // the length is mixed in so that trailing zero bytes still count
+        let seed = (self.key >> 64) as u64 ^ self.key as u64 ^ 0x243f_6a88_85a3_08d3;
+        let mixed = folded_multiply(a ^ 0x1319_8a2e_0370_7344, b ^ seed);
+        let lower = folded_multiply(mixed ^ 0xa409_3822_299f_31d0, self.len as u64 ^ seed);
+        let upper = folded_multiply(lower ^ 0x082e_fa98_ec4e_6c89, mixed ^ 0x4528_21e6_38d0_1377);
+
+        Hashes {
+            g: (lower >> 32) as u32,
+            f1: lower as u32,
+            f2: upper as u32,
+        }
+    }
+}
+
+impl Hasher for ShortKeyHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        // written without indexing, since this is on the lookup path
+        if let Some(sip) = &mut self.sip {
+            sip.write(bytes);
+            return;
+        }
+        let len = self.len.saturating_add(bytes.len());
+        match self.buf.get_mut(self.len..len) {
+            Some(buf) => {
+                for (dst, &src) in buf.iter_mut().zip(bytes) {
+                    *dst = src;
+                }
+                self.len = len;
+            }
+            None => {
+                let mut sip = sip_hasher(self.key);
+                sip.write(self.buf.get(..self.len).unwrap_or(&[]));
+                sip.write(bytes);
+                self.sip = Some(sip);
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let hashes = self.finish_hashes();
+        (u64::from(hashes.g) << 32) | u64::from(hashes.f1)
+    }
+}
+
+#[inline]
+fn folded_multiply(a: u64, b: u64) -> u64 {
+    let full = u128::from(a) * u128::from(b);
+    (full as u64) ^ ((full >> 64) as u64)
+}
+
+/// Return a 16-bit fingerprint of a key's hashes.
+///
+/// The fingerprint is taken from bits which `get_index` mostly ignores, so
+/// keys which land in the same slot are still likely to have different
+/// fingerprints.
+#[inline]
+pub fn fingerprint(hashes: &Hashes) -> u16 {
+    ((hashes.g >> 16) ^ (hashes.f2 >> 16)) as u16
+}
+
+/// Return the 64-bit key by which the entries of a map sorted by hash are
+/// ordered.
+#[inline]
+pub fn sort_key(hashes: &Hashes) -> u64 {
+    (u64::from(hashes.g) << 32) | u64::from(hashes.f1)
+}
+
+/// Return the two bits of a companion filter which are set for a key.
+///
+/// * `bits` is the number of bits in the filter, which must be a power of
+///   two.
+#[inline]
+pub fn filter_bits(hashes: &Hashes, bits: usize) -> [usize; 2] {
+    let mask = bits.wrapping_sub(1);
+    [hashes.f1 as usize & mask, hashes.f2 as usize & mask]
+}
+
+/// Return true if both of a key's bits are set in a companion filter.
+///
+/// An empty filter contains every key.
+#[inline]
+pub fn filter_contains(filter: &[u64], hashes: &Hashes) -> bool {
+    filter.is_empty()
+        || filter_bits(hashes, filter.len().wrapping_mul(64))
+            .iter()
+            .all(|&bit| match filter.get(bit / 64) {
+                Some(word) => word >> (bit % 64) & 1 != 0,
+                None => false,
+            })
+}
+
+/// The number of low bits of a char which select its slot within a page of
+/// a `phf::CharMap`. The remaining bits select the page.
+pub const CHAR_PAGE_BITS: u32 = 8;
+
+/// Return the index of the shard of a sharded map which holds a key.
+///
+/// * `hash` is from `hash_with()` in this crate, using the sharded map's own
+///   key rather than that of any shard.
+/// * `shards` is the number of shards.
+#[inline]
+pub fn get_shard(hashes: &Hashes, shards: usize) -> u32 {
+    hashes.g % (shards as u32)
+}
+
+/// Like `get_shard`, but returns `None` rather than panicking if `shards` is
+/// zero.
+///
+/// This has no panicking branches, which lookups rely on.
+#[inline]
+pub fn try_get_shard(hashes: &Hashes, shards: usize) -> Option<u32> {
+    hashes.g.checked_rem(shards as u32)
+}
+
 /// Return an index into `phf_generator::HashState::map`.
 ///
 /// * `hash` is from `hash()` in this crate.
-/// * `disps` is from `phf_generator::HashState::disps`.
+/// * `disps` is from `phf_generator::HashState::disps`, possibly narrowed to
+///   a smaller integer type.
 /// * `len` is the length of `phf_generator::HashState::map`.
 #[inline]
-pub fn get_index(hashes: &Hashes, disps: &[(u32, u32)], len: usize) -> u32 {
+pub fn get_index<D: Copy + Into<u32>>(hashes: &Hashes, disps: &[(D, D)], len: usize) -> u32 {
     let (d1, d2) = disps[(hashes.g % (disps.len() as u32)) as usize];
-    displace(hashes.f1, hashes.f2, d1, d2) % (len as u32)
+    displace(hashes.f1, hashes.f2, d1.into(), d2.into()) % (len as u32)
+}
+
+/// Like `get_index`, but returns `None` rather than panicking if `disps` or
+/// `len` is zero.
+///
+/// This has no panicking branches, which lookups rely on.
+#[inline]
+pub fn try_get_index<D: Copy + Into<u32>>(
+    hashes: &Hashes,
+    disps: &[(D, D)],
+    len: usize,
+) -> Option<u32> {
+    let bucket = hashes.g.checked_rem(disps.len() as u32)?;
+    let &(d1, d2) = disps.get(bucket as usize)?;
+    displace(hashes.f1, hashes.f2, d1.into(), d2.into()).checked_rem(len as u32)
+}
+
+/// The number of displacement pairs per offset of packed displacements.
+pub const PACKED_DISPS_BLOCK: usize = 16;
+
+/// Returns the displacements of `bucket` from packed displacements, or
+/// `None` if they are inconsistent.
+///
+/// * `bytes` holds each displacement as an LEB128 varint, `d1` then `d2` of
+///   every bucket in turn.
+/// * `offsets` holds the position in `bytes` of every
+///   `PACKED_DISPS_BLOCK`th bucket, so that at most that many pairs are
+///   skipped to find one.
+#[inline]
+pub fn get_packed_disps(offsets: &[u32], bytes: &[u8], bucket: usize) -> Option<(u32, u32)> {
+    let mut pos = *offsets.get(bucket / PACKED_DISPS_BLOCK)? as usize;
+    // every varint ends with the only one of its bytes below 0x80
+    let mut skip = bucket % PACKED_DISPS_BLOCK * 2;
+    while skip > 0 {
+        if *bytes.get(pos)? < 0x80 {
+            skip -= 1;
+        }
+        pos += 1;
+    }
+    let d1 = read_varint(bytes, &mut pos)?;
+    let d2 = read_varint(bytes, &mut pos)?;
+    Some((d1, d2))
+}
+
+#[inline]
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut value = 0u32;
+    let mut shift = 0u32;
+    loop {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        value |= u32::from(byte & 0x7f).checked_shl(shift)?;
+        if byte < 0x80 {
+            return Some(value);
+        }
+        shift += 7;
+    }
+}
+
+/// Like `try_get_index`, but with packed displacements of `buckets` buckets,
+/// see `get_packed_disps`.
+#[inline]
+pub fn try_get_packed_index(
+    hashes: &Hashes,
+    buckets: usize,
+    offsets: &[u32],
+    bytes: &[u8],
+    len: usize,
+) -> Option<u32> {
+    let bucket = hashes.g.checked_rem(buckets as u32)?;
+    let (d1, d2) = get_packed_disps(offsets, bytes, bucket as usize)?;
+    displace(hashes.f1, hashes.f2, d1, d2).checked_rem(len as u32)
 }
 
 /// A trait implemented by types which can be used in PHF data structures.
@@ -87,7 +380,7 @@ pub trait FmtConst {
 /// The same semantic requirements apply:
 ///
 /// > In particular `Eq`, `Ord` and `Hash` must be equivalent for borrowed and owned values:
-/// `x.borrow() == y.borrow()` should give the same result as `x == y`.
+/// > `x.borrow() == y.borrow()` should give the same result as `x == y`.
 ///
 /// (This crate's API only requires `Eq` and `PhfHash`, however.)
 ///
@@ -124,6 +417,19 @@ pub trait PhfBorrow<B: ?Sized> {
     fn borrow(&self) -> &B;
 }
 
+/// A trait implemented by small integer-like types which can be stored in a
+/// bit set.
+///
+/// `bit_index` must be a bijection onto a subset of `u64` which preserves
+/// order, and `from_bit_index` its inverse.
+pub trait PhfBitIndex: Sized {
+    /// Returns the index of the bit representing this value.
+    fn bit_index(&self) -> u64;
+
+    /// Returns the value represented by the bit at `index`.
+    fn from_bit_index(index: u64) -> Self;
+}
+
 /// Create an impl of `FmtConst` delegating to `fmt::Debug` for types that can deal with it.
 ///
 /// Ideally with specialization this could be just one default impl and then specialized where
@@ -177,27 +483,66 @@ impl_reflexive!(
     u128,
     i128,
     bool,
-    [u8]
+    Duration,
+    [u8],
+    Option<u8>,
+    Option<i8>,
+    Option<u16>,
+    Option<i16>,
+    Option<u32>,
+    Option<i32>,
+    Option<u64>,
+    Option<i64>,
+    Option<u128>,
+    Option<i128>,
+    Option<bool>,
+    Option<char>
 );
 
-#[cfg(feature = "std")]
+/// Lets a map with `Option<&'static T>` keys be probed with a shorter-lived
+/// `Option<&T>`.
+impl<'a, 'b: 'a, T: ?Sized> PhfBorrow<Option<&'a T>> for Option<&'b T> {
+    fn borrow(&self) -> &Option<&'a T> {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl PhfBorrow<str> for String {
     fn borrow(&self) -> &str {
         self
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl PhfBorrow<[u8]> for Vec<u8> {
     fn borrow(&self) -> &[u8] {
         self
     }
 }
 
+/// Lets a map with `Rc<str>` or `Rc<[u8]>` keys be probed with a `&str` or
+/// `&[u8]`.
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> PhfBorrow<T> for Rc<T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+/// `Arc` is only used with `std`, since `alloc` lacks it on targets without
+/// atomics.
 #[cfg(feature = "std")]
+impl<T: ?Sized> PhfBorrow<T> for Arc<T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
 delegate_debug!(String);
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl PhfHash for String {
     #[inline]
     fn phf_hash<H: Hasher>(&self, state: &mut H) {
@@ -205,7 +550,7 @@ impl PhfHash for String {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl PhfHash for Vec<u8> {
     #[inline]
     fn phf_hash<H: Hasher>(&self, state: &mut H) {
@@ -213,6 +558,22 @@ impl PhfHash for Vec<u8> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T: PhfHash + ?Sized> PhfHash for Rc<T> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PhfHash + ?Sized> PhfHash for Arc<T> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
 impl<'a, T: 'a + PhfHash + ?Sized> PhfHash for &'a T {
     fn phf_hash<H: Hasher>(&self, state: &mut H) {
         (*self).phf_hash(state)
@@ -225,13 +586,13 @@ impl<'a, T: 'a + FmtConst + ?Sized> FmtConst for &'a T {
     }
 }
 
-impl<'a> PhfBorrow<str> for &'a str {
+impl PhfBorrow<str> for &str {
     fn borrow(&self) -> &str {
         self
     }
 }
 
-impl<'a> PhfBorrow<[u8]> for &'a [u8] {
+impl PhfBorrow<[u8]> for &[u8] {
     fn borrow(&self) -> &[u8] {
         self
     }
@@ -251,6 +612,40 @@ impl PhfHash for [u8] {
     }
 }
 
+/// A string which hashes the same as its ASCII lowercase form, without
+/// allocating a lowercase copy.
+#[doc(hidden)]
+pub struct AsciiFolded<'a>(pub &'a str);
+
+impl PhfHash for AsciiFolded<'_> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        // all hashers are streaming, so writing in chunks is the same as
+        // writing the whole lowercase string at once
+        let mut buf = [0; 32];
+        for chunk in self.0.as_bytes().chunks(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            buf.make_ascii_lowercase();
+            state.write(buf);
+        }
+    }
+}
+
+/// A string which hashes the same as `UniCase::unicode` of it, i.e. like its
+/// full Unicode case folding.
+#[cfg(feature = "unicase")]
+#[doc(hidden)]
+pub struct UnicodeFolded<'a>(pub &'a str);
+
+#[cfg(feature = "unicase")]
+impl PhfHash for UnicodeFolded<'_> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        unicase::UniCase::unicode(self.0).hash(state)
+    }
+}
+
 impl FmtConst for [u8] {
     #[inline]
     fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -359,6 +754,116 @@ impl PhfHash for char {
     }
 }
 
+impl PhfHash for Duration {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_secs().phf_hash(state);
+        self.subsec_nanos().phf_hash(state);
+    }
+}
+
+/// The discriminant is hashed first, so that `None` doesn't hash like any
+/// `Some`.
+impl<T: PhfHash> PhfHash for Option<T> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            None => 0u8.phf_hash(state),
+            Some(value) => {
+                1u8.phf_hash(state);
+                value.phf_hash(state);
+            }
+        }
+    }
+}
+
+impl<T: FmtConst> FmtConst for Option<T> {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            None => write!(f, "::core::option::Option::None"),
+            Some(value) => {
+                write!(f, "::core::option::Option::Some(")?;
+                value.fmt_const(f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl FmtConst for Duration {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "::core::time::Duration::new({}, {})",
+            self.as_secs(),
+            self.subsec_nanos()
+        )
+    }
+}
+
+macro_rules! bit_index_impl (
+    (signed $t:ty) => (
+        impl PhfBitIndex for $t {
+            #[inline]
+            fn bit_index(&self) -> u64 {
+                // flip the sign bit so that negative values sort first
+                (*self as i64 as u64) ^ (1 << 63)
+            }
+
+            #[inline]
+            fn from_bit_index(index: u64) -> $t {
+                (index ^ (1 << 63)) as i64 as $t
+            }
+        }
+    );
+    ($t:ty) => (
+        impl PhfBitIndex for $t {
+            #[inline]
+            fn bit_index(&self) -> u64 {
+                *self as u64
+            }
+
+            #[inline]
+            fn from_bit_index(index: u64) -> $t {
+                index as $t
+            }
+        }
+    )
+);
+
+bit_index_impl!(u8);
+bit_index_impl!(signed i8);
+bit_index_impl!(u16);
+bit_index_impl!(signed i16);
+bit_index_impl!(u32);
+bit_index_impl!(signed i32);
+bit_index_impl!(u64);
+bit_index_impl!(signed i64);
+
+impl PhfBitIndex for bool {
+    #[inline]
+    fn bit_index(&self) -> u64 {
+        *self as u64
+    }
+
+    #[inline]
+    fn from_bit_index(index: u64) -> bool {
+        index != 0
+    }
+}
+
+impl PhfBitIndex for char {
+    #[inline]
+    fn bit_index(&self) -> u64 {
+        *self as u64
+    }
+
+    #[inline]
+    fn from_bit_index(index: u64) -> char {
+        core::char::from_u32(index as u32).expect("invalid bit index for `char`")
+    }
+}
+
 // minimize duplicated code since formatting drags in quite a bit
 fn fmt_array(array: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(f, "{:?}", array)
@@ -0,0 +1,230 @@
+//! SipHash-1-3, with 64-bit and 128-bit output, with its rounds written with
+//! vector instructions.
+//!
+//! SipHash's four state words are updated in pairs, `v0` with `v2` and `v1`
+//! with `v3`, so each half of a round is a couple of two-lane operations. The
+//! hasher here buffers bytes exactly like `siphasher`, whose output it must
+//! match bit for bit: tables are generated on the host and probed on the
+//! target, and only one of them may have a vector backend.
+//!
+//! A backend is picked at compile time from the target features, and targets
+//! without one use `siphasher` as before. There is no SSE2 backend, which
+//! ran at half the speed of the scalar code on x86_64.
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[path = "simd/neon.rs"]
+mod imp;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[path = "simd/wasm.rs"]
+mod imp;
+
+#[cfg(not(any(
+    all(target_arch = "aarch64", target_feature = "neon"),
+    all(target_arch = "wasm32", target_feature = "simd128"),
+)))]
+pub(crate) use siphasher::{sip::SipHasher13 as Sip64Hasher13, sip128::SipHasher13};
+
+#[cfg(any(
+    all(target_arch = "aarch64", target_feature = "neon"),
+    all(target_arch = "wasm32", target_feature = "simd128"),
+))]
+pub(crate) use self::hasher::{Sip64Hasher13, SipHasher13};
+
+#[cfg(any(
+    all(target_arch = "aarch64", target_feature = "neon"),
+    all(target_arch = "wasm32", target_feature = "simd128"),
+))]
+mod hasher {
+    use super::imp::{add, lanes, pair, rotl, swap, xor, Vector};
+    use core::hash::Hasher;
+    use siphasher::sip128::{Hash128, Hasher128};
+
+    /// The SipHash state as `(v0, v2)` and `(v1, v3)`.
+    #[derive(Clone, Copy)]
+    struct State {
+        v02: Vector,
+        v13: Vector,
+    }
+
+    impl State {
+        #[inline]
+        fn round(&mut self) {
+            // v0 += v1; v1 = v1 <<< 13; v1 ^= v0; v0 = v0 <<< 32
+            // v2 += v3; v3 = v3 <<< 16; v3 ^= v2
+            self.v02 = add(self.v02, self.v13);
+            self.v13 = xor(rotl(self.v13, 13, 16), self.v02);
+            self.v02 = rotl(self.v02, 32, 0);
+            // v0 += v3; v3 = v3 <<< 21; v3 ^= v0
+            // v2 += v1; v1 = v1 <<< 17; v1 ^= v2; v2 = v2 <<< 32
+            let v31 = swap(self.v13);
+            self.v02 = add(self.v02, v31);
+            self.v13 = swap(xor(rotl(v31, 21, 17), self.v02));
+            self.v02 = rotl(self.v02, 0, 32);
+        }
+
+        #[inline]
+        fn compress(&mut self, m: u64) {
+            self.v13 = xor(self.v13, pair(0, m));
+            self.round();
+            self.v02 = xor(self.v02, pair(m, 0));
+        }
+
+        #[inline]
+        fn fold(&self) -> u64 {
+            let [a, b] = lanes(xor(self.v02, self.v13));
+            a ^ b
+        }
+    }
+
+    /// The state and unprocessed bytes of either output width.
+    #[derive(Clone, Copy)]
+    struct Buffer {
+        state: State,
+        length: usize,
+        // unprocessed bytes, little endian
+        tail: u64,
+        ntail: usize,
+    }
+
+    impl Buffer {
+        /// `wide` selects the initial state of 128-bit output.
+        #[inline]
+        fn new(k0: u64, k1: u64, wide: bool) -> Buffer {
+            let v1 = if wide {
+                0x646f_7261_6e64_6f83
+            } else {
+                0x646f_7261_6e64_6f6d
+            };
+            Buffer {
+                state: State {
+                    v02: pair(k0 ^ 0x736f_6d65_7073_6575, k0 ^ 0x6c79_6765_6e65_7261),
+                    v13: pair(k1 ^ v1, k1 ^ 0x7465_6462_7974_6573),
+                },
+                length: 0,
+                tail: 0,
+                ntail: 0,
+            }
+        }
+
+        /// Returns the state once the last block, holding the length, is
+        /// compressed.
+        #[inline]
+        fn last(&self) -> State {
+            let mut state = self.state;
+            state.compress(((self.length as u64 & 0xff) << 56) | self.tail);
+            state
+        }
+    }
+
+    /// A drop-in replacement for `siphasher::sip128::SipHasher13`.
+    #[derive(Clone, Copy)]
+    pub(crate) struct SipHasher13(Buffer);
+
+    impl SipHasher13 {
+        #[inline]
+        pub(crate) fn new_with_keys(k0: u64, k1: u64) -> SipHasher13 {
+            SipHasher13(Buffer::new(k0, k1, true))
+        }
+    }
+
+    /// A drop-in replacement for `siphasher::sip::SipHasher13`.
+    #[derive(Clone, Copy)]
+    pub(crate) struct Sip64Hasher13(Buffer);
+
+    impl Sip64Hasher13 {
+        #[inline]
+        pub(crate) fn new_with_keys(k0: u64, k1: u64) -> Sip64Hasher13 {
+            Sip64Hasher13(Buffer::new(k0, k1, false))
+        }
+    }
+
+    /// Reads up to 8 bytes as a little endian integer.
+    // a loop, since copying a variable length slice calls `memcpy`
+    #[inline]
+    fn read_le(bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .rev()
+            .fold(0, |word, &byte| (word << 8) | u64::from(byte))
+    }
+
+    impl Buffer {
+        #[inline]
+        fn write(&mut self, mut bytes: &[u8]) {
+            self.length = self.length.wrapping_add(bytes.len());
+
+            if self.ntail != 0 {
+                let needed = 8 - self.ntail;
+                let (head, rest) = bytes.split_at(needed.min(bytes.len()));
+                self.tail |= read_le(head) << (8 * self.ntail);
+                if head.len() < needed {
+                    self.ntail += head.len();
+                    return;
+                }
+                self.state.compress(self.tail);
+                bytes = rest;
+            }
+
+            let mut chunks = bytes.chunks_exact(8);
+            for chunk in &mut chunks {
+                let mut word = [0; 8];
+                word.copy_from_slice(chunk);
+                self.state.compress(u64::from_le_bytes(word));
+            }
+            let rest = chunks.remainder();
+            self.tail = read_le(rest);
+            self.ntail = rest.len();
+        }
+    }
+
+    impl Hasher for SipHasher13 {
+        #[inline]
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.write(bytes);
+        }
+
+        #[inline]
+        fn finish(&self) -> u64 {
+            self.finish128().h2
+        }
+    }
+
+    impl Hasher128 for SipHasher13 {
+        #[inline]
+        fn finish128(&self) -> Hash128 {
+            let mut state = self.0.last();
+
+            state.v02 = xor(state.v02, pair(0, 0xee));
+            for _ in 0..3 {
+                state.round();
+            }
+            let h1 = state.fold();
+
+            state.v13 = xor(state.v13, pair(0xdd, 0));
+            for _ in 0..3 {
+                state.round();
+            }
+            let h2 = state.fold();
+
+            Hash128 { h1, h2 }
+        }
+    }
+
+    impl Hasher for Sip64Hasher13 {
+        #[inline]
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.write(bytes);
+        }
+
+        #[inline]
+        fn finish(&self) -> u64 {
+            let mut state = self.0.last();
+            state.v02 = xor(state.v02, pair(0, 0xff));
+            for _ in 0..3 {
+                state.round();
+            }
+            state.fold()
+        }
+    }
+}
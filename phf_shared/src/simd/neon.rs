@@ -0,0 +1,47 @@
+//! Vector operations on AArch64 with NEON.
+// some of these intrinsics are safe when NEON is enabled for the whole crate
+#![allow(unused_unsafe)]
+
+use core::arch::aarch64::*;
+
+pub(super) type Vector = uint64x2_t;
+
+#[inline]
+pub(super) fn pair(lo: u64, hi: u64) -> Vector {
+    unsafe { vld1q_u64([lo, hi].as_ptr()) }
+}
+
+#[inline]
+pub(super) fn lanes(v: Vector) -> [u64; 2] {
+    let mut lanes = [0; 2];
+    unsafe { vst1q_u64(lanes.as_mut_ptr(), v) };
+    lanes
+}
+
+#[inline]
+pub(super) fn add(a: Vector, b: Vector) -> Vector {
+    unsafe { vaddq_u64(a, b) }
+}
+
+#[inline]
+pub(super) fn xor(a: Vector, b: Vector) -> Vector {
+    unsafe { veorq_u64(a, b) }
+}
+
+#[inline]
+pub(super) fn swap(v: Vector) -> Vector {
+    unsafe { vextq_u64::<1>(v, v) }
+}
+
+/// Rotates the low lane left by `lo` bits and the high lane by `hi` bits.
+#[inline]
+pub(super) fn rotl(v: Vector, lo: u32, hi: u32) -> Vector {
+    let (lo, hi) = (i64::from(lo), i64::from(hi));
+    unsafe {
+        // negative counts shift right, and shifting by 64 clears a lane, so
+        // a count of 0 leaves it as is
+        let left = vshlq_u64(v, vld1q_s64([lo, hi].as_ptr()));
+        let right = vshlq_u64(v, vld1q_s64([lo - 64, hi - 64].as_ptr()));
+        vorrq_u64(left, right)
+    }
+}
@@ -0,0 +1,38 @@
+//! Vector operations on WebAssembly with simd128.
+
+use core::arch::wasm32::*;
+
+pub(super) type Vector = v128;
+
+#[inline]
+pub(super) fn pair(lo: u64, hi: u64) -> Vector {
+    u64x2(lo, hi)
+}
+
+#[inline]
+pub(super) fn lanes(v: Vector) -> [u64; 2] {
+    [u64x2_extract_lane::<0>(v), u64x2_extract_lane::<1>(v)]
+}
+
+#[inline]
+pub(super) fn add(a: Vector, b: Vector) -> Vector {
+    u64x2_add(a, b)
+}
+
+#[inline]
+pub(super) fn xor(a: Vector, b: Vector) -> Vector {
+    v128_xor(a, b)
+}
+
+#[inline]
+pub(super) fn swap(v: Vector) -> Vector {
+    i64x2_shuffle::<1, 0>(v, v)
+}
+
+/// Rotates the low lane left by `lo` bits and the high lane by `hi` bits.
+#[inline]
+pub(super) fn rotl(v: Vector, lo: u32, hi: u32) -> Vector {
+    // shift counts are taken modulo 64, so a count of 0 leaves a lane as is
+    let rotate = |n: u32| v128_or(u64x2_shl(v, n), u64x2_shr(v, 64 - n));
+    i64x2_shuffle::<0, 3>(rotate(lo), rotate(hi))
+}